@@ -0,0 +1,6 @@
+//! Runs the root crate's NEP-141 test suite against this crate's build of the contract.
+
+extern crate medi_token_near as medi_token;
+
+#[path = "../../src/tests/near_token_tests.rs"]
+mod near_token_tests;
@@ -0,0 +1,9 @@
+//! MediToken provider payment escrow packaged as a standalone wasm crate.
+//!
+//! The contract lives in the root crate (`src/near_payment_escrow.rs`) next to the token,
+//! whose test suite exercises the two together.
+
+#[path = "../../src/near_payment_escrow.rs"]
+pub mod near_payment_escrow;
+
+pub use near_payment_escrow::ProviderPaymentEscrow;
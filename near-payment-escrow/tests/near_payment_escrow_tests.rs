@@ -0,0 +1,10 @@
+//! Runs the root crate's escrow test suite against this crate's build of the contract,
+//! paid through the token built by `near-contract`.
+
+mod medi_token {
+    pub use medi_token_escrow_near::near_payment_escrow;
+    pub use medi_token_near::MediTokenNEP141;
+}
+
+// Included rather than `#[path]`-mounted so the suite's `medi_token::` imports resolve to the module above
+include!("../../src/tests/near_payment_escrow_tests.rs");
@@ -0,0 +1,526 @@
+//! This module is not available for WASM targets
+#![cfg(not(target_arch = "wasm32"))]
+
+use primitive_types::U256;
+use serde_json::Value;
+use crate::abi::get_meditoken_abi;
+use crate::error::{MediTokenError, Result};
+use crate::rpc::Log;
+use crate::token::MediTokenEvent;
+use crate::utils::Utils;
+
+/// Solidity types supported by the codec
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbiType {
+    Address,
+    /// Unsigned integer with the given bit width
+    Uint(usize),
+    Bool,
+    String,
+    /// Dynamic array `T[]`
+    Array(Box<AbiType>),
+}
+
+/// Decoded (or to-be-encoded) ABI value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbiValue {
+    /// Lowercase 0x-prefixed address
+    Address(String),
+    Uint(U256),
+    Bool(bool),
+    String(String),
+    Array(Vec<AbiValue>),
+}
+
+/// Named parameter of a function, event or error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbiParam {
+    pub name: String,
+    pub kind: AbiType,
+    pub indexed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbiFunction {
+    pub name: String,
+    pub inputs: Vec<AbiParam>,
+    pub outputs: Vec<AbiParam>,
+    pub state_mutability: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbiEvent {
+    pub name: String,
+    pub inputs: Vec<AbiParam>,
+    pub anonymous: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbiError {
+    pub name: String,
+    pub inputs: Vec<AbiParam>,
+}
+
+/// Contract interface parsed from a JSON ABI
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Abi {
+    pub constructor: Option<Vec<AbiParam>>,
+    pub functions: Vec<AbiFunction>,
+    pub events: Vec<AbiEvent>,
+    pub errors: Vec<AbiError>,
+}
+
+impl AbiType {
+    /// Parses a Solidity type name such as `uint256` or `address[]`
+    pub fn parse(name: &str) -> Result<Self> {
+        if let Some(inner) = name.strip_suffix("[]") {
+            return Ok(AbiType::Array(Box::new(Self::parse(inner)?)));
+        }
+        match name {
+            "address" => Ok(AbiType::Address),
+            "bool" => Ok(AbiType::Bool),
+            "string" => Ok(AbiType::String),
+            "uint" => Ok(AbiType::Uint(256)),
+            _ => {
+                let bits = name
+                    .strip_prefix("uint")
+                    .and_then(|bits| bits.parse::<usize>().ok())
+                    .filter(|bits| *bits > 0 && *bits <= 256 && bits % 8 == 0)
+                    .ok_or_else(|| abi_error(format!("Unsupported ABI type: {}", name)))?;
+                Ok(AbiType::Uint(bits))
+            }
+        }
+    }
+
+    /// Canonical type name used in signatures
+    pub fn canonical(&self) -> String {
+        match self {
+            AbiType::Address => "address".to_string(),
+            AbiType::Uint(bits) => format!("uint{}", bits),
+            AbiType::Bool => "bool".to_string(),
+            AbiType::String => "string".to_string(),
+            AbiType::Array(inner) => format!("{}[]", inner.canonical()),
+        }
+    }
+
+    pub fn is_dynamic(&self) -> bool {
+        matches!(self, AbiType::String | AbiType::Array(_))
+    }
+}
+
+impl AbiValue {
+    /// Checks that the value can be encoded as `kind`
+    fn check(&self, kind: &AbiType) -> Result<()> {
+        match (self, kind) {
+            (AbiValue::Address(address), AbiType::Address) if Utils::is_valid_address(address) => Ok(()),
+            (AbiValue::Address(address), AbiType::Address) => {
+                Err(MediTokenError::InvalidAddress { address: address.clone() })
+            }
+            (AbiValue::Uint(value), AbiType::Uint(bits)) if value.bits() <= *bits => Ok(()),
+            (AbiValue::Uint(_), AbiType::Uint(_)) => Err(MediTokenError::ArithmeticOverflow),
+            (AbiValue::Bool(_), AbiType::Bool) | (AbiValue::String(_), AbiType::String) => Ok(()),
+            (AbiValue::Array(items), AbiType::Array(inner)) => items.iter().try_for_each(|item| item.check(inner)),
+            _ => Err(abi_error(format!("Value {:?} does not match type {}", self, kind.canonical()))),
+        }
+    }
+
+    pub fn as_uint(&self) -> Result<U256> {
+        match self {
+            AbiValue::Uint(value) => Ok(*value),
+            other => Err(abi_error(format!("Expected uint, got {:?}", other))),
+        }
+    }
+
+    pub fn as_address(&self) -> Result<String> {
+        match self {
+            AbiValue::Address(address) => Ok(address.clone()),
+            other => Err(abi_error(format!("Expected address, got {:?}", other))),
+        }
+    }
+
+    pub fn as_bool(&self) -> Result<bool> {
+        match self {
+            AbiValue::Bool(value) => Ok(*value),
+            other => Err(abi_error(format!("Expected bool, got {:?}", other))),
+        }
+    }
+
+    pub fn as_string(&self) -> Result<String> {
+        match self {
+            AbiValue::String(value) => Ok(value.clone()),
+            other => Err(abi_error(format!("Expected string, got {:?}", other))),
+        }
+    }
+}
+
+impl Abi {
+    /// Parses a JSON ABI array
+    pub fn from_json(json: &Value) -> Result<Self> {
+        let items = json.as_array().ok_or_else(|| abi_error("ABI must be a JSON array"))?;
+        let mut abi = Abi::default();
+
+        for item in items {
+            let name = item["name"].as_str().unwrap_or_default().to_string();
+            match item["type"].as_str() {
+                Some("constructor") => abi.constructor = Some(parse_params(&item["inputs"])?),
+                Some("function") => abi.functions.push(AbiFunction {
+                    name,
+                    inputs: parse_params(&item["inputs"])?,
+                    outputs: parse_params(&item["outputs"])?,
+                    state_mutability: item["stateMutability"].as_str().unwrap_or("nonpayable").to_string(),
+                }),
+                Some("event") => abi.events.push(AbiEvent {
+                    name,
+                    inputs: parse_params(&item["inputs"])?,
+                    anonymous: item["anonymous"].as_bool().unwrap_or(false),
+                }),
+                Some("error") => abi.errors.push(AbiError {
+                    name,
+                    inputs: parse_params(&item["inputs"])?,
+                }),
+                // fallback / receive carry nothing to encode
+                _ => {}
+            }
+        }
+
+        Ok(abi)
+    }
+
+    /// The MediToken ABI from `abi.rs`
+    pub fn meditoken() -> Self {
+        Self::from_json(&get_meditoken_abi()).expect("bundled MediToken ABI is valid")
+    }
+
+    pub fn function(&self, name: &str) -> Result<&AbiFunction> {
+        self.functions
+            .iter()
+            .find(|function| function.name == name)
+            .ok_or_else(|| abi_error(format!("Function {} not found in ABI", name)))
+    }
+
+    pub fn event(&self, name: &str) -> Result<&AbiEvent> {
+        self.events
+            .iter()
+            .find(|event| event.name == name)
+            .ok_or_else(|| abi_error(format!("Event {} not found in ABI", name)))
+    }
+
+    pub fn error(&self, name: &str) -> Result<&AbiError> {
+        self.errors
+            .iter()
+            .find(|error| error.name == name)
+            .ok_or_else(|| abi_error(format!("Error {} not found in ABI", name)))
+    }
+
+    /// Finds the custom error whose selector prefixes `data`
+    pub fn error_by_selector(&self, data: &[u8]) -> Option<&AbiError> {
+        let selector = data.get(..4)?;
+        self.errors.iter().find(|error| error.selector() == selector)
+    }
+
+    /// Finds the event whose topic hash matches `topic0`
+    pub fn event_by_topic(&self, topic0: &[u8; 32]) -> Option<&AbiEvent> {
+        self.events
+            .iter()
+            .find(|event| !event.anonymous && &event.topic() == topic0)
+    }
+}
+
+impl AbiFunction {
+    pub fn signature(&self) -> String {
+        signature(&self.name, &self.inputs)
+    }
+
+    pub fn selector(&self) -> [u8; 4] {
+        selector(&self.signature())
+    }
+
+    /// Selector followed by the encoded arguments
+    pub fn encode_call(&self, args: &[AbiValue]) -> Result<Vec<u8>> {
+        let mut data = self.selector().to_vec();
+        data.extend(encode_params(&self.inputs, args)?);
+        Ok(data)
+    }
+
+    /// Decodes calldata produced by `encode_call`
+    pub fn decode_input(&self, data: &[u8]) -> Result<Vec<AbiValue>> {
+        if data.get(..4) != Some(&self.selector()[..]) {
+            return Err(abi_error(format!("Calldata does not start with the {} selector", self.name)));
+        }
+        decode(&param_types(&self.inputs), &data[4..])
+    }
+
+    pub fn decode_output(&self, data: &[u8]) -> Result<Vec<AbiValue>> {
+        decode(&param_types(&self.outputs), data)
+    }
+}
+
+impl AbiEvent {
+    pub fn signature(&self) -> String {
+        signature(&self.name, &self.inputs)
+    }
+
+    /// Topic 0 of logs emitted by this event
+    pub fn topic(&self) -> [u8; 32] {
+        Utils::keccak256(self.signature().as_bytes())
+    }
+
+    /// Decodes a log into named values, in declaration order
+    pub fn decode_log(&self, log: &Log) -> Result<Vec<(String, AbiValue)>> {
+        let topics = log
+            .topics
+            .iter()
+            .map(|topic| decode_hex(topic))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut topic_index = 0;
+        if !self.anonymous {
+            if topics.first().map(|topic| &topic[..]) != Some(&self.topic()[..]) {
+                return Err(abi_error(format!("Log is not a {} event", self.name)));
+            }
+            topic_index = 1;
+        }
+
+        let data_params: Vec<AbiParam> = self.inputs.iter().filter(|p| !p.indexed).cloned().collect();
+        let mut data_values = decode(&param_types(&data_params), &decode_hex(&log.data)?)?.into_iter();
+
+        let mut values = Vec::with_capacity(self.inputs.len());
+        for param in &self.inputs {
+            let value = if param.indexed {
+                if param.kind.is_dynamic() {
+                    return Err(abi_error(format!("Indexed dynamic parameter {} is not supported", param.name)));
+                }
+                let topic = topics
+                    .get(topic_index)
+                    .ok_or_else(|| abi_error(format!("Log is missing topic for {}", param.name)))?;
+                topic_index += 1;
+                decode(&[param.kind.clone()], topic)?.remove(0)
+            } else {
+                data_values.next().expect("one decoded value per data parameter")
+            };
+            values.push((param.name.clone(), value));
+        }
+
+        Ok(values)
+    }
+}
+
+impl AbiError {
+    pub fn signature(&self) -> String {
+        signature(&self.name, &self.inputs)
+    }
+
+    pub fn selector(&self) -> [u8; 4] {
+        selector(&self.signature())
+    }
+
+    /// Decodes revert data that starts with this error's selector
+    pub fn decode(&self, data: &[u8]) -> Result<Vec<AbiValue>> {
+        if data.get(..4) != Some(&self.selector()[..]) {
+            return Err(abi_error(format!("Revert data is not a {} error", self.name)));
+        }
+        decode(&param_types(&self.inputs), &data[4..])
+    }
+}
+
+/// Encodes values as an ABI tuple
+pub fn encode(values: &[AbiValue]) -> Result<Vec<u8>> {
+    let head_size: usize = 32 * values.len();
+    let mut head = Vec::with_capacity(head_size);
+    let mut tail = Vec::new();
+
+    for value in values {
+        match value {
+            AbiValue::String(_) | AbiValue::Array(_) => {
+                head.extend(uint_word(U256::from(head_size + tail.len())));
+                tail.extend(encode_dynamic(value)?);
+            }
+            _ => head.extend(encode_static(value)?),
+        }
+    }
+
+    head.extend(tail);
+    Ok(head)
+}
+
+/// Decodes an ABI tuple of the given types
+pub fn decode(types: &[AbiType], data: &[u8]) -> Result<Vec<AbiValue>> {
+    types
+        .iter()
+        .enumerate()
+        .map(|(index, kind)| {
+            if kind.is_dynamic() {
+                let offset = read_usize(data, index * 32)?;
+                decode_dynamic(kind, data.get(offset..).ok_or_else(|| too_short(data))?)
+            } else {
+                decode_static(kind, read_word(data, index * 32)?)
+            }
+        })
+        .collect()
+}
+
+/// Decodes a `Transfer` or `Approval` log into a `MediTokenEvent`
+pub fn decode_meditoken_event(abi: &Abi, log: &Log) -> Result<MediTokenEvent> {
+    let topic0 = log
+        .topics
+        .first()
+        .ok_or_else(|| abi_error("Log has no topics"))
+        .and_then(|topic| decode_hex(topic))?;
+    let topic0: [u8; 32] = topic0
+        .try_into()
+        .map_err(|_| abi_error("Topic is not 32 bytes"))?;
+    let event = abi
+        .event_by_topic(&topic0)
+        .ok_or_else(|| abi_error("Log does not match any event in the ABI"))?;
+    let values = event.decode_log(log)?;
+    if values.len() < 3 {
+        return Err(abi_error(format!("Event {} has {} inputs, expected 3", event.name, values.len())));
+    }
+
+    match event.name.as_str() {
+        "Transfer" => Ok(MediTokenEvent::Transfer {
+            from: values[0].1.as_address()?,
+            to: values[1].1.as_address()?,
+            value: values[2].1.as_uint()?,
+        }),
+        "Approval" => Ok(MediTokenEvent::Approval {
+            owner: values[0].1.as_address()?,
+            spender: values[1].1.as_address()?,
+            value: values[2].1.as_uint()?,
+        }),
+        other => Err(abi_error(format!("Event {} has no MediTokenEvent equivalent", other))),
+    }
+}
+
+fn encode_params(params: &[AbiParam], args: &[AbiValue]) -> Result<Vec<u8>> {
+    if params.len() != args.len() {
+        return Err(abi_error(format!("Expected {} arguments, got {}", params.len(), args.len())));
+    }
+    for (param, arg) in params.iter().zip(args) {
+        arg.check(&param.kind)?;
+    }
+    encode(args)
+}
+
+fn encode_static(value: &AbiValue) -> Result<[u8; 32]> {
+    match value {
+        AbiValue::Address(address) => {
+            let bytes = address
+                .strip_prefix("0x")
+                .and_then(|digits| hex::decode(digits).ok())
+                .filter(|bytes| bytes.len() == 20)
+                .ok_or_else(|| MediTokenError::InvalidAddress { address: address.clone() })?;
+            let mut word = [0u8; 32];
+            word[12..].copy_from_slice(&bytes);
+            Ok(word)
+        }
+        AbiValue::Uint(value) => Ok(uint_word(*value)),
+        AbiValue::Bool(value) => Ok(uint_word(U256::from(*value as u8))),
+        AbiValue::String(_) | AbiValue::Array(_) => unreachable!("dynamic values are encoded in the tail"),
+    }
+}
+
+fn encode_dynamic(value: &AbiValue) -> Result<Vec<u8>> {
+    match value {
+        AbiValue::String(value) => {
+            let mut out = uint_word(U256::from(value.len())).to_vec();
+            out.extend_from_slice(value.as_bytes());
+            out.resize(32 + value.len().div_ceil(32) * 32, 0);
+            Ok(out)
+        }
+        AbiValue::Array(items) => {
+            let mut out = uint_word(U256::from(items.len())).to_vec();
+            out.extend(encode(items)?);
+            Ok(out)
+        }
+        _ => unreachable!("static values are encoded in the head"),
+    }
+}
+
+fn decode_static(kind: &AbiType, word: &[u8]) -> Result<AbiValue> {
+    let value = U256::from_big_endian(word);
+    match kind {
+        AbiType::Address if value.bits() <= 160 => Ok(AbiValue::Address(format!("0x{}", hex::encode(&word[12..])))),
+        AbiType::Uint(bits) if value.bits() <= *bits => Ok(AbiValue::Uint(value)),
+        AbiType::Bool if value <= U256::one() => Ok(AbiValue::Bool(value == U256::one())),
+        _ => Err(abi_error(format!("Invalid {} word: 0x{}", kind.canonical(), hex::encode(word)))),
+    }
+}
+
+fn decode_dynamic(kind: &AbiType, data: &[u8]) -> Result<AbiValue> {
+    let len = read_usize(data, 0)?;
+    match kind {
+        AbiType::String => {
+            let bytes = data.get(32..32 + len).ok_or_else(|| too_short(data))?;
+            String::from_utf8(bytes.to_vec())
+                .map(AbiValue::String)
+                .map_err(|_| abi_error("String is not valid UTF-8"))
+        }
+        AbiType::Array(inner) => {
+            let types = vec![(**inner).clone(); len];
+            decode(&types, &data[32..]).map(AbiValue::Array)
+        }
+        _ => unreachable!("static types are decoded from the head"),
+    }
+}
+
+fn read_word(data: &[u8], offset: usize) -> Result<&[u8]> {
+    data.get(offset..offset + 32).ok_or_else(|| too_short(data))
+}
+
+fn read_usize(data: &[u8], offset: usize) -> Result<usize> {
+    let value = U256::from_big_endian(read_word(data, offset)?);
+    if value > U256::from(data.len()) {
+        return Err(too_short(data));
+    }
+    Ok(value.as_usize())
+}
+
+fn uint_word(value: U256) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    value.to_big_endian(&mut word);
+    word
+}
+
+fn parse_params(json: &Value) -> Result<Vec<AbiParam>> {
+    json.as_array()
+        .map(|params| {
+            params
+                .iter()
+                .map(|param| {
+                    Ok(AbiParam {
+                        name: param["name"].as_str().unwrap_or_default().to_string(),
+                        kind: AbiType::parse(param["type"].as_str().unwrap_or_default())?,
+                        indexed: param["indexed"].as_bool().unwrap_or(false),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_else(|| Ok(Vec::new()))
+}
+
+fn param_types(params: &[AbiParam]) -> Vec<AbiType> {
+    params.iter().map(|param| param.kind.clone()).collect()
+}
+
+fn signature(name: &str, params: &[AbiParam]) -> String {
+    let types: Vec<String> = params.iter().map(|param| param.kind.canonical()).collect();
+    format!("{}({})", name, types.join(","))
+}
+
+fn selector(signature: &str) -> [u8; 4] {
+    let hash = Utils::keccak256(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+fn decode_hex(value: &str) -> Result<Vec<u8>> {
+    hex::decode(value.trim_start_matches("0x")).map_err(|_| abi_error(format!("Invalid hex: {}", value)))
+}
+
+fn too_short(data: &[u8]) -> MediTokenError {
+    abi_error(format!("ABI data too short: {} bytes", data.len()))
+}
+
+fn abi_error(message: impl Into<String>) -> MediTokenError {
+    MediTokenError::AbiError { message: message.into() }
+}
@@ -0,0 +1,105 @@
+//! This module is not available for WASM targets
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::collections::{BTreeSet, HashMap};
+use serde::{Deserialize, Serialize};
+use crate::error::{MediTokenError, Result};
+
+/// Role that administers every role without an explicit admin
+pub const DEFAULT_ADMIN_ROLE: &str = "admin";
+/// Role allowed to mint and burn tokens
+pub const MINTER_ROLE: &str = "minter";
+/// Role allowed to pause and unpause transfers
+pub const PAUSER_ROLE: &str = "pauser";
+/// Role held by healthcare providers (doctors, hospitals, pharmacies)
+pub const PROVIDER_ROLE: &str = "provider";
+/// Role held by registered patients
+pub const PATIENT_ROLE: &str = "patient";
+/// Role allowed to freeze and unfreeze accounts
+pub const COMPLIANCE_ROLE: &str = "compliance";
+/// Role allowed to take balance snapshots
+pub const SNAPSHOT_ROLE: &str = "snapshot";
+/// Role allowed to settle disputed appointment escrows
+pub const ARBITER_ROLE: &str = "arbiter";
+
+/// Members and admin role of a single role
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct RoleData {
+    members: BTreeSet<String>,
+    admin_role: Option<String>,
+}
+
+/// OpenZeppelin-style role registry
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccessControl {
+    roles: HashMap<String, RoleData>,
+}
+
+impl AccessControl {
+    /// Creates an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks whether an account holds a role
+    pub fn has_role(&self, role: &str, account: &str) -> bool {
+        self.roles
+            .get(role)
+            .map(|data| data.members.contains(account))
+            .unwrap_or(false)
+    }
+
+    /// Gets the role that administers `role`
+    pub fn get_role_admin(&self, role: &str) -> String {
+        self.roles
+            .get(role)
+            .and_then(|data| data.admin_role.clone())
+            .unwrap_or_else(|| DEFAULT_ADMIN_ROLE.to_string())
+    }
+
+    /// Lists the members of a role in sorted order
+    pub fn role_members(&self, role: &str) -> Vec<String> {
+        self.roles
+            .get(role)
+            .map(|data| data.members.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Fails with `MissingRole` unless the account holds the role
+    pub fn check_role(&self, role: &str, account: &str) -> Result<()> {
+        if !self.has_role(role, account) {
+            return Err(MediTokenError::MissingRole {
+                account: account.to_string(),
+                role: role.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Adds an account to a role without checking the caller.
+    /// Returns whether the account was newly added.
+    pub fn grant_role_unchecked(&mut self, role: &str, account: &str) -> bool {
+        self.roles
+            .entry(role.to_string())
+            .or_default()
+            .members
+            .insert(account.to_string())
+    }
+
+    /// Removes an account from a role without checking the caller.
+    /// Returns whether the account held the role.
+    pub fn revoke_role_unchecked(&mut self, role: &str, account: &str) -> bool {
+        self.roles
+            .get_mut(role)
+            .map(|data| data.members.remove(account))
+            .unwrap_or(false)
+    }
+
+    /// Sets the admin role of `role` without checking the caller.
+    /// Returns the previous admin role.
+    pub fn set_role_admin_unchecked(&mut self, role: &str, admin_role: &str) -> String {
+        let previous = self.get_role_admin(role);
+        self.roles.entry(role.to_string()).or_default().admin_role = Some(admin_role.to_string());
+        previous
+    }
+}
@@ -0,0 +1,112 @@
+//! This module is not available for WASM targets
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::fmt;
+use std::str::FromStr;
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+use crate::error::{MediTokenError, Result};
+
+/// Largest number of decimals representable by a 256-bit amount
+pub const MAX_DECIMALS: u8 = 77;
+/// Denominator of shares expressed in basis points, such as fees and quorums
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+/// Exact token amount: a raw integer value scaled by `10^decimals`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct TokenAmount {
+    raw: U256,
+    decimals: u8,
+}
+
+impl TokenAmount {
+    /// Wraps a raw (smallest unit) amount
+    pub fn from_raw(raw: U256, decimals: u8) -> Result<Self> {
+        if decimals > MAX_DECIMALS {
+            return Err(MediTokenError::InvalidAmount { amount: raw.to_string() });
+        }
+        Ok(Self { raw, decimals })
+    }
+
+    /// Parses a decimal string such as "123.456789012345678901"
+    pub fn parse(amount: &str, decimals: u8) -> Result<Self> {
+        if decimals > MAX_DECIMALS {
+            return Err(MediTokenError::InvalidAmount { amount: amount.to_string() });
+        }
+
+        let trimmed = amount.trim();
+        let (whole, fraction) = match trimmed.split_once('.') {
+            Some((whole, fraction)) => (whole, fraction),
+            None => (trimmed, ""),
+        };
+
+        let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+        if (whole.is_empty() && fraction.is_empty()) || !is_digits(whole) || !is_digits(fraction) {
+            return Err(MediTokenError::InvalidAmount { amount: amount.to_string() });
+        }
+        if fraction.len() > decimals as usize {
+            return Err(MediTokenError::TooManyDecimals {
+                amount: amount.to_string(),
+                decimals,
+            });
+        }
+
+        let scale = U256::exp10(decimals as usize);
+        let whole = Self::parse_digits(whole)?;
+        let fraction_scale = U256::exp10(decimals as usize - fraction.len());
+        let fraction = Self::parse_digits(fraction)?
+            .checked_mul(fraction_scale)
+            .ok_or(MediTokenError::ArithmeticOverflow)?;
+
+        let raw = whole
+            .checked_mul(scale)
+            .and_then(|whole| whole.checked_add(fraction))
+            .ok_or(MediTokenError::ArithmeticOverflow)?;
+
+        Ok(Self { raw, decimals })
+    }
+
+    /// Raw amount in the smallest unit
+    pub fn raw(&self) -> U256 {
+        self.raw
+    }
+
+    /// Number of decimals this amount is scaled by
+    pub fn decimals(&self) -> u8 {
+        self.decimals
+    }
+
+    fn parse_digits(digits: &str) -> Result<U256> {
+        if digits.is_empty() {
+            return Ok(U256::zero());
+        }
+        U256::from_dec_str(digits).map_err(|_| MediTokenError::ArithmeticOverflow)
+    }
+}
+
+impl fmt::Display for TokenAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (whole, fraction) = self.raw.div_mod(U256::exp10(self.decimals as usize));
+        if fraction.is_zero() {
+            return write!(f, "{}", whole);
+        }
+
+        let fraction = format!("{:0>width$}", fraction, width = self.decimals as usize);
+        write!(f, "{}.{}", whole, fraction.trim_end_matches('0'))
+    }
+}
+
+impl FromStr for TokenAmount {
+    type Err = MediTokenError;
+
+    /// Parses using the MediToken default of 18 decimals
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s, 18)
+    }
+}
+
+impl From<TokenAmount> for U256 {
+    fn from(amount: TokenAmount) -> Self {
+        amount.raw
+    }
+}
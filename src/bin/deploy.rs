@@ -0,0 +1,78 @@
+use anyhow::Result;
+use clap::Parser;
+use medi_token::deployment::{Deployer, Network};
+use medi_token::registry::{DeploymentRecord, DeploymentRegistry, CONTRACT_VERSION, DEFAULT_REGISTRY_PATH};
+use medi_token::{TokenAmount, U256};
+use std::path::PathBuf;
+
+/// Deploy the MediToken ERC20 contract to an EVM network
+#[derive(Parser, Debug)]
+#[command(name = "deploy", version, about)]
+struct Args {
+    /// Target network: sepolia, amoy, arbitrum, optimism, cardona, scroll or local
+    #[arg(short, long)]
+    network: Network,
+
+    /// Initial supply in whole MEDT (decimals allowed), minted to the deployer
+    #[arg(short, long, default_value = "1000", value_parser = parse_supply)]
+    initial_supply: U256,
+
+    /// Build and sign the deployment transaction without sending it
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Deployments registry the result is written to
+    #[arg(long, default_value = DEFAULT_REGISTRY_PATH)]
+    registry: PathBuf,
+
+    /// Print the deployment configuration and the recorded result
+    #[arg(short, long)]
+    verbose: bool,
+}
+
+fn parse_supply(value: &str) -> std::result::Result<U256, String> {
+    TokenAmount::parse(value, 18)
+        .map(U256::from)
+        .map_err(|e| e.to_string())
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv::dotenv().ok();
+    let args = Args::parse();
+
+    let deployer = Deployer::new(args.network.clone())?.with_initial_supply(args.initial_supply);
+    // Read the registry up front so a bad file fails before anything is sent on-chain
+    let mut registry = DeploymentRegistry::load(&args.registry)?;
+
+    if args.verbose {
+        let config = deployer.config();
+        println!("🔧 Network: {} (chain id {})", config.network, config.network.chain_id());
+        println!("🔧 RPC URL: {}", config.rpc_url);
+        println!("🔧 Initial Supply: {} MEDT", TokenAmount::from_raw(config.initial_supply, 18)?);
+        println!("🔧 Bytecode: {} bytes", config.bytecode.len());
+        println!("🔧 Registry: {}", args.registry.display());
+    }
+
+    if args.dry_run {
+        println!("🧪 Dry run: the transaction will not be sent");
+        let signed = deployer.prepare().await?;
+        println!("📝 Transaction Hash: {}", signed.hash_hex());
+        println!("📝 Raw Transaction: {}", signed.raw_hex());
+        return Ok(());
+    }
+
+    let deployed = deployer.deploy().await?;
+    let record = DeploymentRecord::from_deployed(&deployed, CONTRACT_VERSION);
+    if let Some(previous) = registry.add(record) {
+        println!("📁 Replacing previous {} deployment at {}", previous.contract_version, previous.address);
+    }
+    registry.save(&args.registry)?;
+    println!("📁 Recorded deployment in {}", args.registry.display());
+
+    if args.verbose {
+        println!("{}", serde_json::to_string_pretty(&deployed)?);
+    }
+
+    Ok(())
+}
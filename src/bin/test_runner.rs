@@ -1,4 +1,4 @@
-use medi_token::{MediToken, utils::Utils};
+use medi_token::{MediToken, utils::Utils, U256};
 // use std::collections::HashMap;
 
 fn main() {
@@ -30,7 +30,7 @@ fn test_token_creation() {
     println!("🔸 Test 1: Token Creation");
     
     let owner = "0x1234567890123456789012345678901234567890".to_string();
-    let initial_supply = 1000 * 10_u64.pow(18);
+    let initial_supply = U256::from(1000) * U256::exp10(18);
     let token = MediToken::new(initial_supply, owner.clone());
     
     assert_eq!(token.name, "MediToken");
@@ -48,10 +48,10 @@ fn test_balance_check() {
     
     let owner = "0x1234567890123456789012345678901234567890".to_string();
     let user = "0x0987654321098765432109876543210987654321".to_string();
-    let token = MediToken::new(1000 * 10_u64.pow(18), owner.clone());
+    let token = MediToken::new(U256::from(1000) * U256::exp10(18), owner.clone());
     
-    assert_eq!(token.balance_of(&owner), 1000 * 10_u64.pow(18));
-    assert_eq!(token.balance_of(&user), 0);
+    assert_eq!(token.balance_of(&owner), U256::from(1000) * U256::exp10(18));
+    assert_eq!(token.balance_of(&user), U256::zero());
     
     println!("   ✓ Balance checks working correctly");
 }
@@ -61,14 +61,14 @@ fn test_transfer() {
     
     let owner = "0x1234567890123456789012345678901234567890".to_string();
     let recipient = "0x0987654321098765432109876543210987654321".to_string();
-    let mut token = MediToken::new(1000 * 10_u64.pow(18), owner.clone());
+    let mut token = MediToken::new(U256::from(1000) * U256::exp10(18), owner.clone());
     
-    let transfer_amount = 100 * 10_u64.pow(18);
+    let transfer_amount = U256::from(100) * U256::exp10(18);
     let result = token.transfer(&owner, &recipient, transfer_amount);
     
     assert!(result.is_ok());
-    assert_eq!(token.balance_of(&owner), 900 * 10_u64.pow(18));
-    assert_eq!(token.balance_of(&recipient), 100 * 10_u64.pow(18));
+    assert_eq!(token.balance_of(&owner), U256::from(900) * U256::exp10(18));
+    assert_eq!(token.balance_of(&recipient), U256::from(100) * U256::exp10(18));
     
     // Check events
     let events = token.get_events();
@@ -83,10 +83,10 @@ fn test_allowance_and_transfer_from() {
     let owner = "0x1234567890123456789012345678901234567890".to_string();
     let spender = "0x0987654321098765432109876543210987654321".to_string();
     let recipient = "0xabcdefabcdefabcdefabcdefabcdefabcdefabcd".to_string();
-    let mut token = MediToken::new(1000 * 10_u64.pow(18), owner.clone());
+    let mut token = MediToken::new(U256::from(1000) * U256::exp10(18), owner.clone());
     
-    let allowance_amount = 200 * 10_u64.pow(18);
-    let transfer_amount = 50 * 10_u64.pow(18);
+    let allowance_amount = U256::from(200) * U256::exp10(18);
+    let transfer_amount = U256::from(50) * U256::exp10(18);
     
     // Approve spender
     let approve_result = token.approve(&owner, &spender, allowance_amount);
@@ -97,9 +97,9 @@ fn test_allowance_and_transfer_from() {
     let transfer_result = token.transfer_from(&spender, &owner, &recipient, transfer_amount);
     assert!(transfer_result.is_ok());
     
-    assert_eq!(token.balance_of(&owner), 950 * 10_u64.pow(18));
-    assert_eq!(token.balance_of(&recipient), 50 * 10_u64.pow(18));
-    assert_eq!(token.allowance(&owner, &spender), 150 * 10_u64.pow(18));
+    assert_eq!(token.balance_of(&owner), U256::from(950) * U256::exp10(18));
+    assert_eq!(token.balance_of(&recipient), U256::from(50) * U256::exp10(18));
+    assert_eq!(token.allowance(&owner, &spender), U256::from(150) * U256::exp10(18));
     
     println!("   ✓ Allowance and transfer from working correctly");
 }
@@ -110,22 +110,22 @@ fn test_error_cases() {
     let owner = "0x1234567890123456789012345678901234567890".to_string();
     let user = "0x0987654321098765432109876543210987654321".to_string();
     let zero_address = "0x0000000000000000000000000000000000000000".to_string();
-    let mut token = MediToken::new(1000 * 10_u64.pow(18), owner.clone());
+    let mut token = MediToken::new(U256::from(1000) * U256::exp10(18), owner.clone());
     
     // Test insufficient balance
-    let insufficient_result = token.transfer(&user, &owner, 100);
+    let insufficient_result = token.transfer(&user, &owner, U256::from(100));
     assert!(insufficient_result.is_err());
     
     // Test transfer to zero address
-    let zero_transfer_result = token.transfer(&owner, &zero_address, 100);
+    let zero_transfer_result = token.transfer(&owner, &zero_address, U256::from(100));
     assert!(zero_transfer_result.is_err());
     
     // Test approve to zero address
-    let zero_approve_result = token.approve(&owner, &zero_address, 100);
+    let zero_approve_result = token.approve(&owner, &zero_address, U256::from(100));
     assert!(zero_approve_result.is_err());
     
     // Test insufficient allowance
-    let no_allowance_result = token.transfer_from(&user, &owner, &user, 100);
+    let no_allowance_result = token.transfer_from(&user, &owner, &user, U256::from(100));
     assert!(no_allowance_result.is_err());
     
     println!("   ✓ Error cases handled correctly");
@@ -144,25 +144,25 @@ fn test_utils() {
     assert!(Utils::is_valid_address(&random_addr));
     
     // Test wei/ether conversion
-    let wei_amount = 1000000000000000000u64; // 1 ether in wei
+    let wei_amount = U256::exp10(18); // 1 ether in wei
     let ether_amount = Utils::wei_to_ether(wei_amount);
-    assert_eq!(ether_amount, 1.0);
+    assert_eq!(ether_amount, "1");
     
-    let converted_back = Utils::ether_to_wei(ether_amount);
+    let converted_back = Utils::ether_to_wei(&ether_amount).unwrap();
     assert_eq!(converted_back, wei_amount);
     
     // Test overflow/underflow checks
-    let overflow_result = Utils::check_overflow(u64::MAX, 1);
+    let overflow_result = Utils::check_overflow(U256::MAX, U256::one());
     assert!(overflow_result.is_err());
     
-    let underflow_result = Utils::check_underflow(0, 1);
+    let underflow_result = Utils::check_underflow(U256::zero(), U256::one());
     assert!(underflow_result.is_err());
     
-    let valid_add = Utils::check_overflow(100, 200);
-    assert_eq!(valid_add.unwrap(), 300);
+    let valid_add = Utils::check_overflow(U256::from(100), U256::from(200));
+    assert_eq!(valid_add.unwrap(), U256::from(300));
     
-    let valid_sub = Utils::check_underflow(200, 100);
-    assert_eq!(valid_sub.unwrap(), 100);
+    let valid_sub = Utils::check_underflow(U256::from(200), U256::from(100));
+    assert_eq!(valid_sub.unwrap(), U256::from(100));
     
     println!("   ✓ Utility functions working correctly");
 }
\ No newline at end of file
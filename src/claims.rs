@@ -0,0 +1,288 @@
+//! Insurance claims: a provider bills a patient's insurer, the insurer adjudicates and
+//! approves or denies the claim, and approved amounts are paid or later reversed.
+//!
+//! This module is not available for WASM targets
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::fmt;
+use serde::{Deserialize, Serialize};
+use primitive_types::U256;
+use crate::access_control::PROVIDER_ROLE;
+use crate::error::{MediTokenError, Result};
+use crate::records::{EventLog, Records};
+use crate::token::MediToken;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClaimStatus {
+    Submitted,
+    /// Under review by the insurer
+    Adjudicated,
+    Approved,
+    PartiallyApproved,
+    Denied,
+    Paid,
+    Reversed,
+}
+
+impl fmt::Display for ClaimStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// An insurance claim for a service billed by a provider
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Claim {
+    pub id: u64,
+    pub provider: String,
+    pub patient: String,
+    pub insurer: String,
+    pub billed: U256,
+    pub description: String,
+    pub status: ClaimStatus,
+    /// Part of the allowed amount paid by the insurer
+    pub coverage: U256,
+    /// Part of the allowed amount paid by the patient
+    pub copay: U256,
+    /// Why the claim was denied or reversed
+    pub reason: Option<String>,
+}
+
+/// Events emitted by the claims subsystem
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClaimEvent {
+    ClaimSubmitted {
+        id: u64,
+        provider: String,
+        patient: String,
+        insurer: String,
+        billed: U256,
+    },
+    ClaimAdjudicated {
+        id: u64,
+    },
+    ClaimApproved {
+        id: u64,
+        status: ClaimStatus,
+        coverage: U256,
+        copay: U256,
+    },
+    ClaimDenied {
+        id: u64,
+        reason: String,
+    },
+    ClaimPaid {
+        id: u64,
+        coverage: U256,
+        copay: U256,
+    },
+    ClaimReversed {
+        id: u64,
+        reason: String,
+    },
+}
+
+/// Insurance claim settlement on `MediToken`.
+/// `address` never holds funds; it spends allowances instead. The insurer and patient approve
+/// it for their shares of a payment, and the provider approves it to fund reversals.
+#[derive(Debug, Clone)]
+pub struct Claims {
+    pub address: String,
+    claims: Records<Claim>,
+    events: EventLog<ClaimEvent>,
+}
+
+impl Claims {
+    /// Creates a claims subsystem settling through `address`
+    pub fn new(address: String) -> Self {
+        Self {
+            address,
+            claims: Records::new(),
+            events: EventLog::new(),
+        }
+    }
+
+    /// Files a claim for `billed` tokens with the patient's insurer (provider role only)
+    pub fn submit(
+        &mut self,
+        token: &MediToken,
+        caller: &str,
+        patient: &str,
+        insurer: &str,
+        billed: U256,
+        description: &str,
+    ) -> Result<u64> {
+        token.roles.check_role(PROVIDER_ROLE, caller)?;
+        if billed.is_zero() {
+            return Err(MediTokenError::InvalidAmount {
+                amount: billed.to_string(),
+            });
+        }
+
+        let id = self.claims.insert_with(|id| Claim {
+            id,
+            provider: caller.to_string(),
+            patient: patient.to_string(),
+            insurer: insurer.to_string(),
+            billed,
+            description: description.to_string(),
+            status: ClaimStatus::Submitted,
+            coverage: U256::zero(),
+            copay: U256::zero(),
+            reason: None,
+        });
+
+        self.events.emit(ClaimEvent::ClaimSubmitted {
+            id,
+            provider: caller.to_string(),
+            patient: patient.to_string(),
+            insurer: insurer.to_string(),
+            billed,
+        });
+        Ok(id)
+    }
+
+    /// Starts reviewing a submitted claim (insurer only)
+    pub fn adjudicate(&mut self, caller: &str, id: u64) -> Result<()> {
+        self.check_insurer(caller, id)?;
+        self.transition(id, &[ClaimStatus::Submitted], ClaimStatus::Adjudicated)?;
+
+        self.events.emit(ClaimEvent::ClaimAdjudicated { id });
+        Ok(())
+    }
+
+    /// Allows `coverage + copay` of the billed amount (insurer only). The claim is approved when
+    /// the whole bill is allowed and partially approved otherwise; the provider writes off the rest.
+    pub fn approve(&mut self, caller: &str, id: u64, coverage: U256, copay: U256) -> Result<ClaimStatus> {
+        self.check_insurer(caller, id)?;
+        let allowed = coverage
+            .checked_add(copay)
+            .ok_or(MediTokenError::ArithmeticOverflow)?;
+        let billed = self.claim(id)?.billed;
+        if allowed.is_zero() || allowed > billed {
+            return Err(MediTokenError::InvalidAmount {
+                amount: allowed.to_string(),
+            });
+        }
+
+        let status = if allowed == billed {
+            ClaimStatus::Approved
+        } else {
+            ClaimStatus::PartiallyApproved
+        };
+        self.transition(id, &[ClaimStatus::Adjudicated], status)?;
+        let claim = self.claims.get_mut(id).expect("claim exists");
+        claim.coverage = coverage;
+        claim.copay = copay;
+
+        self.events.emit(ClaimEvent::ClaimApproved { id, status, coverage, copay });
+        Ok(status)
+    }
+
+    /// Rejects a claim under review (insurer only)
+    pub fn deny(&mut self, caller: &str, id: u64, reason: &str) -> Result<()> {
+        self.check_insurer(caller, id)?;
+        self.transition(id, &[ClaimStatus::Adjudicated], ClaimStatus::Denied)?;
+        self.claims.get_mut(id).expect("claim exists").reason = Some(reason.to_string());
+
+        self.events.emit(ClaimEvent::ClaimDenied {
+            id,
+            reason: reason.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Pays the provider the coverage from the insurer and the copay from the patient.
+    /// Both payments go through or neither does.
+    pub fn pay(&mut self, token: &mut MediToken, id: u64) -> Result<()> {
+        let claim = self.claim(id)?;
+        let approved = [ClaimStatus::Approved, ClaimStatus::PartiallyApproved];
+        check_transition(claim, &approved, ClaimStatus::Paid)?;
+
+        let mut updated = token.clone();
+        if !claim.coverage.is_zero() {
+            updated.transfer_from(&self.address, &claim.insurer, &claim.provider, claim.coverage)?;
+        }
+        if !claim.copay.is_zero() {
+            updated.transfer_from(&self.address, &claim.patient, &claim.provider, claim.copay)?;
+        }
+        *token = updated;
+
+        let (coverage, copay) = (claim.coverage, claim.copay);
+        self.claims.get_mut(id).expect("claim exists").status = ClaimStatus::Paid;
+        self.events.emit(ClaimEvent::ClaimPaid { id, coverage, copay });
+        Ok(())
+    }
+
+    /// Refunds a paid claim from the provider to the insurer and patient (insurer only)
+    pub fn reverse(&mut self, token: &mut MediToken, caller: &str, id: u64, reason: &str) -> Result<()> {
+        self.check_insurer(caller, id)?;
+        let claim = self.claim(id)?;
+        check_transition(claim, &[ClaimStatus::Paid], ClaimStatus::Reversed)?;
+
+        let mut updated = token.clone();
+        if !claim.coverage.is_zero() {
+            updated.transfer_from(&self.address, &claim.provider, &claim.insurer, claim.coverage)?;
+        }
+        if !claim.copay.is_zero() {
+            updated.transfer_from(&self.address, &claim.provider, &claim.patient, claim.copay)?;
+        }
+        *token = updated;
+
+        let claim = self.claims.get_mut(id).expect("claim exists");
+        claim.status = ClaimStatus::Reversed;
+        claim.reason = Some(reason.to_string());
+        self.events.emit(ClaimEvent::ClaimReversed {
+            id,
+            reason: reason.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Gets a claim by id
+    pub fn claim(&self, id: u64) -> Result<&Claim> {
+        self.claims
+            .get(id)
+            .ok_or(MediTokenError::ClaimNotFound { id })
+    }
+
+    /// Gets the claims filed against a patient
+    pub fn claims_for_patient(&self, patient: &str) -> Vec<&Claim> {
+        self.claims
+            .values()
+            .filter(|claim| claim.patient == patient)
+            .collect()
+    }
+
+    /// Gets all events
+    pub fn get_events(&self) -> &[ClaimEvent] {
+        self.events.all()
+    }
+
+    fn check_insurer(&self, caller: &str, id: u64) -> Result<()> {
+        if self.claim(id)?.insurer != caller {
+            return Err(MediTokenError::Unauthorized {
+                caller: caller.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    fn transition(&mut self, id: u64, from: &[ClaimStatus], to: ClaimStatus) -> Result<()> {
+        check_transition(self.claim(id)?, from, to)?;
+        self.claims.get_mut(id).expect("claim exists").status = to;
+        Ok(())
+    }
+}
+
+/// Fails with `InvalidClaimTransition` unless the claim is in one of the `from` states
+fn check_transition(claim: &Claim, from: &[ClaimStatus], to: ClaimStatus) -> Result<()> {
+    if !from.contains(&claim.status) {
+        return Err(MediTokenError::InvalidClaimTransition {
+            id: claim.id,
+            from: claim.status.to_string(),
+            to: to.to_string(),
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,229 @@
+//! This module is not available for WASM targets
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::env;
+use std::time::Duration;
+use k256::ecdsa::SigningKey;
+use primitive_types::U256;
+use serde_json::json;
+use crate::abi_codec::{decode_meditoken_event, Abi, AbiValue};
+use crate::amount::MAX_DECIMALS;
+use crate::deployment::{get_deployed_addresses, Network};
+use crate::error::{MediTokenError, Result};
+use crate::rpc::{RpcClient, TransactionReceipt};
+use crate::token::MediTokenEvent;
+use crate::transaction::{buffered_gas_limit, parse_private_key, signer_address, LegacyTransaction};
+use crate::utils::Utils;
+
+/// Client for a deployed MediToken ERC20 contract
+pub struct MediTokenClient {
+    rpc: RpcClient,
+    abi: Abi,
+    address: String,
+    signer: Option<SigningKey>,
+    gas_buffer_percent: u64,
+    receipt_poll_interval: Duration,
+    receipt_max_polls: u32,
+}
+
+impl MediTokenClient {
+    /// Creates a read-only client for the contract at `address`
+    pub fn new(rpc_url: impl Into<String>, address: &str) -> Result<Self> {
+        if !Utils::is_valid_address(address) {
+            return Err(MediTokenError::InvalidAddress { address: address.to_string() });
+        }
+
+        Ok(Self {
+            rpc: RpcClient::new(rpc_url),
+            abi: Abi::meditoken(),
+            address: address.to_string(),
+            signer: None,
+            gas_buffer_percent: 20,
+            receipt_poll_interval: Duration::from_secs(2),
+            receipt_max_polls: 90,
+        })
+    }
+
+    /// Creates a client for the known deployment on `network`, using its RPC URL environment variable
+    pub fn for_network(network: Network) -> Result<Self> {
+        let rpc_url = env::var(network.rpc_url_env_var()).map_err(|_| MediTokenError::NetworkError {
+            message: format!("RPC URL not found for network {:?}", network),
+        })?;
+        let address = get_deployed_addresses()
+            .remove(&network)
+            .ok_or_else(|| MediTokenError::ContractError {
+                message: format!("No MediToken deployment known for {:?}", network),
+            })?;
+
+        Self::new(rpc_url, &address)
+    }
+
+    /// Enables write calls signed with `private_key`
+    pub fn with_signer(mut self, private_key: &str) -> Result<Self> {
+        self.signer = Some(parse_private_key(private_key)?);
+        Ok(self)
+    }
+
+    /// Overrides how often and how long write calls wait for a receipt
+    pub fn with_receipt_polling(mut self, poll_interval: Duration, max_polls: u32) -> Self {
+        self.receipt_poll_interval = poll_interval;
+        self.receipt_max_polls = max_polls;
+        self
+    }
+
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Address of the configured signer, if any
+    pub fn signer_address(&self) -> Option<String> {
+        self.signer.as_ref().map(signer_address)
+    }
+
+    pub async fn name(&self) -> Result<String> {
+        self.call("name", &[]).await?[0].as_string()
+    }
+
+    pub async fn symbol(&self) -> Result<String> {
+        self.call("symbol", &[]).await?[0].as_string()
+    }
+
+    pub async fn decimals(&self) -> Result<u8> {
+        let decimals = self.call("decimals", &[]).await?[0].as_uint()?;
+        if decimals > U256::from(MAX_DECIMALS) {
+            return Err(MediTokenError::ContractError {
+                message: format!("decimals() returned {}, more than the supported {}", decimals, MAX_DECIMALS),
+            });
+        }
+        Ok(decimals.as_u32() as u8)
+    }
+
+    pub async fn total_supply(&self) -> Result<U256> {
+        self.call("totalSupply", &[]).await?[0].as_uint()
+    }
+
+    pub async fn balance_of(&self, account: &str) -> Result<U256> {
+        self.call("balanceOf", &[address(account)]).await?[0].as_uint()
+    }
+
+    pub async fn allowance(&self, owner: &str, spender: &str) -> Result<U256> {
+        self.call("allowance", &[address(owner), address(spender)]).await?[0].as_uint()
+    }
+
+    pub async fn transfer(&self, to: &str, amount: U256) -> Result<TransactionReceipt> {
+        self.send("transfer", &[address(to), AbiValue::Uint(amount)]).await
+    }
+
+    pub async fn approve(&self, spender: &str, amount: U256) -> Result<TransactionReceipt> {
+        self.send("approve", &[address(spender), AbiValue::Uint(amount)]).await
+    }
+
+    pub async fn transfer_from(&self, from: &str, to: &str, amount: U256) -> Result<TransactionReceipt> {
+        self.send("transferFrom", &[address(from), address(to), AbiValue::Uint(amount)])
+            .await
+    }
+
+    /// Decodes the `Transfer` and `Approval` events in a receipt emitted by this contract
+    pub fn events(&self, receipt: &TransactionReceipt) -> Result<Vec<MediTokenEvent>> {
+        receipt
+            .logs
+            .iter()
+            .filter(|log| log.address.eq_ignore_ascii_case(&self.address))
+            .map(|log| decode_meditoken_event(&self.abi, log))
+            .collect()
+    }
+
+    /// Runs a view function through `eth_call` and decodes its outputs
+    async fn call(&self, function: &str, args: &[AbiValue]) -> Result<Vec<AbiValue>> {
+        let function = self.abi.function(function)?;
+        let data = function.encode_call(args)?;
+        let output = self
+            .rpc
+            .call(json!({ "to": self.address, "data": format!("0x{}", hex::encode(data)) }))
+            .await
+            .map_err(|e| self.decode_revert(e))?;
+        function.decode_output(&output)
+    }
+
+    /// Signs and submits a state-changing call, then waits for its receipt
+    async fn send(&self, function: &str, args: &[AbiValue]) -> Result<TransactionReceipt> {
+        let key = self.signer.as_ref().ok_or_else(|| MediTokenError::ContractError {
+            message: "No signer configured for write calls".to_string(),
+        })?;
+        let from = signer_address(key);
+        let data = self.abi.function(function)?.encode_call(args)?;
+
+        let chain_id = self.rpc.chain_id().await?;
+        let nonce = self.rpc.get_transaction_count(&from).await?;
+        let gas_price = self.rpc.gas_price().await?;
+        let estimate = self
+            .rpc
+            .estimate_gas(json!({
+                "from": from,
+                "to": self.address,
+                "data": format!("0x{}", hex::encode(&data)),
+            }))
+            .await
+            .map_err(|e| self.decode_revert(e))?;
+
+        let mut to = [0u8; 20];
+        to.copy_from_slice(&hex::decode(&self.address[2..]).expect("address validated in new"));
+        let tx = LegacyTransaction {
+            nonce,
+            gas_price,
+            gas_limit: buffered_gas_limit(estimate, self.gas_buffer_percent)?,
+            to: Some(to),
+            value: U256::zero(),
+            data,
+            chain_id,
+        };
+
+        let hash = self.rpc.send_raw_transaction(&tx.sign(key)?).await.map_err(|e| self.decode_revert(e))?;
+        let receipt = self
+            .rpc
+            .wait_for_receipt(&hash, self.receipt_poll_interval, self.receipt_max_polls)
+            .await?;
+        if !receipt.succeeded() {
+            return Err(MediTokenError::ContractError {
+                message: format!("{} transaction {} reverted", function, hash),
+            });
+        }
+
+        Ok(receipt)
+    }
+
+    /// Maps ERC20 custom-error revert data to typed errors, leaving other errors untouched
+    fn decode_revert(&self, error: MediTokenError) -> MediTokenError {
+        let data = match &error {
+            MediTokenError::RpcError { data: Some(data), .. } => data,
+            _ => return error,
+        };
+        let data = match hex::decode(data.trim_start_matches("0x")) {
+            Ok(data) => data,
+            Err(_) => return error,
+        };
+        let abi_error = match self.abi.error_by_selector(&data) {
+            Some(abi_error) => abi_error,
+            None => return error,
+        };
+
+        let decoded = abi_error.decode(&data).and_then(|args| match abi_error.name.as_str() {
+            "ERC20InsufficientBalance" => Ok(MediTokenError::InsufficientBalance {
+                required: args[2].as_uint()?,
+                available: args[1].as_uint()?,
+            }),
+            "ERC20InsufficientAllowance" => Ok(MediTokenError::InsufficientAllowance {
+                spender: args[0].as_address()?,
+                allowance: args[1].as_uint()?,
+                needed: args[2].as_uint()?,
+            }),
+            _ => Err(MediTokenError::ContractError { message: abi_error.signature() }),
+        });
+
+        decoded.unwrap_or(error)
+    }
+}
+
+fn address(address: &str) -> AbiValue {
+    AbiValue::Address(address.to_lowercase())
+}
@@ -0,0 +1,51 @@
+//! This module is not available for WASM targets
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::collections::BTreeSet;
+use serde::{Deserialize, Serialize};
+use crate::error::{MediTokenError, Result};
+
+/// Accounts blocked from sending or receiving tokens, e.g. while a fraudulent claim is investigated
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Compliance {
+    frozen: BTreeSet<String>,
+}
+
+impl Compliance {
+    /// Creates a blocklist with no frozen accounts
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks whether an account is frozen
+    pub fn is_frozen(&self, account: &str) -> bool {
+        self.frozen.contains(account)
+    }
+
+    /// Lists frozen accounts in sorted order
+    pub fn frozen_accounts(&self) -> Vec<String> {
+        self.frozen.iter().cloned().collect()
+    }
+
+    /// Fails with `AccountFrozen` if the account is frozen
+    pub fn check_not_frozen(&self, account: &str) -> Result<()> {
+        if self.is_frozen(account) {
+            return Err(MediTokenError::AccountFrozen {
+                account: account.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Freezes an account without checking the caller.
+    /// Returns whether the account was newly frozen.
+    pub fn freeze_unchecked(&mut self, account: &str) -> bool {
+        self.frozen.insert(account.to_string())
+    }
+
+    /// Unfreezes an account without checking the caller.
+    /// Returns whether the account was frozen.
+    pub fn unfreeze_unchecked(&mut self, account: &str) -> bool {
+        self.frozen.remove(account)
+    }
+}
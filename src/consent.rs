@@ -0,0 +1,427 @@
+//! Patient consent: patients grant providers scoped, expiring access to categories of their
+//! data, every access attempt is logged, and responders can break glass in emergencies.
+//!
+//! This module is not available for WASM targets
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::collections::BTreeSet;
+use serde::{Deserialize, Serialize};
+use crate::access_control::PROVIDER_ROLE;
+use crate::clock::{Clock, SystemClock};
+use crate::error::{MediTokenError, Result};
+use crate::records::{EventLog, Records};
+use crate::token::MediToken;
+
+/// How long break-glass access lasts, in seconds
+pub const DEFAULT_EMERGENCY_WINDOW: u64 = 24 * 60 * 60;
+/// How long a responder has to justify break-glass access, in seconds
+pub const DEFAULT_JUSTIFICATION_PERIOD: u64 = 72 * 60 * 60;
+
+/// Kinds of patient data that consent can cover
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum DataCategory {
+    Demographics,
+    Diagnoses,
+    Medications,
+    LabResults,
+    Imaging,
+    Genomics,
+    MentalHealth,
+    Other(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum AccessLevel {
+    Read,
+    /// Also allows reading
+    Write,
+}
+
+/// Access a patient granted to a provider.
+/// Patients and grantees are `MediToken` addresses or NEAR account ids.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsentGrant {
+    pub id: u64,
+    pub patient: String,
+    pub grantee: String,
+    pub categories: BTreeSet<DataCategory>,
+    pub level: AccessLevel,
+    pub granted_at: u64,
+    pub expires_at: Option<u64>,
+    pub revoked_at: Option<u64>,
+}
+
+impl ConsentGrant {
+    /// Whether the grant allows `level` access to `category` at time `now`
+    pub fn allows(&self, category: &DataCategory, level: AccessLevel, now: u64) -> bool {
+        self.revoked_at.is_none()
+            && self.expires_at.is_none_or(|expires_at| now < expires_at)
+            && self.level >= level
+            && self.categories.contains(category)
+    }
+}
+
+/// Break-glass read access to every category of a patient's data
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmergencyAccess {
+    pub id: u64,
+    pub patient: String,
+    pub responder: String,
+    pub reason: String,
+    pub opened_at: u64,
+    pub expires_at: u64,
+    /// Justification is due by this time
+    pub justify_by: u64,
+    pub justification: Option<String>,
+}
+
+/// Entries of a patient's access history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConsentEvent {
+    ConsentGranted {
+        id: u64,
+        patient: String,
+        grantee: String,
+        timestamp: u64,
+    },
+    ConsentRevoked {
+        id: u64,
+        patient: String,
+        grantee: String,
+        timestamp: u64,
+    },
+    DataAccessed {
+        patient: String,
+        accessor: String,
+        category: DataCategory,
+        level: AccessLevel,
+        /// Break-glass session used, if access was not consented
+        emergency_id: Option<u64>,
+        timestamp: u64,
+    },
+    AccessDenied {
+        patient: String,
+        accessor: String,
+        category: DataCategory,
+        level: AccessLevel,
+        timestamp: u64,
+    },
+    EmergencyAccessOpened {
+        id: u64,
+        patient: String,
+        responder: String,
+        reason: String,
+        timestamp: u64,
+    },
+    EmergencyAccessJustified {
+        id: u64,
+        patient: String,
+        responder: String,
+        timestamp: u64,
+    },
+}
+
+impl ConsentEvent {
+    /// Patient whose data the event concerns
+    pub fn patient(&self) -> &str {
+        match self {
+            ConsentEvent::ConsentGranted { patient, .. }
+            | ConsentEvent::ConsentRevoked { patient, .. }
+            | ConsentEvent::DataAccessed { patient, .. }
+            | ConsentEvent::AccessDenied { patient, .. }
+            | ConsentEvent::EmergencyAccessOpened { patient, .. }
+            | ConsentEvent::EmergencyAccessJustified { patient, .. } => patient,
+        }
+    }
+}
+
+/// Patient consent for data access, with logged break-glass access for emergencies.
+/// Consent can only be granted to, and break-glass access taken by, accounts holding the
+/// provider role on the token.
+#[derive(Debug, Clone)]
+pub struct ConsentRegistry<C: Clock = SystemClock> {
+    clock: C,
+    pub emergency_window: u64,
+    pub justification_period: u64,
+    grants: Records<ConsentGrant>,
+    emergencies: Records<EmergencyAccess>,
+    events: EventLog<ConsentEvent>,
+}
+
+impl ConsentRegistry<SystemClock> {
+    /// Creates a registry that follows the system clock
+    pub fn new() -> Self {
+        Self::with_clock(SystemClock)
+    }
+}
+
+impl Default for ConsentRegistry<SystemClock> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Clock> ConsentRegistry<C> {
+    /// Creates a registry that reads time from `clock`
+    pub fn with_clock(clock: C) -> Self {
+        Self {
+            clock,
+            emergency_window: DEFAULT_EMERGENCY_WINDOW,
+            justification_period: DEFAULT_JUSTIFICATION_PERIOD,
+            grants: Records::new(),
+            emergencies: Records::new(),
+            events: EventLog::new(),
+        }
+    }
+
+    /// Grants provider `grantee` access to some of the patient's data, optionally until
+    /// `expires_at` (patient only)
+    #[allow(clippy::too_many_arguments)]
+    pub fn grant(
+        &mut self,
+        token: &MediToken,
+        caller: &str,
+        patient: &str,
+        grantee: &str,
+        categories: BTreeSet<DataCategory>,
+        level: AccessLevel,
+        expires_at: Option<u64>,
+    ) -> Result<u64> {
+        if patient != caller {
+            return Err(MediTokenError::Unauthorized {
+                caller: caller.to_string(),
+            });
+        }
+        token.roles.check_role(PROVIDER_ROLE, grantee)?;
+        let now = self.clock.now();
+        if categories.is_empty() {
+            return Err(consent_error("Consent must cover at least one data category"));
+        }
+        if expires_at.is_some_and(|expires_at| expires_at <= now) {
+            return Err(consent_error("Consent must expire in the future"));
+        }
+
+        let id = self.grants.insert_with(|id| ConsentGrant {
+            id,
+            patient: patient.to_string(),
+            grantee: grantee.to_string(),
+            categories,
+            level,
+            granted_at: now,
+            expires_at,
+            revoked_at: None,
+        });
+
+        self.events.emit(ConsentEvent::ConsentGranted {
+            id,
+            patient: patient.to_string(),
+            grantee: grantee.to_string(),
+            timestamp: now,
+        });
+        Ok(id)
+    }
+
+    /// Withdraws a grant (patient only)
+    pub fn revoke(&mut self, caller: &str, id: u64) -> Result<()> {
+        let grant = self.grant_by_id(id)?;
+        if grant.patient != caller {
+            return Err(MediTokenError::Unauthorized {
+                caller: caller.to_string(),
+            });
+        }
+        if grant.revoked_at.is_some() {
+            return Err(consent_error("Consent is already revoked"));
+        }
+
+        let now = self.clock.now();
+        let grantee = grant.grantee.clone();
+        self.grants.get_mut(id).expect("grant exists").revoked_at = Some(now);
+        self.events.emit(ConsentEvent::ConsentRevoked {
+            id,
+            patient: caller.to_string(),
+            grantee,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Whether `accessor` may access a category of the patient's data through consent or
+    /// an open break-glass session (read only)
+    pub fn has_access(&self, patient: &str, accessor: &str, category: &DataCategory, level: AccessLevel) -> bool {
+        let now = self.clock.now();
+        self.consenting_grant(patient, accessor, category, level, now).is_some()
+            || (level == AccessLevel::Read && self.open_emergency(patient, accessor, now).is_some())
+    }
+
+    /// Records an access attempt in the patient's history, failing if it is not allowed
+    pub fn access(&mut self, patient: &str, accessor: &str, category: DataCategory, level: AccessLevel) -> Result<()> {
+        let now = self.clock.now();
+        let emergency_id = if self.consenting_grant(patient, accessor, &category, level, now).is_some() {
+            None
+        } else if let Some(emergency) = self
+            .open_emergency(patient, accessor, now)
+            .filter(|_| level == AccessLevel::Read)
+        {
+            Some(emergency.id)
+        } else {
+            self.events.emit(ConsentEvent::AccessDenied {
+                patient: patient.to_string(),
+                accessor: accessor.to_string(),
+                category: category.clone(),
+                level,
+                timestamp: now,
+            });
+            return Err(MediTokenError::AccessDenied {
+                accessor: accessor.to_string(),
+                patient: patient.to_string(),
+            });
+        };
+
+        self.events.emit(ConsentEvent::DataAccessed {
+            patient: patient.to_string(),
+            accessor: accessor.to_string(),
+            category,
+            level,
+            emergency_id,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Opens logged read access to all of the patient's data without consent (provider role
+    /// only). Responders with overdue justifications cannot break glass again.
+    pub fn break_glass(&mut self, token: &MediToken, responder: &str, patient: &str, reason: &str) -> Result<u64> {
+        token.roles.check_role(PROVIDER_ROLE, responder)?;
+        let now = self.clock.now();
+        if self
+            .overdue_justifications()
+            .iter()
+            .any(|emergency| emergency.responder == responder)
+        {
+            return Err(consent_error("Earlier emergency access must be justified first"));
+        }
+
+        let expires_at = now.saturating_add(self.emergency_window);
+        let justify_by = expires_at.saturating_add(self.justification_period);
+        let id = self.emergencies.insert_with(|id| EmergencyAccess {
+            id,
+            patient: patient.to_string(),
+            responder: responder.to_string(),
+            reason: reason.to_string(),
+            opened_at: now,
+            expires_at,
+            justify_by,
+            justification: None,
+        });
+
+        self.events.emit(ConsentEvent::EmergencyAccessOpened {
+            id,
+            patient: patient.to_string(),
+            responder: responder.to_string(),
+            reason: reason.to_string(),
+            timestamp: now,
+        });
+        Ok(id)
+    }
+
+    /// Records why break-glass access was needed (responder only)
+    pub fn justify(&mut self, caller: &str, id: u64, justification: &str) -> Result<()> {
+        let emergency = self
+            .emergencies
+            .get_mut(id)
+            .ok_or_else(|| consent_error(&format!("Emergency access {} not found", id)))?;
+        if emergency.responder != caller {
+            return Err(MediTokenError::Unauthorized {
+                caller: caller.to_string(),
+            });
+        }
+        if emergency.justification.is_some() {
+            return Err(consent_error("Emergency access is already justified"));
+        }
+        if justification.trim().is_empty() {
+            return Err(consent_error("Justification must not be empty"));
+        }
+
+        emergency.justification = Some(justification.to_string());
+        let patient = emergency.patient.clone();
+        let timestamp = self.clock.now();
+        self.events.emit(ConsentEvent::EmergencyAccessJustified {
+            id,
+            patient,
+            responder: caller.to_string(),
+            timestamp,
+        });
+        Ok(())
+    }
+
+    /// Break-glass sessions whose justification deadline has passed without one
+    pub fn overdue_justifications(&self) -> Vec<&EmergencyAccess> {
+        let now = self.clock.now();
+        self.emergencies
+            .values()
+            .filter(|emergency| emergency.justification.is_none() && now >= emergency.justify_by)
+            .collect()
+    }
+
+    /// Gets a grant by id
+    pub fn grant_by_id(&self, id: u64) -> Result<&ConsentGrant> {
+        self.grants
+            .get(id)
+            .ok_or(MediTokenError::ConsentNotFound { id })
+    }
+
+    /// Gets every grant a patient has made, including expired and revoked ones
+    pub fn grants_for_patient(&self, patient: &str) -> Vec<&ConsentGrant> {
+        self.grants
+            .values()
+            .filter(|grant| grant.patient == patient)
+            .collect()
+    }
+
+    /// Gets every break-glass session for a patient
+    pub fn emergencies_for_patient(&self, patient: &str) -> Vec<&EmergencyAccess> {
+        self.emergencies
+            .values()
+            .filter(|emergency| emergency.patient == patient)
+            .collect()
+    }
+
+    /// Gets the patient's access history in order
+    pub fn history(&self, patient: &str) -> Vec<&ConsentEvent> {
+        self.events
+            .all()
+            .iter()
+            .filter(|event| event.patient() == patient)
+            .collect()
+    }
+
+    /// Gets all events
+    pub fn get_events(&self) -> &[ConsentEvent] {
+        self.events.all()
+    }
+
+    fn consenting_grant(
+        &self,
+        patient: &str,
+        accessor: &str,
+        category: &DataCategory,
+        level: AccessLevel,
+        now: u64,
+    ) -> Option<&ConsentGrant> {
+        self.grants.values().find(|grant| {
+            grant.patient == patient && grant.grantee == accessor && grant.allows(category, level, now)
+        })
+    }
+
+    fn open_emergency(&self, patient: &str, responder: &str, now: u64) -> Option<&EmergencyAccess> {
+        self.emergencies.values().find(|emergency| {
+            emergency.patient == patient && emergency.responder == responder && now < emergency.expires_at
+        })
+    }
+}
+
+fn consent_error(message: &str) -> MediTokenError {
+    MediTokenError::ConsentError {
+        message: message.to_string(),
+    }
+}
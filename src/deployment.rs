@@ -1,10 +1,22 @@
 //! This module is not available for WASM targets
 #![cfg(not(target_arch = "wasm32"))]
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
+use primitive_types::U256;
+use crate::amount::TokenAmount;
+use crate::registry::DeploymentRegistry;
+use crate::rpc::RpcClient;
+use crate::transaction::{buffered_gas_limit, parse_private_key, signer_address, LegacyTransaction, SignedTransaction};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use std::collections::HashMap;
 use std::env;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Environment variable holding the hex-encoded MediToken creation bytecode
+pub const BYTECODE_ENV_VAR: &str = "MEDITOKEN_BYTECODE";
 
 /// Supported blockchain networks
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -42,15 +54,77 @@ impl Network {
             Network::Local => 31337,
         }
     }
+    
+    /// Every supported network
+    pub fn all() -> [Network; 7] {
+        [
+            Network::Sepolia,
+            Network::Amoy,
+            Network::ArbitrumSepolia,
+            Network::OpSepolia,
+            Network::Cardona,
+            Network::ScrollSepolia,
+            Network::Local,
+        ]
+    }
+    
+    /// Canonical command-line name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Network::Sepolia => "sepolia",
+            Network::Amoy => "amoy",
+            Network::ArbitrumSepolia => "arbitrum-sepolia",
+            Network::OpSepolia => "op-sepolia",
+            Network::Cardona => "cardona",
+            Network::ScrollSepolia => "scroll-sepolia",
+            Network::Local => "local",
+        }
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Network {
+    type Err = anyhow::Error;
+    
+    /// Parses a network name or one of its short aliases (e.g. `arbitrum`, `optimism`)
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().replace('_', "-").as_str() {
+            "sepolia" | "ethereum-sepolia" => Ok(Network::Sepolia),
+            "amoy" | "polygon-amoy" => Ok(Network::Amoy),
+            "arbitrum" | "arbitrum-sepolia" => Ok(Network::ArbitrumSepolia),
+            "optimism" | "op" | "op-sepolia" | "optimism-sepolia" => Ok(Network::OpSepolia),
+            "cardona" | "polygon-zkevm-cardona" => Ok(Network::Cardona),
+            "scroll" | "scroll-sepolia" => Ok(Network::ScrollSepolia),
+            "local" | "localhost" | "anvil" | "hardhat" => Ok(Network::Local),
+            _ => bail!(
+                "Unknown network '{}', expected one of: {}",
+                s,
+                Network::all().map(|network| network.name()).join(", ")
+            ),
+        }
+    }
 }
 
 /// Deployment configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeploymentConfig {
     pub network: Network,
-    pub initial_supply: u64,
+    pub initial_supply: U256,
     pub private_key: String,
     pub rpc_url: String,
+    /// Contract creation bytecode, without constructor arguments
+    pub bytecode: Vec<u8>,
+    /// Extra gas on top of the estimate, in percent
+    pub gas_buffer_percent: u64,
+    /// Delay between receipt polls
+    pub receipt_poll_interval: Duration,
+    /// Give up waiting for a receipt after this many polls
+    pub receipt_max_polls: u32,
 }
 
 /// Deployed contract information
@@ -61,6 +135,8 @@ pub struct DeployedContract {
     pub transaction_hash: String,
     pub block_number: u64,
     pub gas_used: u64,
+    /// Address that signed the deployment transaction
+    pub deployer: String,
 }
 
 /// Contract deployer
@@ -76,32 +152,134 @@ impl Deployer {
         let private_key = env::var("PRIVATE_KEY")
             .map_err(|_| anyhow::anyhow!("PRIVATE_KEY environment variable not set"))?;
         
+        let bytecode = env::var(BYTECODE_ENV_VAR)
+            .map_err(|_| anyhow::anyhow!("{} environment variable not set", BYTECODE_ENV_VAR))?;
+        let bytecode = hex::decode(bytecode.trim().trim_start_matches("0x"))
+            .with_context(|| format!("{} is not valid hex", BYTECODE_ENV_VAR))?;
+        
         let config = DeploymentConfig {
             network,
-            initial_supply: 1000 * 10_u64.pow(18), 
+            initial_supply: U256::from(1000) * U256::exp10(18),
             private_key,
             rpc_url,
+            bytecode,
+            gas_buffer_percent: 20,
+            receipt_poll_interval: Duration::from_secs(2),
+            receipt_max_polls: 90,
         };
         
         Ok(Self { config })
     }
     
+    /// Creates a deployer from an explicit configuration
+    pub fn with_config(config: DeploymentConfig) -> Self {
+        Self { config }
+    }
+    
+    pub fn config(&self) -> &DeploymentConfig {
+        &self.config
+    }
+    
+    /// Overrides the initial supply (in wei) minted to the deployer
+    pub fn with_initial_supply(mut self, initial_supply: U256) -> Self {
+        self.config.initial_supply = initial_supply;
+        self
+    }
+    
+    /// Creation code followed by the ABI-encoded `initialSupply` constructor argument
+    pub fn init_code(&self) -> Vec<u8> {
+        let mut init_code = self.config.bytecode.clone();
+        let mut initial_supply = [0u8; 32];
+        self.config.initial_supply.to_big_endian(&mut initial_supply);
+        init_code.extend_from_slice(&initial_supply);
+        init_code
+    }
+    
+    /// Builds and signs the contract-creation transaction without sending it
+    pub async fn prepare(&self) -> Result<SignedTransaction> {
+        if self.config.bytecode.is_empty() {
+            bail!("Contract bytecode is empty");
+        }
+        
+        let key = parse_private_key(&self.config.private_key)?;
+        let from = signer_address(&key);
+        let rpc = RpcClient::new(&self.config.rpc_url);
+        
+        let chain_id = rpc.chain_id().await?;
+        if chain_id != self.config.network.chain_id() {
+            bail!(
+                "RPC endpoint reports chain id {} but {:?} expects {}",
+                chain_id,
+                self.config.network,
+                self.config.network.chain_id()
+            );
+        }
+        
+        let init_code = self.init_code();
+        let nonce = rpc.get_transaction_count(&from).await?;
+        let gas_price = rpc.gas_price().await?;
+        let estimate = rpc
+            .estimate_gas(json!({
+                "from": from,
+                "data": format!("0x{}", hex::encode(&init_code)),
+            }))
+            .await?;
+        let gas_limit = buffered_gas_limit(estimate, self.config.gas_buffer_percent)?;
+        
+        println!(" Deployer: {}", from);
+        println!(" Nonce: {}", nonce);
+        println!(" Gas Price: {} wei", gas_price);
+        println!(" Gas Limit: {} (estimate {})", gas_limit, estimate);
+        
+        let tx = LegacyTransaction {
+            nonce,
+            gas_price,
+            gas_limit,
+            to: None,
+            value: U256::zero(),
+            data: init_code,
+            chain_id,
+        };
+        
+        Ok(tx.sign(&key)?)
+    }
+    
     /// Deploy the MediToken contract
     pub async fn deploy(&self) -> Result<DeployedContract> {
         println!(" Deploying MediToken to {:?}", self.config.network);
         println!(" RPC URL: {}", self.config.rpc_url);
         println!(
             " Initial Supply: {} MEDT",
-            self.config.initial_supply / 10_u64.pow(18)
+            TokenAmount::from_raw(self.config.initial_supply, 18)?
         );
         
-
+        let signed = self.prepare().await?;
+        let rpc = RpcClient::new(&self.config.rpc_url);
+        let transaction_hash = rpc.send_raw_transaction(&signed).await?;
+        println!(" Transaction sent: {}", transaction_hash);
+        
+        let receipt = rpc
+            .wait_for_receipt(
+                &transaction_hash,
+                self.config.receipt_poll_interval,
+                self.config.receipt_max_polls,
+            )
+            .await?;
+        if !receipt.succeeded() {
+            bail!("Deployment transaction {} reverted", transaction_hash);
+        }
+        let address = receipt
+            .contract_address
+            .clone()
+            .context("Receipt has no contract address")?;
+        
         let deployed = DeployedContract {
-            address: format!("0x{}", hex::encode(rand::random::<[u8; 20]>())),
+            address,
             network: self.config.network.clone(),
-            transaction_hash: format!("0x{}", hex::encode(rand::random::<[u8; 32]>())),
-            block_number: 12345678,
-            gas_used: 1200000,
+            transaction_hash: receipt.transaction_hash.clone(),
+            block_number: receipt.block_number()?,
+            gas_used: receipt.gas_used()?,
+            deployer: signer_address(&parse_private_key(&self.config.private_key)?),
         };
         
         println!(" MediToken deployed successfully!");
@@ -114,33 +292,7 @@ impl Deployer {
     }
 }
 
-/// Known deployed contract addresses
+/// Known deployed contract addresses, from the registry's built-in seed entries
 pub fn get_deployed_addresses() -> HashMap<Network, String> {
-    let mut addresses = HashMap::new();
-    addresses.insert(
-        Network::OpSepolia,
-        "0xc898870DF59123F346a0e3787966023e0ED78B93".to_string(),
-    );
-    addresses.insert(
-        Network::ArbitrumSepolia,
-        "0x89E4F30AFB281689632535e1657D15243a83b802".to_string(),
-    );
-    addresses.insert(
-        Network::Sepolia,
-        "0x3B550adA770897B0b215e414e45354861357788c".to_string(),
-    );
-    addresses.insert(
-        Network::Amoy,
-        "0x7aD0A9dB054101be9428fa89bB1194506586D1aD".to_string(),
-    );
-    addresses.insert(
-        Network::Cardona,
-        "0x4216a9c6EB59FcA323169Ef3194783d3dC9b7F23".to_string(),
-    );
-    addresses.insert(
-        Network::ScrollSepolia,
-        "0x6e650a339AbE4D9cf0aa8091fB2099284968beFf".to_string(),
-    );
-
-    addresses
-}
\ No newline at end of file
+    DeploymentRegistry::seed().addresses()
+}
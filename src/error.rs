@@ -1,14 +1,15 @@
 
+use primitive_types::U256;
 use thiserror::Error;
 
 /// Custom error types for MediToken operations
 #[derive(Error, Debug)]
 pub enum MediTokenError {
     #[error("Insufficient balance: required {required}, available {available}")]
-    InsufficientBalance { required: u64, available: u64 },
+    InsufficientBalance { required: U256, available: U256 },
     
     #[error("Insufficient allowance: spender {spender}, allowance {allowance}, needed {needed}")]
-    InsufficientAllowance { spender: String, allowance: u64, needed: u64 },
+    InsufficientAllowance { spender: String, allowance: U256, needed: U256 },
     
     #[error("Invalid address: {address}")]
     InvalidAddress { address: String },
@@ -25,18 +26,126 @@ pub enum MediTokenError {
     #[error("Approve from zero address")]
     ApproveFromZeroAddress,
     
+    #[error("Mint to zero address")]
+    MintToZeroAddress,
+    
+    #[error("Burn from zero address")]
+    BurnFromZeroAddress,
+    
+    #[error("Unauthorized caller: {caller}")]
+    Unauthorized { caller: String },
+    
+    #[error("Account {account} is missing role {role}")]
+    MissingRole { account: String, role: String },
+    
+    #[error("Token transfers are paused")]
+    Paused,
+    
+    #[error("Token transfers are not paused")]
+    NotPaused,
+    
+    #[error("Account {account} is frozen")]
+    AccountFrozen { account: String },
+    
+    #[error("Snapshot {id} does not exist")]
+    SnapshotNotFound { id: u64 },
+    
+    #[error("Proposal {id} not found")]
+    ProposalNotFound { id: u64 },
+    
+    #[error("Proposal {id} is {state}, expected {expected}")]
+    InvalidProposalState { id: u64, state: String, expected: String },
+    
+    #[error("{voter} has already voted on proposal {id}")]
+    AlreadyVoted { id: u64, voter: String },
+    
+    #[error("Insufficient voting power: required {required}, available {available}")]
+    InsufficientVotingPower { required: U256, available: U256 },
+    
+    #[error("Proposal {id} is timelocked until {eta}")]
+    TimelockNotExpired { id: u64, eta: u64 },
+    
+    #[error("Governance error: {message}")]
+    GovernanceError { message: String },
+    
+    #[error("Vesting grant {id} not found")]
+    GrantNotFound { id: u64 },
+    
+    #[error("Vesting error: {message}")]
+    VestingError { message: String },
+    
+    #[error("Reward program {id} not found")]
+    RewardProgramNotFound { id: u64 },
+    
+    #[error("Nonce {nonce} of {signer} has already been used")]
+    NonceAlreadyUsed { signer: String, nonce: u64 },
+    
+    #[error("{patient} has reached the period cap of reward program {program_id}")]
+    RewardCapReached { program_id: u64, patient: String },
+    
+    #[error("Reward error: {message}")]
+    RewardError { message: String },
+    
+    #[error("Claim {id} not found")]
+    ClaimNotFound { id: u64 },
+    
+    #[error("Claim {id} cannot move from {from} to {to}")]
+    InvalidClaimTransition { id: u64, from: String, to: String },
+    
+    #[error("Escrow {id} not found")]
+    EscrowNotFound { id: u64 },
+    
+    #[error("Escrow {id} is {state}, expected {expected}")]
+    InvalidEscrowState { id: u64, state: String, expected: String },
+    
+    #[error("Escrow {id} does not expire until {expires_at}")]
+    EscrowNotExpired { id: u64, expires_at: u64 },
+    
+    #[error("Escrow error: {message}")]
+    EscrowError { message: String },
+    
+    #[error("Consent {id} not found")]
+    ConsentNotFound { id: u64 },
+    
+    #[error("{accessor} has no consent to access data of {patient}")]
+    AccessDenied { accessor: String, patient: String },
+    
+    #[error("Consent error: {message}")]
+    ConsentError { message: String },
+    
+    #[error("Dataset offer {id} not found")]
+    OfferNotFound { id: u64 },
+    
+    #[error("Marketplace error: {message}")]
+    MarketplaceError { message: String },
+    
+    #[error("Supply cap exceeded: cap {cap}, requested total {requested}")]
+    SupplyCapExceeded { cap: U256, requested: U256 },
+    
     #[error("Arithmetic overflow")]
     ArithmeticOverflow,
     
     #[error("Arithmetic underflow")]
     ArithmeticUnderflow,
     
+    #[error("Invalid amount: {amount}")]
+    InvalidAmount { amount: String },
+    
+    #[error("Too many decimals in {amount}: token supports {decimals}")]
+    TooManyDecimals { amount: String, decimals: u8 },
+    
     #[error("Contract error: {message}")]
     ContractError { message: String },
     
+    #[error("ABI error: {message}")]
+    AbiError { message: String },
+    
     #[error("Network error: {message}")]
     NetworkError { message: String },
     
+    #[error("RPC error {code}: {message}")]
+    RpcError { code: i64, message: String, data: Option<String> },
+    
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
     
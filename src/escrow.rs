@@ -0,0 +1,282 @@
+//! Appointment escrow: a patient prepays a provider for a visit, and the payment is released
+//! on completion, refunded on cancellation or expiry, or split by an arbiter after a dispute.
+//!
+//! This module is not available for WASM targets
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::fmt;
+use serde::{Deserialize, Serialize};
+use primitive_types::U256;
+use crate::access_control::ARBITER_ROLE;
+use crate::clock::{Clock, SystemClock};
+use crate::error::{MediTokenError, Result};
+use crate::records::{EventLog, Records};
+use crate::token::MediToken;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BookingStatus {
+    /// Funds are locked until completion, cancellation or expiry
+    Booked,
+    Disputed,
+    Completed,
+    Refunded,
+    Resolved,
+}
+
+impl fmt::Display for BookingStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Funds a patient locked for an appointment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Booking {
+    pub id: u64,
+    pub patient: String,
+    pub provider: String,
+    pub amount: U256,
+    pub description: String,
+    /// After this time an undisputed booking can be refunded to the patient
+    pub expires_at: u64,
+    pub status: BookingStatus,
+}
+
+/// Events emitted by the appointment escrow
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EscrowEvent {
+    Booked {
+        id: u64,
+        patient: String,
+        provider: String,
+        amount: U256,
+        expires_at: u64,
+    },
+    Released {
+        id: u64,
+        provider: String,
+        amount: U256,
+    },
+    Refunded {
+        id: u64,
+        patient: String,
+        amount: U256,
+    },
+    Disputed {
+        id: u64,
+        by: String,
+        reason: String,
+    },
+    Resolved {
+        id: u64,
+        arbiter: String,
+        to_provider: U256,
+        to_patient: U256,
+    },
+}
+
+/// Appointment bookings whose payments sit in the `address` balance until the visit is settled.
+/// Disputes are settled by accounts holding the arbiter role on the token.
+#[derive(Debug, Clone)]
+pub struct AppointmentEscrow<C: Clock = SystemClock> {
+    pub address: String,
+    clock: C,
+    bookings: Records<Booking>,
+    events: EventLog<EscrowEvent>,
+}
+
+impl AppointmentEscrow<SystemClock> {
+    /// Creates an escrow that follows the system clock
+    pub fn new(address: String) -> Self {
+        Self::with_clock(address, SystemClock)
+    }
+}
+
+impl<C: Clock> AppointmentEscrow<C> {
+    /// Creates an escrow that reads time from `clock`
+    pub fn with_clock(address: String, clock: C) -> Self {
+        Self {
+            address,
+            clock,
+            bookings: Records::new(),
+            events: EventLog::new(),
+        }
+    }
+
+    /// Locks `amount` of the patient's tokens for an appointment and returns the booking id
+    pub fn book(
+        &mut self,
+        token: &mut MediToken,
+        patient: &str,
+        provider: &str,
+        amount: U256,
+        expires_at: u64,
+        description: &str,
+    ) -> Result<u64> {
+        if amount.is_zero() {
+            return Err(MediTokenError::InvalidAmount {
+                amount: amount.to_string(),
+            });
+        }
+        if expires_at <= self.clock.now() {
+            return Err(MediTokenError::EscrowError {
+                message: "Booking must expire in the future".to_string(),
+            });
+        }
+        token.transfer(patient, &self.address, amount)?;
+
+        let id = self.bookings.insert_with(|id| Booking {
+            id,
+            patient: patient.to_string(),
+            provider: provider.to_string(),
+            amount,
+            description: description.to_string(),
+            expires_at,
+            status: BookingStatus::Booked,
+        });
+
+        self.events.emit(EscrowEvent::Booked {
+            id,
+            patient: patient.to_string(),
+            provider: provider.to_string(),
+            amount,
+            expires_at,
+        });
+        Ok(id)
+    }
+
+    /// Pays the provider for a completed appointment (provider only)
+    pub fn complete(&mut self, token: &mut MediToken, caller: &str, id: u64) -> Result<()> {
+        let booking = self.expect_status(id, BookingStatus::Booked)?;
+        if booking.provider != caller {
+            return Err(MediTokenError::Unauthorized {
+                caller: caller.to_string(),
+            });
+        }
+
+        let (provider, amount) = (booking.provider.clone(), booking.amount);
+        token.transfer(&self.address, &provider, amount)?;
+        self.set_status(id, BookingStatus::Completed);
+        self.events.emit(EscrowEvent::Released { id, provider, amount });
+        Ok(())
+    }
+
+    /// Refunds the patient for an appointment the provider cancelled (provider only)
+    pub fn cancel(&mut self, token: &mut MediToken, caller: &str, id: u64) -> Result<()> {
+        let booking = self.expect_status(id, BookingStatus::Booked)?;
+        if booking.provider != caller {
+            return Err(MediTokenError::Unauthorized {
+                caller: caller.to_string(),
+            });
+        }
+        self.refund(token, id)
+    }
+
+    /// Refunds the patient once an undisputed booking has expired (anyone)
+    pub fn refund_expired(&mut self, token: &mut MediToken, id: u64) -> Result<()> {
+        let booking = self.expect_status(id, BookingStatus::Booked)?;
+        if self.clock.now() < booking.expires_at {
+            return Err(MediTokenError::EscrowNotExpired {
+                id,
+                expires_at: booking.expires_at,
+            });
+        }
+        self.refund(token, id)
+    }
+
+    /// Holds the funds for arbitration (patient or provider)
+    pub fn dispute(&mut self, caller: &str, id: u64, reason: &str) -> Result<()> {
+        let booking = self.expect_status(id, BookingStatus::Booked)?;
+        if booking.patient != caller && booking.provider != caller {
+            return Err(MediTokenError::Unauthorized {
+                caller: caller.to_string(),
+            });
+        }
+
+        self.set_status(id, BookingStatus::Disputed);
+        self.events.emit(EscrowEvent::Disputed {
+            id,
+            by: caller.to_string(),
+            reason: reason.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Settles a dispute, paying `to_provider` to the provider and the rest back to the patient
+    /// (arbiter role only)
+    pub fn resolve(&mut self, token: &mut MediToken, caller: &str, id: u64, to_provider: U256) -> Result<()> {
+        token.roles.check_role(ARBITER_ROLE, caller)?;
+        let booking = self.expect_status(id, BookingStatus::Disputed)?;
+        if to_provider > booking.amount {
+            return Err(MediTokenError::InvalidAmount {
+                amount: to_provider.to_string(),
+            });
+        }
+
+        let to_patient = booking.amount - to_provider;
+        let (patient, provider) = (booking.patient.clone(), booking.provider.clone());
+        let mut updated = token.clone();
+        if !to_provider.is_zero() {
+            updated.transfer(&self.address, &provider, to_provider)?;
+        }
+        if !to_patient.is_zero() {
+            updated.transfer(&self.address, &patient, to_patient)?;
+        }
+        *token = updated;
+
+        self.set_status(id, BookingStatus::Resolved);
+        self.events.emit(EscrowEvent::Resolved {
+            id,
+            arbiter: caller.to_string(),
+            to_provider,
+            to_patient,
+        });
+        Ok(())
+    }
+
+    /// Gets a booking by id
+    pub fn booking(&self, id: u64) -> Result<&Booking> {
+        self.bookings
+            .get(id)
+            .ok_or(MediTokenError::EscrowNotFound { id })
+    }
+
+    /// Total still held for open and disputed bookings
+    pub fn locked_balance(&self) -> U256 {
+        self.bookings
+            .values()
+            .filter(|booking| matches!(booking.status, BookingStatus::Booked | BookingStatus::Disputed))
+            .fold(U256::zero(), |total, booking| total + booking.amount)
+    }
+
+    /// Gets all events
+    pub fn get_events(&self) -> &[EscrowEvent] {
+        self.events.all()
+    }
+
+    fn refund(&mut self, token: &mut MediToken, id: u64) -> Result<()> {
+        let booking = &self.bookings[id];
+        let (patient, amount) = (booking.patient.clone(), booking.amount);
+        token.transfer(&self.address, &patient, amount)?;
+
+        self.set_status(id, BookingStatus::Refunded);
+        self.events.emit(EscrowEvent::Refunded { id, patient, amount });
+        Ok(())
+    }
+
+    fn expect_status(&self, id: u64, expected: BookingStatus) -> Result<&Booking> {
+        let booking = self.booking(id)?;
+        if booking.status != expected {
+            return Err(MediTokenError::InvalidEscrowState {
+                id,
+                state: booking.status.to_string(),
+                expected: expected.to_string(),
+            });
+        }
+        Ok(booking)
+    }
+
+    fn set_status(&mut self, id: u64, status: BookingStatus) {
+        self.bookings.get_mut(id).expect("booking exists").status = status;
+    }
+}
@@ -0,0 +1,466 @@
+//! Token-weighted governance: proposals are voted on with balances fixed by a token
+//! snapshot, then executed against the token after a timelock.
+//!
+//! This module is not available for WASM targets
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::collections::HashMap;
+use std::fmt;
+use serde::{Deserialize, Serialize};
+use primitive_types::U256;
+use crate::amount::BPS_DENOMINATOR;
+use crate::error::{MediTokenError, Result};
+use crate::records::{EventLog, Records};
+use crate::token::MediToken;
+
+/// Voting and timelock parameters; durations are in seconds
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GovernanceConfig {
+    /// How long a proposal accepts votes
+    pub voting_period: u64,
+    /// Delay between queueing a successful proposal and executing it
+    pub timelock_delay: u64,
+    /// Share of the snapshot total supply that must vote for or abstain
+    pub quorum_bps: u32,
+    /// Share of for + against votes that must be in favour
+    pub threshold_bps: u32,
+    /// Voting power needed to create a proposal
+    pub proposal_threshold: U256,
+}
+
+impl Default for GovernanceConfig {
+    fn default() -> Self {
+        Self {
+            voting_period: 3 * 24 * 60 * 60,
+            timelock_delay: 2 * 24 * 60 * 60,
+            quorum_bps: 400,
+            threshold_bps: 5_000,
+            proposal_threshold: U256::zero(),
+        }
+    }
+}
+
+impl GovernanceConfig {
+    /// Checks that the settings are usable
+    pub fn validate(&self) -> Result<()> {
+        if self.voting_period == 0 {
+            return Err(governance_error("Voting period must be positive"));
+        }
+        if self.quorum_bps > BPS_DENOMINATOR || self.threshold_bps > BPS_DENOMINATOR {
+            return Err(governance_error("Quorum and threshold must be at most 10000 basis points"));
+        }
+        Ok(())
+    }
+}
+
+/// A change applied to the token, or to governance itself, when a proposal executes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GovernanceAction {
+    Mint { to: String, amount: U256 },
+    Burn { from: String, amount: U256 },
+    Pause,
+    Unpause,
+    GrantRole { role: String, account: String },
+    RevokeRole { role: String, account: String },
+    SetCap { cap: Option<U256> },
+    UpdateConfig { config: GovernanceConfig },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VoteType {
+    For,
+    Against,
+    Abstain,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProposalState {
+    Active,
+    Defeated,
+    Succeeded,
+    Queued,
+    Executed,
+    Canceled,
+}
+
+impl fmt::Display for ProposalState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// A proposal and its tally
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Proposal {
+    pub id: u64,
+    pub proposer: String,
+    pub description: String,
+    pub actions: Vec<GovernanceAction>,
+    /// Token snapshot that fixes voting power
+    pub snapshot_id: u64,
+    pub start_time: u64,
+    pub end_time: u64,
+    /// For + abstain votes needed, from the total supply at the snapshot
+    pub quorum_votes: U256,
+    /// Threshold in force when the proposal was created
+    pub threshold_bps: u32,
+    pub for_votes: U256,
+    pub against_votes: U256,
+    pub abstain_votes: U256,
+    pub votes: HashMap<String, (VoteType, U256)>,
+    /// Earliest execution time once queued
+    pub eta: Option<u64>,
+    pub executed: bool,
+    pub canceled: bool,
+}
+
+impl Proposal {
+    /// Whether quorum was reached and enough votes were in favour
+    pub fn succeeded(&self) -> bool {
+        let participation = self.for_votes.saturating_add(self.abstain_votes);
+        let decided = self.for_votes.saturating_add(self.against_votes);
+        participation >= self.quorum_votes
+            && !self.for_votes.is_zero()
+            && self.for_votes.full_mul(U256::from(BPS_DENOMINATOR))
+                > decided.full_mul(U256::from(self.threshold_bps))
+    }
+}
+
+/// Events emitted by governance
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GovernanceEvent {
+    ProposalCreated {
+        id: u64,
+        proposer: String,
+        snapshot_id: u64,
+        end_time: u64,
+    },
+    VoteCast {
+        id: u64,
+        voter: String,
+        support: VoteType,
+        weight: U256,
+    },
+    ProposalQueued {
+        id: u64,
+        eta: u64,
+    },
+    ProposalExecuted {
+        id: u64,
+    },
+    ProposalCanceled {
+        id: u64,
+    },
+    DelegateChanged {
+        delegator: String,
+        from_delegate: String,
+        to_delegate: String,
+    },
+}
+
+/// Token-weighted governance over `MediToken` snapshots.
+/// Governance acts on the token as `address`, which must hold the roles its actions need
+/// (the snapshot role to create proposals, plus e.g. minter, pauser or admin).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Governance {
+    pub address: String,
+    pub config: GovernanceConfig,
+    proposals: Records<Proposal>,
+    /// Per delegator: (latest snapshot id when delegating, delegate). A delegation applies
+    /// to snapshots taken after it, so it cannot change the outcome of open proposals.
+    delegations: HashMap<String, Vec<(u64, String)>>,
+    events: EventLog<GovernanceEvent>,
+}
+
+impl Governance {
+    /// Creates governance acting on the token as `address`
+    pub fn new(address: String, config: GovernanceConfig) -> Result<Self> {
+        config.validate()?;
+        Ok(Self {
+            address,
+            config,
+            proposals: Records::new(),
+            delegations: HashMap::new(),
+            events: EventLog::new(),
+        })
+    }
+
+    /// Delegates the delegator's voting power, for proposals created from now on
+    pub fn delegate(&mut self, token: &MediToken, delegator: &str, delegatee: &str) {
+        let current_snapshot = token.current_snapshot_id();
+        let from_delegate = self.delegate_at(delegator, current_snapshot + 1);
+        let history = self.delegations.entry(delegator.to_string()).or_default();
+        if history.last().map(|(id, _)| *id) == Some(current_snapshot) {
+            history.pop();
+        }
+        history.push((current_snapshot, delegatee.to_string()));
+
+        self.events.emit(GovernanceEvent::DelegateChanged {
+            delegator: delegator.to_string(),
+            from_delegate,
+            to_delegate: delegatee.to_string(),
+        });
+    }
+
+    /// Account whose votes carry the delegator's balance at a snapshot; accounts vote for
+    /// themselves until they delegate
+    pub fn delegate_at(&self, delegator: &str, snapshot_id: u64) -> String {
+        self.delegations
+            .get(delegator)
+            .and_then(|history| history.iter().rev().find(|(id, _)| *id < snapshot_id))
+            .map(|(_, delegatee)| delegatee.clone())
+            .unwrap_or_else(|| delegator.to_string())
+    }
+
+    /// Own undelegated balance plus balances delegated to the account, at a snapshot
+    pub fn voting_power(&self, token: &MediToken, account: &str, snapshot_id: u64) -> Result<U256> {
+        self.power_with(account, snapshot_id, |holder| token.balance_of_at(holder, snapshot_id))
+    }
+
+    /// Voting power the next snapshot would record, from current balances
+    fn current_voting_power(&self, token: &MediToken, account: &str) -> Result<U256> {
+        self.power_with(account, token.current_snapshot_id() + 1, |holder| Ok(token.balance_of(holder)))
+    }
+
+    fn power_with(
+        &self,
+        account: &str,
+        snapshot_id: u64,
+        balance: impl Fn(&str) -> Result<U256>,
+    ) -> Result<U256> {
+        let mut power = if self.delegate_at(account, snapshot_id) == account {
+            balance(account)?
+        } else {
+            U256::zero()
+        };
+        for delegator in self.delegations.keys() {
+            if delegator != account && self.delegate_at(delegator, snapshot_id) == account {
+                power = power
+                    .checked_add(balance(delegator)?)
+                    .ok_or(MediTokenError::ArithmeticOverflow)?;
+            }
+        }
+        Ok(power)
+    }
+
+    /// Takes a token snapshot and opens a proposal for voting
+    pub fn propose(
+        &mut self,
+        token: &mut MediToken,
+        proposer: &str,
+        actions: Vec<GovernanceAction>,
+        description: &str,
+        now: u64,
+    ) -> Result<u64> {
+        if actions.is_empty() {
+            return Err(governance_error("Proposal must contain at least one action"));
+        }
+
+        // Checked before snapshotting so a rejected proposal leaves the token untouched
+        let power = self.current_voting_power(token, proposer)?;
+        if power < self.config.proposal_threshold {
+            return Err(MediTokenError::InsufficientVotingPower {
+                required: self.config.proposal_threshold,
+                available: power,
+            });
+        }
+        let snapshot_id = token.snapshot(&self.address)?;
+        let quorum_votes = token
+            .total_supply_at(snapshot_id)?
+            .checked_mul(U256::from(self.config.quorum_bps))
+            .ok_or(MediTokenError::ArithmeticOverflow)?
+            / U256::from(BPS_DENOMINATOR);
+
+        let end_time = now.saturating_add(self.config.voting_period);
+        let id = self.proposals.insert_with(|id| Proposal {
+            id,
+            proposer: proposer.to_string(),
+            description: description.to_string(),
+            actions,
+            snapshot_id,
+            start_time: now,
+            end_time,
+            quorum_votes,
+            threshold_bps: self.config.threshold_bps,
+            for_votes: U256::zero(),
+            against_votes: U256::zero(),
+            abstain_votes: U256::zero(),
+            votes: HashMap::new(),
+            eta: None,
+            executed: false,
+            canceled: false,
+        });
+
+        self.events.emit(GovernanceEvent::ProposalCreated {
+            id,
+            proposer: proposer.to_string(),
+            snapshot_id,
+            end_time,
+        });
+        Ok(id)
+    }
+
+    /// Votes with the voter's power at the proposal snapshot, returning the weight counted
+    pub fn cast_vote(
+        &mut self,
+        token: &MediToken,
+        id: u64,
+        voter: &str,
+        support: VoteType,
+        now: u64,
+    ) -> Result<U256> {
+        self.expect_state(id, now, ProposalState::Active)?;
+        let proposal = &self.proposals[id];
+        if proposal.votes.contains_key(voter) {
+            return Err(MediTokenError::AlreadyVoted {
+                id,
+                voter: voter.to_string(),
+            });
+        }
+        let weight = self.voting_power(token, voter, proposal.snapshot_id)?;
+        if weight.is_zero() {
+            return Err(MediTokenError::InsufficientVotingPower {
+                required: U256::one(),
+                available: weight,
+            });
+        }
+
+        let proposal = self.proposals.get_mut(id).expect("proposal exists");
+        let tally = match support {
+            VoteType::For => &mut proposal.for_votes,
+            VoteType::Against => &mut proposal.against_votes,
+            VoteType::Abstain => &mut proposal.abstain_votes,
+        };
+        *tally = tally.checked_add(weight).ok_or(MediTokenError::ArithmeticOverflow)?;
+        proposal.votes.insert(voter.to_string(), (support, weight));
+
+        self.events.emit(GovernanceEvent::VoteCast {
+            id,
+            voter: voter.to_string(),
+            support,
+            weight,
+        });
+        Ok(weight)
+    }
+
+    /// Queues a successful proposal behind the timelock, returning its earliest execution time
+    pub fn queue(&mut self, id: u64, now: u64) -> Result<u64> {
+        self.expect_state(id, now, ProposalState::Succeeded)?;
+        let eta = now.saturating_add(self.config.timelock_delay);
+        self.proposals.get_mut(id).expect("proposal exists").eta = Some(eta);
+
+        self.events.emit(GovernanceEvent::ProposalQueued { id, eta });
+        Ok(eta)
+    }
+
+    /// Applies a queued proposal's actions once its timelock has passed.
+    /// Actions run against a copy of the token, so a failing action leaves it untouched.
+    pub fn execute(&mut self, token: &mut MediToken, id: u64, now: u64) -> Result<()> {
+        self.expect_state(id, now, ProposalState::Queued)?;
+        let proposal = &self.proposals[id];
+        let eta = proposal.eta.expect("queued proposals have an eta");
+        if now < eta {
+            return Err(MediTokenError::TimelockNotExpired { id, eta });
+        }
+
+        let mut updated = token.clone();
+        let mut config = self.config.clone();
+        for action in &proposal.actions {
+            self.apply(&mut updated, &mut config, action)?;
+        }
+
+        *token = updated;
+        self.config = config;
+        self.proposals.get_mut(id).expect("proposal exists").executed = true;
+        self.events.emit(GovernanceEvent::ProposalExecuted { id });
+        Ok(())
+    }
+
+    /// Withdraws a proposal that has not been executed (proposer only)
+    pub fn cancel(&mut self, caller: &str, id: u64) -> Result<()> {
+        let proposal = self.proposal(id)?;
+        if proposal.proposer != caller {
+            return Err(MediTokenError::Unauthorized {
+                caller: caller.to_string(),
+            });
+        }
+        if proposal.executed || proposal.canceled {
+            return Err(MediTokenError::InvalidProposalState {
+                id,
+                state: if proposal.executed { ProposalState::Executed } else { ProposalState::Canceled }.to_string(),
+                expected: "not executed or canceled".to_string(),
+            });
+        }
+
+        self.proposals.get_mut(id).expect("proposal exists").canceled = true;
+        self.events.emit(GovernanceEvent::ProposalCanceled { id });
+        Ok(())
+    }
+
+    /// Gets a proposal by id
+    pub fn proposal(&self, id: u64) -> Result<&Proposal> {
+        self.proposals
+            .get(id)
+            .ok_or(MediTokenError::ProposalNotFound { id })
+    }
+
+    /// Gets the state of a proposal at time `now`
+    pub fn state(&self, id: u64, now: u64) -> Result<ProposalState> {
+        let proposal = self.proposal(id)?;
+        Ok(if proposal.canceled {
+            ProposalState::Canceled
+        } else if proposal.executed {
+            ProposalState::Executed
+        } else if proposal.eta.is_some() {
+            ProposalState::Queued
+        } else if now < proposal.end_time {
+            ProposalState::Active
+        } else if proposal.succeeded() {
+            ProposalState::Succeeded
+        } else {
+            ProposalState::Defeated
+        })
+    }
+
+    /// Gets all events
+    pub fn get_events(&self) -> &[GovernanceEvent] {
+        self.events.all()
+    }
+
+    fn apply(&self, token: &mut MediToken, config: &mut GovernanceConfig, action: &GovernanceAction) -> Result<()> {
+        let caller = self.address.as_str();
+        match action {
+            GovernanceAction::Mint { to, amount } => token.mint(caller, to, *amount)?,
+            GovernanceAction::Burn { from, amount } => token.burn(caller, from, *amount)?,
+            GovernanceAction::Pause => token.pause(caller)?,
+            GovernanceAction::Unpause => token.unpause(caller)?,
+            GovernanceAction::GrantRole { role, account } => token.grant_role(caller, role, account)?,
+            GovernanceAction::RevokeRole { role, account } => token.revoke_role(caller, role, account)?,
+            GovernanceAction::SetCap { cap } => token.set_cap(caller, *cap)?,
+            GovernanceAction::UpdateConfig { config: new_config } => {
+                new_config.validate()?;
+                *config = new_config.clone();
+                true
+            }
+        };
+        Ok(())
+    }
+
+    fn expect_state(&self, id: u64, now: u64, expected: ProposalState) -> Result<()> {
+        let state = self.state(id, now)?;
+        if state != expected {
+            return Err(MediTokenError::InvalidProposalState {
+                id,
+                state: state.to_string(),
+                expected: expected.to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+fn governance_error(message: &str) -> MediTokenError {
+    MediTokenError::GovernanceError {
+        message: message.to_string(),
+    }
+}
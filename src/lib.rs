@@ -10,21 +10,67 @@
 pub mod near_token;
 pub use near_token::MediTokenNEP141;
 
+// The payment escrow is a separate contract; its wasm is built by `near-payment-escrow/`
+#[cfg(not(target_arch = "wasm32"))]
+pub mod near_payment_escrow;
+
 // For non-WASM builds, include EVM/blockchain modules
 #[cfg(not(target_arch = "wasm32"))]
 pub mod token;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod error;
 #[cfg(not(target_arch = "wasm32"))]
+pub mod amount;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod access_control;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod compliance;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod snapshot;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod governance;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod clock;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod records;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod vesting;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod rewards;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod claims;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod escrow;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod consent;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod marketplace;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod utils;
 #[cfg(not(target_arch = "wasm32"))]
+pub mod transaction;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod rpc;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod deployment;
 #[cfg(not(target_arch = "wasm32"))]
+pub mod registry;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod abi;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod abi_codec;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod client;
 
 #[cfg(not(target_arch = "wasm32"))]
 pub use token::MediToken;
 #[cfg(not(target_arch = "wasm32"))]
 pub use error::MediTokenError;
 #[cfg(not(target_arch = "wasm32"))]
-pub use utils::Utils;
\ No newline at end of file
+pub use utils::Utils;
+#[cfg(not(target_arch = "wasm32"))]
+pub use amount::TokenAmount;
+#[cfg(not(target_arch = "wasm32"))]
+pub use client::MediTokenClient;
+#[cfg(not(target_arch = "wasm32"))]
+pub use primitive_types::U256;
\ No newline at end of file
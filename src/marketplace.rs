@@ -0,0 +1,272 @@
+//! Research data marketplace: patients list anonymized datasets, and researchers buy
+//! time-limited access with each sale split between the patient, platform and data custodian.
+//!
+//! This module is not available for WASM targets
+#![cfg(not(target_arch = "wasm32"))]
+
+use serde::{Deserialize, Serialize};
+use primitive_types::U256;
+use crate::access_control::DEFAULT_ADMIN_ROLE;
+use crate::amount::BPS_DENOMINATOR;
+use crate::clock::{Clock, SystemClock};
+use crate::error::{MediTokenError, Result};
+use crate::records::{EventLog, Records};
+use crate::token::MediToken;
+
+/// Shares of each sale taken by the platform and the data custodian, in basis points.
+/// The patient receives the rest, including any rounding remainder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeSplit {
+    pub platform_bps: u32,
+    pub custodian_bps: u32,
+}
+
+impl FeeSplit {
+    /// Checks that the fees leave a share for the patient
+    pub fn validate(&self) -> Result<()> {
+        if self.platform_bps.saturating_add(self.custodian_bps) > BPS_DENOMINATOR {
+            return Err(marketplace_error("Fees must add up to at most 10000 basis points"));
+        }
+        Ok(())
+    }
+
+    /// Splits `price` into (patient, platform, custodian) amounts that add up to exactly `price`
+    pub fn split(&self, price: U256) -> (U256, U256, U256) {
+        let share = |bps: u32| {
+            let amount = price.full_mul(U256::from(bps)) / U256::from(BPS_DENOMINATOR);
+            U256::try_from(amount).expect("share is at most the price")
+        };
+        let platform = share(self.platform_bps);
+        let custodian = share(self.custodian_bps);
+        (price - platform - custodian, platform, custodian)
+    }
+}
+
+/// What a patient offers and on which terms
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OfferTerms {
+    /// Description of the anonymized dataset
+    pub dataset: String,
+    pub price: U256,
+    /// Account holding the data, paid its share of each sale
+    pub custodian: String,
+    /// Permitted uses of the data
+    pub terms: String,
+    /// How long a purchase grants access, in seconds; `None` for no limit
+    pub access_duration: Option<u64>,
+}
+
+/// A listed dataset
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DatasetOffer {
+    pub id: u64,
+    pub patient: String,
+    pub terms: OfferTerms,
+    pub active: bool,
+}
+
+/// Access a researcher bought
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccessGrant {
+    pub offer_id: u64,
+    pub researcher: String,
+    pub price: U256,
+    pub purchased_at: u64,
+    pub expires_at: Option<u64>,
+}
+
+/// Events emitted by the marketplace
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MarketplaceEvent {
+    OfferListed {
+        id: u64,
+        patient: String,
+        price: U256,
+    },
+    OfferDelisted {
+        id: u64,
+    },
+    DatasetPurchased {
+        offer_id: u64,
+        researcher: String,
+        to_patient: U256,
+        to_platform: U256,
+        to_custodian: U256,
+    },
+    FeeSplitUpdated {
+        fee_split: FeeSplit,
+    },
+}
+
+/// Pay-per-access marketplace for anonymized patient datasets on `MediToken`
+#[derive(Debug, Clone)]
+pub struct Marketplace<C: Clock = SystemClock> {
+    /// Account that receives the platform fee
+    pub platform_account: String,
+    pub fee_split: FeeSplit,
+    clock: C,
+    offers: Records<DatasetOffer>,
+    grants: Vec<AccessGrant>,
+    events: EventLog<MarketplaceEvent>,
+}
+
+impl Marketplace<SystemClock> {
+    /// Creates a marketplace that follows the system clock
+    pub fn new(platform_account: String, fee_split: FeeSplit) -> Result<Self> {
+        Self::with_clock(platform_account, fee_split, SystemClock)
+    }
+}
+
+impl<C: Clock> Marketplace<C> {
+    /// Creates a marketplace that reads time from `clock`
+    pub fn with_clock(platform_account: String, fee_split: FeeSplit, clock: C) -> Result<Self> {
+        fee_split.validate()?;
+        Ok(Self {
+            platform_account,
+            fee_split,
+            clock,
+            offers: Records::new(),
+            grants: Vec::new(),
+            events: EventLog::new(),
+        })
+    }
+
+    /// Changes the fee split for future sales (token admin only)
+    pub fn set_fee_split(&mut self, token: &MediToken, caller: &str, fee_split: FeeSplit) -> Result<()> {
+        token.roles.check_role(DEFAULT_ADMIN_ROLE, caller)?;
+        fee_split.validate()?;
+
+        self.fee_split = fee_split;
+        self.events.emit(MarketplaceEvent::FeeSplitUpdated { fee_split });
+        Ok(())
+    }
+
+    /// Lists a patient's dataset and returns the offer id
+    pub fn list_offer(&mut self, patient: &str, terms: OfferTerms) -> Result<u64> {
+        if terms.price.is_zero() {
+            return Err(MediTokenError::InvalidAmount {
+                amount: terms.price.to_string(),
+            });
+        }
+        if terms.access_duration == Some(0) {
+            return Err(marketplace_error("Access duration must be positive"));
+        }
+
+        let price = terms.price;
+        let id = self.offers.insert_with(|id| DatasetOffer {
+            id,
+            patient: patient.to_string(),
+            terms,
+            active: true,
+        });
+
+        self.events.emit(MarketplaceEvent::OfferListed {
+            id,
+            patient: patient.to_string(),
+            price,
+        });
+        Ok(id)
+    }
+
+    /// Stops new sales of an offer; existing access is kept (patient only)
+    pub fn delist_offer(&mut self, caller: &str, id: u64) -> Result<()> {
+        let offer = self.offer(id)?;
+        if offer.patient != caller {
+            return Err(MediTokenError::Unauthorized {
+                caller: caller.to_string(),
+            });
+        }
+        if !offer.active {
+            return Err(marketplace_error("Offer is not listed"));
+        }
+
+        self.offers.get_mut(id).expect("offer exists").active = false;
+        self.events.emit(MarketplaceEvent::OfferDelisted { id });
+        Ok(())
+    }
+
+    /// Buys access to a dataset, paying the patient, platform and custodian their shares.
+    /// All three payments go through or none do.
+    pub fn purchase(&mut self, token: &mut MediToken, researcher: &str, id: u64) -> Result<&AccessGrant> {
+        let offer = self.offer(id)?;
+        if !offer.active {
+            return Err(marketplace_error("Offer is not listed"));
+        }
+        if self.has_access(researcher, id) {
+            return Err(marketplace_error("Researcher already has access"));
+        }
+
+        let price = offer.terms.price;
+        let (to_patient, to_platform, to_custodian) = self.fee_split.split(price);
+        let mut updated = token.clone();
+        for (recipient, amount) in [
+            (&offer.patient, to_patient),
+            (&self.platform_account, to_platform),
+            (&offer.terms.custodian, to_custodian),
+        ] {
+            if !amount.is_zero() {
+                updated.transfer(researcher, recipient, amount)?;
+            }
+        }
+        *token = updated;
+
+        let now = self.clock.now();
+        let expires_at = offer.terms.access_duration.map(|duration| now.saturating_add(duration));
+        self.grants.push(AccessGrant {
+            offer_id: id,
+            researcher: researcher.to_string(),
+            price,
+            purchased_at: now,
+            expires_at,
+        });
+        self.events.emit(MarketplaceEvent::DatasetPurchased {
+            offer_id: id,
+            researcher: researcher.to_string(),
+            to_patient,
+            to_platform,
+            to_custodian,
+        });
+        Ok(self.grants.last().expect("grant was just recorded"))
+    }
+
+    /// Whether a researcher currently has access to an offer's dataset
+    pub fn has_access(&self, researcher: &str, offer_id: u64) -> bool {
+        let now = self.clock.now();
+        self.grants.iter().any(|grant| {
+            grant.offer_id == offer_id
+                && grant.researcher == researcher
+                && grant.expires_at.is_none_or(|expires_at| now < expires_at)
+        })
+    }
+
+    /// Gets an offer by id
+    pub fn offer(&self, id: u64) -> Result<&DatasetOffer> {
+        self.offers
+            .get(id)
+            .ok_or(MediTokenError::OfferNotFound { id })
+    }
+
+    /// Gets the offers open for purchase
+    pub fn active_offers(&self) -> Vec<&DatasetOffer> {
+        self.offers.values().filter(|offer| offer.active).collect()
+    }
+
+    /// Gets every access grant made for an offer
+    pub fn grants_for_offer(&self, offer_id: u64) -> Vec<&AccessGrant> {
+        self.grants
+            .iter()
+            .filter(|grant| grant.offer_id == offer_id)
+            .collect()
+    }
+
+    /// Gets all events
+    pub fn get_events(&self) -> &[MarketplaceEvent] {
+        self.events.all()
+    }
+}
+
+fn marketplace_error(message: &str) -> MediTokenError {
+    MediTokenError::MarketplaceError {
+        message: message.to_string(),
+    }
+}
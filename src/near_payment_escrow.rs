@@ -0,0 +1,290 @@
+//! Provider payment escrow for MediToken on NEAR.
+//!
+//! Patients pay invoices with `ft_transfer_call` on the MediToken contract, naming the
+//! invoice in `msg` as `{"invoice_id":"..."}`. The escrow keeps what the invoice still
+//! owes, hands any overpayment back through `ft_on_transfer`'s return value, and lets
+//! the provider release the funds once the invoice is paid in full.
+
+use near_contract_standards::fungible_token::core::ext_ft_core;
+use near_contract_standards::fungible_token::receiver::FungibleTokenReceiver;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::serde_json::{self, json};
+use near_sdk::store::IterableMap;
+use near_sdk::{env, log, near_bindgen, require, AccountId, Gas, NearToken, PanicOnDefault, Promise, PromiseOrValue};
+
+const EVENT_STANDARD: &str = "meditoken";
+const EVENT_VERSION: &str = "1.0.0";
+
+/// Gas for the `ft_transfer` that pays a provider out
+const FT_TRANSFER_GAS: Gas = Gas::from_tgas(10);
+/// Gas for the callback that checks the payout succeeded
+const RELEASE_CALLBACK_GAS: Gas = Gas::from_tgas(10);
+/// Most payments recorded against one invoice; their storage is paid for when it is created
+pub const MAX_INVOICE_PAYMENTS: usize = 10;
+/// Largest borsh-encoded `InvoicePayment`: a 64-character account id, amount and timestamp
+const PAYMENT_STORAGE_BYTES: u64 = 4 + 64 + 16 + 8;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum InvoiceStatus {
+    /// Waiting for (the rest of) its payment
+    Open,
+    /// Fully paid and held by the escrow
+    Paid,
+    /// Paid out to the provider
+    Released,
+    /// Withdrawn by the provider before any payment
+    Cancelled,
+}
+
+/// A single payment received for an invoice
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct InvoicePayment {
+    pub payer_id: AccountId,
+    pub amount: U128,
+    /// Block timestamp in nanoseconds
+    pub timestamp: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Invoice {
+    pub invoice_id: String,
+    pub provider_id: AccountId,
+    /// Only this account may pay the invoice when set
+    pub patient_id: Option<AccountId>,
+    pub amount: U128,
+    pub paid: U128,
+    pub status: InvoiceStatus,
+    pub description: Option<String>,
+    pub payments: Vec<InvoicePayment>,
+}
+
+/// `msg` expected with `ft_transfer_call`
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PaymentMessage {
+    pub invoice_id: String,
+}
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
+pub struct ProviderPaymentEscrow {
+    token_id: AccountId,
+    invoices: IterableMap<String, Invoice>,
+}
+
+#[near_bindgen]
+impl ProviderPaymentEscrow {
+    /// Initializes an escrow that accepts payments in the fungible token at `token_id`
+    #[init]
+    pub fn new(token_id: AccountId) -> Self {
+        Self {
+            token_id,
+            invoices: IterableMap::new(b"i".to_vec()),
+        }
+    }
+
+    pub fn get_token(&self) -> AccountId {
+        self.token_id.clone()
+    }
+
+    /// Opens an invoice billed by the caller, who pays for its storage including room for
+    /// `MAX_INVOICE_PAYMENTS` payments. Any deposit above that is refunded.
+    #[payable]
+    pub fn create_invoice(
+        &mut self,
+        invoice_id: String,
+        amount: U128,
+        patient_id: Option<AccountId>,
+        description: Option<String>,
+    ) -> Invoice {
+        require!(!invoice_id.is_empty(), "Invoice id cannot be empty");
+        require!(amount.0 > 0, "The amount should be a positive number");
+        require!(!self.invoices.contains_key(&invoice_id), "Invoice already exists");
+
+        let invoice = Invoice {
+            invoice_id: invoice_id.clone(),
+            provider_id: env::predecessor_account_id(),
+            patient_id,
+            amount,
+            paid: U128(0),
+            status: InvoiceStatus::Open,
+            description,
+            payments: Vec::new(),
+        };
+        emit_event(
+            "invoice_created",
+            json!({
+                "invoice_id": invoice.invoice_id,
+                "provider_id": invoice.provider_id,
+                "patient_id": invoice.patient_id,
+                "amount": invoice.amount,
+            }),
+        );
+        let storage_before = env::storage_usage();
+        self.invoices.insert(invoice_id, invoice.clone());
+        self.invoices.flush();
+        let storage_used =
+            env::storage_usage() - storage_before + MAX_INVOICE_PAYMENTS as u64 * PAYMENT_STORAGE_BYTES;
+        let cost = env::storage_byte_cost().saturating_mul(storage_used as u128);
+        let deposit = env::attached_deposit();
+        if deposit < cost {
+            env::panic_str(&format!(
+                "Attach at least {} yoctoNEAR to cover the invoice storage",
+                cost.as_yoctonear()
+            ));
+        }
+        let refund = deposit.saturating_sub(cost);
+        if !refund.is_zero() {
+            Promise::new(env::predecessor_account_id()).transfer(refund);
+        }
+        invoice
+    }
+
+    /// Cancels an invoice that has not received any payment
+    pub fn cancel_invoice(&mut self, invoice_id: String) {
+        let invoice = self.provider_invoice_mut(&invoice_id);
+        require!(invoice.status == InvoiceStatus::Open, "Invoice is not open");
+        require!(invoice.paid.0 == 0, "Cannot cancel an invoice that has received payments");
+        invoice.status = InvoiceStatus::Cancelled;
+        emit_event("invoice_cancelled", json!({ "invoice_id": invoice_id }));
+    }
+
+    /// Pays a fully paid invoice out to its provider
+    pub fn release_payment(&mut self, invoice_id: String) -> Promise {
+        let token_id = self.token_id.clone();
+        let invoice = self.provider_invoice_mut(&invoice_id);
+        require!(invoice.status == InvoiceStatus::Paid, "Invoice is not fully paid");
+        invoice.status = InvoiceStatus::Released;
+        let provider_id = invoice.provider_id.clone();
+        let amount = invoice.paid;
+
+        ext_ft_core::ext(token_id)
+            .with_attached_deposit(NearToken::from_yoctonear(1))
+            .with_static_gas(FT_TRANSFER_GAS)
+            .ft_transfer(provider_id, amount, Some(format!("Invoice {}", invoice_id)))
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(RELEASE_CALLBACK_GAS)
+                    .on_payment_released(invoice_id),
+            )
+    }
+
+    /// Reopens the payout if the token transfer failed
+    #[private]
+    pub fn on_payment_released(&mut self, invoice_id: String) -> bool {
+        let released = matches!(env::promise_result(0), near_sdk::PromiseResult::Successful(_));
+        let invoice = self
+            .invoices
+            .get_mut(&invoice_id)
+            .unwrap_or_else(|| env::panic_str("Invoice not found"));
+        if released {
+            emit_event(
+                "payment_released",
+                json!({ "invoice_id": invoice_id, "provider_id": invoice.provider_id, "amount": invoice.paid }),
+            );
+        } else {
+            invoice.status = InvoiceStatus::Paid;
+        }
+        released
+    }
+
+    pub fn get_invoice(&self, invoice_id: String) -> Option<Invoice> {
+        self.invoices.get(&invoice_id).cloned()
+    }
+
+    /// Invoices billed by `provider_id`, paginated over all invoices
+    pub fn invoices_for_provider(
+        &self,
+        provider_id: AccountId,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<Invoice> {
+        self.invoices
+            .values()
+            .filter(|invoice| invoice.provider_id == provider_id)
+            .skip(from_index.unwrap_or(0) as usize)
+            .take(limit.unwrap_or(50) as usize)
+            .cloned()
+            .collect()
+    }
+}
+
+impl ProviderPaymentEscrow {
+    fn provider_invoice_mut(&mut self, invoice_id: &str) -> &mut Invoice {
+        let caller = env::predecessor_account_id();
+        let invoice = self
+            .invoices
+            .get_mut(invoice_id)
+            .unwrap_or_else(|| env::panic_str("Invoice not found"));
+        require!(invoice.provider_id == caller, "Only the invoice provider can do this");
+        invoice
+    }
+}
+
+/// Logs a NEP-297 event for the MediToken standard
+fn emit_event(event: &str, data: serde_json::Value) {
+    let event = json!({
+        "standard": EVENT_STANDARD,
+        "version": EVENT_VERSION,
+        "event": event,
+        "data": [data],
+    });
+    log!("EVENT_JSON:{}", event);
+}
+
+#[near_bindgen]
+impl FungibleTokenReceiver for ProviderPaymentEscrow {
+    /// Applies a payment to the invoice named in `msg` and returns the overpayment to refund.
+    /// Panicking here makes the token refund the whole transfer.
+    fn ft_on_transfer(&mut self, sender_id: AccountId, amount: U128, msg: String) -> PromiseOrValue<U128> {
+        require!(
+            env::predecessor_account_id() == self.token_id,
+            "Only payments in the escrow token are accepted"
+        );
+        let message: PaymentMessage =
+            serde_json::from_str(&msg).unwrap_or_else(|_| env::panic_str("Invalid payment message"));
+        let invoice = self
+            .invoices
+            .get_mut(&message.invoice_id)
+            .unwrap_or_else(|| env::panic_str("Invoice not found"));
+        require!(invoice.status == InvoiceStatus::Open, "Invoice is not open");
+        if let Some(patient_id) = &invoice.patient_id {
+            require!(patient_id == &sender_id, "Invoice is billed to another patient");
+        }
+
+        let remaining = invoice.amount.0 - invoice.paid.0;
+        if invoice.payments.len() + 1 >= MAX_INVOICE_PAYMENTS {
+            require!(
+                amount.0 >= remaining,
+                "The last payment allowed on an invoice must cover the remaining amount"
+            );
+        }
+
+        let accepted = amount.0.min(remaining);
+        invoice.paid = U128(invoice.paid.0 + accepted);
+        invoice.payments.push(InvoicePayment {
+            payer_id: sender_id.clone(),
+            amount: U128(accepted),
+            timestamp: env::block_timestamp(),
+        });
+        if invoice.paid == invoice.amount {
+            invoice.status = InvoiceStatus::Paid;
+        }
+        emit_event(
+            "invoice_payment",
+            json!({
+                "invoice_id": invoice.invoice_id,
+                "payer_id": sender_id,
+                "amount": U128(accepted),
+                "remaining": U128(invoice.amount.0 - invoice.paid.0),
+            }),
+        );
+
+        PromiseOrValue::Value(U128(amount.0 - accepted))
+    }
+}
@@ -1,45 +1,735 @@
 use near_contract_standards::fungible_token::core::FungibleTokenCore;
+use near_contract_standards::fungible_token::events::{FtBurn, FtMint};
 use near_contract_standards::fungible_token::FungibleTokenResolver;
 use near_contract_standards::fungible_token::metadata::{FungibleTokenMetadata, FungibleTokenMetadataProvider, FT_METADATA_SPEC};
 use near_contract_standards::fungible_token::FungibleToken;
 use near_contract_standards::storage_management::{StorageManagement, StorageBalance, StorageBalanceBounds};
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::{near_bindgen, AccountId, NearToken, PanicOnDefault, PromiseOrValue};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::serde_json::{self, json};
+use near_sdk::store::{IterableMap, IterableSet, LookupMap, LookupSet, Vector};
+use near_sdk::{assert_one_yocto, env, log, near_bindgen, require, AccountId, Gas, NearToken, PanicOnDefault, Promise, PromiseOrValue};
 use near_sdk::json_types::U128;
 
+/// Role that administers every role without an explicit admin
+pub const DEFAULT_ADMIN_ROLE: &str = "admin";
+/// Role allowed to mint and burn tokens
+pub const MINTER_ROLE: &str = "minter";
+/// Role allowed to pause and unpause transfers
+pub const PAUSER_ROLE: &str = "pauser";
+/// Role held by healthcare providers (doctors, hospitals, pharmacies)
+pub const PROVIDER_ROLE: &str = "provider";
+/// Role held by registered patients
+pub const PATIENT_ROLE: &str = "patient";
+/// Role allowed to register new receivers at the sponsor's or the pool's expense
+pub const SPONSOR_ROLE: &str = "sponsor";
+/// Role allowed to freeze and unfreeze accounts
+pub const COMPLIANCE_ROLE: &str = "compliance";
+/// Role allowed to take balance snapshots
+pub const SNAPSHOT_ROLE: &str = "snapshot";
+
+const EVENT_STANDARD: &str = "meditoken";
+const EVENT_VERSION: &str = "1.0.0";
+
+/// Version of the current `MediTokenNEP141` state layout
+pub const STATE_VERSION: u32 = 2;
+/// Storage key of the state version; absent for the original v1 deployment
+pub const STATE_VERSION_KEY: &[u8] = b"v";
+/// Gas reserved for the `migrate` call made by `upgrade`
+const MIGRATE_GAS: Gas = Gas::from_tgas(50);
+/// Largest accepted metadata icon data URL, in bytes
+pub const MAX_ICON_LENGTH: usize = 4 * 1024;
+
+/// State layout of the original testnet deployment (`harshitnayan.testnet`)
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct MediTokenNEP141V1 {
+    pub token: FungibleToken,
+    pub metadata: FungibleTokenMetadata,
+}
+
+/// Limits on registering receivers without a storage deposit of their own
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SponsorshipConfig {
+    /// Whether transfers from sponsors register unregistered receivers
+    pub enabled: bool,
+    /// Accounts the contract's own balance may pay for at once; 0 disables the pool
+    pub pool_max_accounts: u32,
+    /// Accounts a single sponsor may have registered at once
+    pub max_accounts_per_sponsor: u32,
+}
+
+impl Default for SponsorshipConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            pool_max_accounts: 0,
+            max_accounts_per_sponsor: 100,
+        }
+    }
+}
+
+/// An account whose storage was paid by a sponsor or the contract pool
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SponsoredAccount {
+    pub account_id: AccountId,
+    pub sponsor_id: AccountId,
+    /// Paid from the contract's own balance rather than the sponsor's deposit
+    pub paid_by_pool: bool,
+}
+
+/// A value as it stood when snapshot `snapshot_id` was taken, recorded at its first change afterwards
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy)]
+struct Checkpoint {
+    snapshot_id: u64,
+    value: u128,
+}
+
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 pub struct MediTokenNEP141 {
+    owner_id: AccountId,
     token: FungibleToken,
     metadata: FungibleTokenMetadata,
+    role_members: LookupSet<(String, AccountId)>,
+    role_admins: LookupMap<String, String>,
+    paused: bool,
+    sponsorship: SponsorshipConfig,
+    sponsored: IterableMap<AccountId, SponsoredAccount>,
+    sponsor_balances: LookupMap<AccountId, u128>,
+    sponsor_counts: LookupMap<AccountId, u32>,
+    pool_sponsored: u32,
+    frozen: IterableSet<AccountId>,
+    balance_checkpoints: LookupMap<(AccountId, u32), Checkpoint>,
+    checkpoint_counts: LookupMap<AccountId, u32>,
+    supply_checkpoints: Vector<Checkpoint>,
+    /// Block height each snapshot was taken at; snapshot `id` is at index `id - 1`
+    snapshot_heights: Vector<u64>,
 }
 
 #[near_bindgen]
 impl MediTokenNEP141 {
+    /// Initializes the token, using the default MediToken metadata unless `metadata` is given
     #[init]
-    pub fn new(owner_id: AccountId, total_supply: U128) -> Self {
-        let mut contract = Self {
-            token: FungibleToken::new(b"t".to_vec()),
-            metadata: FungibleTokenMetadata {
-                spec: FT_METADATA_SPEC.to_string(),
-                name: "MediToken".to_string(),
-                symbol: "MEDT".to_string(),
-                icon: None,
-                reference: None,
-                reference_hash: None,
-                decimals: 18,
-            },
-        };
+    pub fn new(owner_id: AccountId, total_supply: U128, metadata: Option<FungibleTokenMetadata>) -> Self {
+        let metadata = metadata.unwrap_or_else(default_metadata);
+        assert_valid_metadata(&metadata);
+        let mut contract = Self::with_owner(owner_id.clone(), FungibleToken::new(b"t".to_vec()), metadata);
         contract.token.internal_register_account(&owner_id);
         contract.token.internal_deposit(&owner_id, total_supply.0);
+        FtMint {
+            owner_id: &owner_id,
+            amount: total_supply,
+            memo: Some("Initial token supply"),
+        }
+        .emit();
+        write_state_version();
+        contract
+    }
+
+    /// Rewrites the stored state into the current layout.
+    /// v1 state has no owner, so only the contract account can migrate it and `owner_id`
+    /// (defaulting to the contract account) becomes the owner.
+    #[init(ignore_state)]
+    pub fn migrate(owner_id: Option<AccountId>) -> Self {
+        let caller = env::predecessor_account_id();
+        let from_version = read_state_version();
+        let contract = match from_version {
+            1 => {
+                require!(
+                    caller == env::current_account_id(),
+                    "Only the contract account can migrate v1 state"
+                );
+                let old: MediTokenNEP141V1 =
+                    env::state_read().unwrap_or_else(|| env::panic_str("No contract state to migrate"));
+                Self::with_owner(owner_id.unwrap_or_else(env::current_account_id), old.token, old.metadata)
+            }
+            STATE_VERSION => {
+                let contract: Self =
+                    env::state_read().unwrap_or_else(|| env::panic_str("No contract state to migrate"));
+                require!(
+                    caller == contract.owner_id || caller == env::current_account_id(),
+                    "Only the owner can migrate the contract"
+                );
+                contract
+            }
+            version => env::panic_str(&format!("Unsupported state version {}", version)),
+        };
+
+        write_state_version();
+        emit_event(
+            "state_migrated",
+            json!({ "from_version": from_version, "to_version": STATE_VERSION }),
+        );
+        contract
+    }
+
+    /// Deploys the wasm passed as the raw call input, then calls `migrate` in the same batch
+    pub fn upgrade(&mut self) -> Promise {
+        require!(
+            env::predecessor_account_id() == self.owner_id,
+            "Only the owner can upgrade the contract"
+        );
+        let code = env::input().unwrap_or_default();
+        require!(!code.is_empty(), "Missing contract code");
+
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .function_call("migrate".to_string(), b"{}".to_vec(), NearToken::from_yoctonear(0), MIGRATE_GAS)
+    }
+
+    pub fn state_version(&self) -> u32 {
+        read_state_version()
+    }
+
+    pub fn get_owner(&self) -> AccountId {
+        self.owner_id.clone()
+    }
+
+    /// Replaces the token metadata; decimals cannot change once balances exist
+    pub fn set_metadata(&mut self, metadata: FungibleTokenMetadata) {
+        require!(
+            env::predecessor_account_id() == self.owner_id,
+            "Only the owner can update metadata"
+        );
+        require!(
+            metadata.decimals == self.metadata.decimals,
+            "Decimals cannot be changed"
+        );
+        assert_valid_metadata(&metadata);
+        self.metadata = metadata;
+        emit_event(
+            "metadata_updated",
+            json!({ "name": self.metadata.name, "symbol": self.metadata.symbol }),
+        );
+    }
+
+    /// Mints new tokens to a storage-registered account
+    pub fn ft_mint(&mut self, account_id: AccountId, amount: U128, memo: Option<String>) {
+        self.assert_role(MINTER_ROLE, &env::predecessor_account_id());
+        require!(amount.0 > 0, "The amount should be a positive number");
+        self.assert_registered(&account_id);
+        self.assert_not_frozen(&account_id);
+        self.checkpoint_balance(&account_id);
+        self.checkpoint_supply();
+        self.token.internal_deposit(&account_id, amount.0);
+        FtMint {
+            owner_id: &account_id,
+            amount,
+            memo: memo.as_deref(),
+        }
+        .emit();
+    }
+
+    /// Burns tokens held by an account
+    pub fn ft_burn(&mut self, account_id: AccountId, amount: U128, memo: Option<String>) {
+        self.assert_role(MINTER_ROLE, &env::predecessor_account_id());
+        require!(amount.0 > 0, "The amount should be a positive number");
+        self.assert_registered(&account_id);
+        self.checkpoint_balance(&account_id);
+        self.checkpoint_supply();
+        self.token.internal_withdraw(&account_id, amount.0);
+        FtBurn {
+            owner_id: &account_id,
+            amount,
+            memo: memo.as_deref(),
+        }
+        .emit();
+    }
+
+    pub fn has_role(&self, role: String, account_id: AccountId) -> bool {
+        self.role_members.contains(&(role, account_id))
+    }
+
+    pub fn get_role_admin(&self, role: String) -> String {
+        self.role_admins
+            .get(&role)
+            .cloned()
+            .unwrap_or_else(|| DEFAULT_ADMIN_ROLE.to_string())
+    }
+
+    pub fn grant_role(&mut self, role: String, account_id: AccountId) {
+        let sender_id = env::predecessor_account_id();
+        self.assert_role(&self.get_role_admin(role.clone()), &sender_id);
+        if self.role_members.insert((role.clone(), account_id.clone())) {
+            emit_event("role_granted", json!({ "role": role, "account_id": account_id, "sender_id": sender_id }));
+        }
+    }
+
+    pub fn revoke_role(&mut self, role: String, account_id: AccountId) {
+        let sender_id = env::predecessor_account_id();
+        self.assert_role(&self.get_role_admin(role.clone()), &sender_id);
+        self.internal_revoke_role(role, account_id, sender_id);
+    }
+
+    /// Gives up a role held by the caller
+    pub fn renounce_role(&mut self, role: String) {
+        let sender_id = env::predecessor_account_id();
+        self.internal_revoke_role(role, sender_id.clone(), sender_id);
+    }
+
+    pub fn set_role_admin(&mut self, role: String, admin_role: String) {
+        let sender_id = env::predecessor_account_id();
+        let previous_admin_role = self.get_role_admin(role.clone());
+        self.assert_role(&previous_admin_role, &sender_id);
+        self.role_admins.insert(role.clone(), admin_role.clone());
+        emit_event(
+            "role_admin_changed",
+            json!({ "role": role, "previous_admin_role": previous_admin_role, "new_admin_role": admin_role }),
+        );
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn sponsorship_config(&self) -> SponsorshipConfig {
+        self.sponsorship.clone()
+    }
+
+    pub fn set_sponsorship_config(&mut self, config: SponsorshipConfig) {
+        require!(
+            env::predecessor_account_id() == self.owner_id,
+            "Only the owner can configure sponsorship"
+        );
+        self.sponsorship = config;
+        emit_event("sponsorship_configured", json!({ "config": self.sponsorship }));
+    }
+
+    /// Adds the attached deposit to the caller's sponsorship balance
+    #[payable]
+    pub fn sponsor_deposit(&mut self) -> U128 {
+        let sponsor_id = env::predecessor_account_id();
+        self.assert_role(SPONSOR_ROLE, &sponsor_id);
+        let balance = self.sponsor_balance(sponsor_id.clone()).0 + env::attached_deposit().as_yoctonear();
+        self.sponsor_balances.insert(sponsor_id, balance);
+        U128(balance)
+    }
+
+    /// Returns unspent sponsorship deposit to the caller, all of it if `amount` is omitted
+    #[payable]
+    pub fn sponsor_withdraw(&mut self, amount: Option<U128>) -> U128 {
+        assert_one_yocto();
+        let sponsor_id = env::predecessor_account_id();
+        let balance = self.sponsor_balance(sponsor_id.clone()).0;
+        let amount = amount.map(|amount| amount.0).unwrap_or(balance);
+        require!(amount <= balance, "Not enough sponsorship balance");
+        self.sponsor_balances.insert(sponsor_id.clone(), balance - amount);
+        if amount > 0 {
+            Promise::new(sponsor_id).transfer(NearToken::from_yoctonear(amount));
+        }
+        U128(balance - amount)
+    }
+
+    pub fn sponsor_balance(&self, account_id: AccountId) -> U128 {
+        U128(self.sponsor_balances.get(&account_id).copied().unwrap_or(0))
+    }
+
+    pub fn sponsored_account(&self, account_id: AccountId) -> Option<SponsoredAccount> {
+        self.sponsored.get(&account_id).cloned()
+    }
+
+    /// Lists sponsored accounts, paginated
+    pub fn sponsored_accounts(&self, from_index: Option<u32>, limit: Option<u32>) -> Vec<SponsoredAccount> {
+        self.sponsored
+            .values()
+            .skip(from_index.unwrap_or(0) as usize)
+            .take(limit.unwrap_or(100) as usize)
+            .cloned()
+            .collect()
+    }
+
+    /// Halts all transfers
+    pub fn pause(&mut self) {
+        let sender_id = env::predecessor_account_id();
+        self.assert_role(PAUSER_ROLE, &sender_id);
+        require!(!self.paused, "Token transfers are paused");
+        self.paused = true;
+        emit_event("paused", json!({ "account_id": sender_id }));
+    }
+
+    /// Resumes transfers
+    pub fn unpause(&mut self) {
+        let sender_id = env::predecessor_account_id();
+        self.assert_role(PAUSER_ROLE, &sender_id);
+        require!(self.paused, "Token transfers are not paused");
+        self.paused = false;
+        emit_event("unpaused", json!({ "account_id": sender_id }));
+    }
+
+    pub fn is_frozen(&self, account_id: AccountId) -> bool {
+        self.frozen.contains(&account_id)
+    }
+
+    pub fn frozen_accounts(&self, from_index: Option<u32>, limit: Option<u32>) -> Vec<AccountId> {
+        self.frozen
+            .iter()
+            .skip(from_index.unwrap_or(0) as usize)
+            .take(limit.unwrap_or(100) as usize)
+            .cloned()
+            .collect()
+    }
+
+    /// Blocks an account from sending or receiving tokens
+    pub fn freeze_account(&mut self, account_id: AccountId) {
+        let sender_id = env::predecessor_account_id();
+        self.assert_role(COMPLIANCE_ROLE, &sender_id);
+        if self.frozen.insert(account_id.clone()) {
+            emit_event("account_frozen", json!({ "account_id": account_id, "sender_id": sender_id }));
+        }
+    }
+
+    /// Lifts a freeze
+    pub fn unfreeze_account(&mut self, account_id: AccountId) {
+        let sender_id = env::predecessor_account_id();
+        self.assert_role(COMPLIANCE_ROLE, &sender_id);
+        if self.frozen.remove(&account_id) {
+            emit_event("account_unfrozen", json!({ "account_id": account_id, "sender_id": sender_id }));
+        }
+    }
+
+    /// Records balances and total supply under a new snapshot id and returns it
+    pub fn snapshot(&mut self) -> u64 {
+        let sender_id = env::predecessor_account_id();
+        self.assert_role(SNAPSHOT_ROLE, &sender_id);
+        let block_height = env::block_height();
+        self.snapshot_heights.push(block_height);
+        let id = self.current_snapshot_id();
+        emit_event("snapshot", json!({ "id": id, "block_height": block_height }));
+        id
+    }
+
+    /// Id of the latest snapshot, 0 before the first one
+    pub fn current_snapshot_id(&self) -> u64 {
+        self.snapshot_heights.len() as u64
+    }
+
+    /// Balance of an account when a snapshot was taken
+    pub fn ft_balance_of_at(&self, account_id: AccountId, snapshot_id: u64) -> U128 {
+        self.assert_snapshot_taken(snapshot_id);
+        let count = self.checkpoint_counts.get(&account_id).copied().unwrap_or(0);
+        let index = first_checkpoint_from(count, snapshot_id, |i| {
+            self.balance_checkpoints[&(account_id.clone(), i)].snapshot_id
+        });
+        if index < count {
+            U128(self.balance_checkpoints[&(account_id, index)].value)
+        } else {
+            self.token.ft_balance_of(account_id)
+        }
+    }
+
+    /// Total supply when a snapshot was taken
+    pub fn ft_total_supply_at(&self, snapshot_id: u64) -> U128 {
+        self.assert_snapshot_taken(snapshot_id);
+        let index = first_checkpoint_from(self.supply_checkpoints.len(), snapshot_id, |i| {
+            self.supply_checkpoints[i].snapshot_id
+        });
+        match self.supply_checkpoints.get(index) {
+            Some(checkpoint) => U128(checkpoint.value),
+            None => self.token.ft_total_supply(),
+        }
+    }
+
+    /// Balance of an account as of the latest snapshot taken at or before `block_height`
+    pub fn ft_balance_of_at_block(&self, account_id: AccountId, block_height: u64) -> U128 {
+        self.ft_balance_of_at(account_id, self.snapshot_at_block(block_height))
+    }
+
+    /// Total supply as of the latest snapshot taken at or before `block_height`
+    pub fn ft_total_supply_at_block(&self, block_height: u64) -> U128 {
+        self.ft_total_supply_at(self.snapshot_at_block(block_height))
+    }
+}
+
+impl MediTokenNEP141 {
+    /// Builds state around an existing token, granting the owner its initial roles
+    fn with_owner(owner_id: AccountId, token: FungibleToken, metadata: FungibleTokenMetadata) -> Self {
+        let mut contract = Self {
+            owner_id: owner_id.clone(),
+            token,
+            metadata,
+            role_members: LookupSet::new(b"r".to_vec()),
+            role_admins: LookupMap::new(b"a".to_vec()),
+            paused: false,
+            sponsorship: SponsorshipConfig::default(),
+            sponsored: IterableMap::new(b"s".to_vec()),
+            sponsor_balances: LookupMap::new(b"b".to_vec()),
+            sponsor_counts: LookupMap::new(b"c".to_vec()),
+            pool_sponsored: 0,
+            frozen: IterableSet::new(b"f".to_vec()),
+            balance_checkpoints: LookupMap::new(b"k".to_vec()),
+            checkpoint_counts: LookupMap::new(b"n".to_vec()),
+            supply_checkpoints: Vector::new(b"h".to_vec()),
+            snapshot_heights: Vector::new(b"g".to_vec()),
+        };
+        for role in [DEFAULT_ADMIN_ROLE, MINTER_ROLE, PAUSER_ROLE, COMPLIANCE_ROLE, SNAPSHOT_ROLE] {
+            contract.role_members.insert((role.to_string(), owner_id.clone()));
+        }
         contract
     }
+
+    fn assert_snapshot_taken(&self, snapshot_id: u64) {
+        require!(
+            snapshot_id > 0 && snapshot_id <= self.current_snapshot_id(),
+            "Snapshot not found"
+        );
+    }
+
+    /// Id of the latest snapshot taken at or before `block_height`
+    fn snapshot_at_block(&self, block_height: u64) -> u64 {
+        require!(block_height <= env::block_height(), "Block height is in the future");
+        let (mut low, mut high) = (0, self.snapshot_heights.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.snapshot_heights[mid] <= block_height {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        require!(low > 0, "No snapshot was taken at or before this block height");
+        low as u64
+    }
+
+    /// Records an account's balance before its first change since the latest snapshot.
+    /// Nothing is written until a snapshot has been taken.
+    fn checkpoint_balance(&mut self, account_id: &AccountId) {
+        let snapshot_id = self.current_snapshot_id();
+        if snapshot_id == 0 {
+            return;
+        }
+        let count = self.checkpoint_counts.get(account_id).copied().unwrap_or(0);
+        if count > 0 && self.balance_checkpoints[&(account_id.clone(), count - 1)].snapshot_id == snapshot_id {
+            return;
+        }
+        let value = self.token.accounts.get(account_id).unwrap_or(0);
+        self.balance_checkpoints
+            .insert((account_id.clone(), count), Checkpoint { snapshot_id, value });
+        self.checkpoint_counts.insert(account_id.clone(), count + 1);
+    }
+
+    /// Records the total supply before its first change since the latest snapshot
+    fn checkpoint_supply(&mut self) {
+        let snapshot_id = self.current_snapshot_id();
+        if snapshot_id == 0 {
+            return;
+        }
+        let len = self.supply_checkpoints.len();
+        if len > 0 && self.supply_checkpoints[len - 1].snapshot_id == snapshot_id {
+            return;
+        }
+        self.supply_checkpoints.push(Checkpoint { snapshot_id, value: self.token.total_supply });
+    }
+
+    /// Registers an unregistered receiver of a transfer from a sponsor, paying its storage
+    /// from the sponsor's deposit or, failing that, the contract pool
+    fn sponsor_registration(&mut self, sponsor_id: &AccountId, account_id: &AccountId) {
+        if self.token.accounts.contains_key(account_id)
+            || !self.sponsorship.enabled
+            || !self.role_members.contains(&(SPONSOR_ROLE.to_string(), sponsor_id.clone()))
+        {
+            return;
+        }
+
+        let count = self.sponsor_counts.get(sponsor_id).copied().unwrap_or(0);
+        require!(
+            count < self.sponsorship.max_accounts_per_sponsor,
+            "Sponsor has reached its sponsored account limit"
+        );
+
+        let cost = self.storage_balance_bounds().min.as_yoctonear();
+        let balance = self.sponsor_balance(sponsor_id.clone()).0;
+        let paid_by_pool = if balance >= cost {
+            self.sponsor_balances.insert(sponsor_id.clone(), balance - cost);
+            false
+        } else {
+            require!(
+                self.pool_sponsored < self.sponsorship.pool_max_accounts,
+                "No storage sponsorship available"
+            );
+            self.pool_sponsored += 1;
+            true
+        };
+
+        self.sponsor_counts.insert(sponsor_id.clone(), count + 1);
+        self.token.internal_register_account(account_id);
+        self.sponsored.insert(
+            account_id.clone(),
+            SponsoredAccount {
+                account_id: account_id.clone(),
+                sponsor_id: sponsor_id.clone(),
+                paid_by_pool,
+            },
+        );
+        emit_event(
+            "storage_sponsored",
+            json!({ "account_id": account_id, "sponsor_id": sponsor_id, "paid_by_pool": paid_by_pool }),
+        );
+    }
+
+    /// Unregisters a sponsored caller, returning the storage deposit to whoever paid it
+    fn unregister_sponsored(&mut self, sponsored: SponsoredAccount, force: bool) -> bool {
+        let account_id = sponsored.account_id;
+        let balance = self.token.accounts.get(&account_id).unwrap_or(0);
+        require!(
+            balance == 0 || force,
+            "Can't unregister the account with the positive balance without force"
+        );
+
+        self.token.accounts.remove(&account_id);
+        self.token.total_supply -= balance;
+        if balance > 0 {
+            FtBurn {
+                owner_id: &account_id,
+                amount: U128(balance),
+                memo: Some("Sponsored account closed"),
+            }
+            .emit();
+        }
+
+        self.sponsored.remove(&account_id);
+        let count = self.sponsor_counts.get(&sponsored.sponsor_id).copied().unwrap_or(1);
+        self.sponsor_counts.insert(sponsored.sponsor_id.clone(), count - 1);
+        if sponsored.paid_by_pool {
+            self.pool_sponsored -= 1;
+        } else {
+            let refund = self.sponsor_balance(sponsored.sponsor_id.clone()).0
+                + self.storage_balance_bounds().min.as_yoctonear();
+            self.sponsor_balances.insert(sponsored.sponsor_id, refund);
+        }
+        true
+    }
+
+    fn assert_registered(&self, account_id: &AccountId) {
+        require!(
+            self.token.accounts.contains_key(account_id),
+            format!("The account {} is not registered", account_id)
+        );
+    }
+
+    fn assert_not_paused(&self) {
+        require!(!self.paused, "Token transfers are paused");
+    }
+
+    fn assert_not_frozen(&self, account_id: &AccountId) {
+        require!(
+            !self.frozen.contains(account_id),
+            format!("Account {} is frozen", account_id)
+        );
+    }
+
+    fn assert_role(&self, role: &str, account_id: &AccountId) {
+        require!(
+            self.role_members.contains(&(role.to_string(), account_id.clone())),
+            format!("Account {} is missing role {}", account_id, role)
+        );
+    }
+
+    fn internal_revoke_role(&mut self, role: String, account_id: AccountId, sender_id: AccountId) {
+        if self.role_members.remove(&(role.clone(), account_id.clone())) {
+            emit_event("role_revoked", json!({ "role": role, "account_id": account_id, "sender_id": sender_id }));
+        }
+    }
+}
+
+fn default_metadata() -> FungibleTokenMetadata {
+    FungibleTokenMetadata {
+        spec: FT_METADATA_SPEC.to_string(),
+        name: "MediToken".to_string(),
+        symbol: "MEDT".to_string(),
+        icon: None,
+        reference: None,
+        reference_hash: None,
+        decimals: 18,
+    }
+}
+
+/// Checks metadata against NEP-148, with readable messages for the common mistakes
+fn assert_valid_metadata(metadata: &FungibleTokenMetadata) {
+    require!(metadata.spec == FT_METADATA_SPEC, format!("Metadata spec must be {}", FT_METADATA_SPEC));
+    require!(!metadata.name.is_empty(), "Metadata name cannot be empty");
+    require!(!metadata.symbol.is_empty(), "Metadata symbol cannot be empty");
+    if let Some(icon) = &metadata.icon {
+        require!(icon.starts_with("data:"), "Icon must be a data URL");
+        require!(
+            icon.len() <= MAX_ICON_LENGTH,
+            format!("Icon must be at most {} bytes", MAX_ICON_LENGTH)
+        );
+    }
+    if let Some(reference) = &metadata.reference {
+        require!(
+            ["https://", "ipfs://", "ar://"].iter().any(|scheme| reference.starts_with(scheme))
+                && !reference.contains(char::is_whitespace),
+            "Reference must be an https, ipfs or ar URL"
+        );
+    }
+    require!(
+        metadata.reference.is_some() == metadata.reference_hash.is_some(),
+        "Reference and reference_hash must be set together"
+    );
+    if let Some(reference_hash) = &metadata.reference_hash {
+        require!(
+            reference_hash.0.len() == 32,
+            "Reference hash must be a base64-encoded sha256 hash"
+        );
+    }
+    metadata.assert_valid();
+}
+
+/// Index of the first of `len` checkpoints recorded for `snapshot_id` or a later one, or `len`
+/// if none was. The checkpoint found holds the value when `snapshot_id` was taken.
+fn first_checkpoint_from(len: u32, snapshot_id: u64, id_at: impl Fn(u32) -> u64) -> u32 {
+    let (mut low, mut high) = (0, len);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if id_at(mid) < snapshot_id {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
+fn read_state_version() -> u32 {
+    env::storage_read(STATE_VERSION_KEY)
+        .map(|bytes| {
+            u32::from_le_bytes(
+                bytes
+                    .try_into()
+                    .unwrap_or_else(|_| env::panic_str("Corrupt state version")),
+            )
+        })
+        .unwrap_or(1)
+}
+
+fn write_state_version() {
+    env::storage_write(STATE_VERSION_KEY, &STATE_VERSION.to_le_bytes());
+}
+
+/// Logs a NEP-297 event for the MediToken standard
+fn emit_event(event: &str, data: serde_json::Value) {
+    let event = json!({
+        "standard": EVENT_STANDARD,
+        "version": EVENT_VERSION,
+        "event": event,
+        "data": [data],
+    });
+    log!("EVENT_JSON:{}", event);
 }
 
 #[near_bindgen]
 impl FungibleTokenCore for MediTokenNEP141 {
     #[payable]
     fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>) {
+        let sender_id = env::predecessor_account_id();
+        self.assert_not_paused();
+        self.assert_not_frozen(&sender_id);
+        self.assert_not_frozen(&receiver_id);
+        self.sponsor_registration(&sender_id, &receiver_id);
+        self.checkpoint_balance(&sender_id);
+        self.checkpoint_balance(&receiver_id);
         self.token.ft_transfer(receiver_id, amount, memo)
     }
 
@@ -51,6 +741,13 @@ impl FungibleTokenCore for MediTokenNEP141 {
         memo: Option<String>,
         msg: String,
     ) -> PromiseOrValue<U128> {
+        let sender_id = env::predecessor_account_id();
+        self.assert_not_paused();
+        self.assert_not_frozen(&sender_id);
+        self.assert_not_frozen(&receiver_id);
+        self.sponsor_registration(&sender_id, &receiver_id);
+        self.checkpoint_balance(&sender_id);
+        self.checkpoint_balance(&receiver_id);
         self.token.ft_transfer_call(receiver_id, amount, memo, msg)
     }
 
@@ -67,6 +764,10 @@ impl FungibleTokenCore for MediTokenNEP141 {
 impl FungibleTokenResolver for MediTokenNEP141 {
     #[private]
     fn ft_resolve_transfer(&mut self, owner_id: AccountId, receiver_id: AccountId, amount: U128) -> U128 {
+        // Refunds move tokens back from the receiver, or burn them if either side has unregistered
+        self.checkpoint_balance(&owner_id);
+        self.checkpoint_balance(&receiver_id);
+        self.checkpoint_supply();
         self.token.ft_resolve_transfer(owner_id, receiver_id, amount)
     }
 }
@@ -96,7 +797,18 @@ impl StorageManagement for MediTokenNEP141 {
 
     #[payable]
     fn storage_unregister(&mut self, force: Option<bool>) -> bool {
-        self.token.storage_unregister(force)
+        let account_id = env::predecessor_account_id();
+        if self.token.accounts.contains_key(&account_id) {
+            self.checkpoint_balance(&account_id);
+            self.checkpoint_supply();
+        }
+        match self.sponsored.get(&account_id).cloned() {
+            Some(sponsored) => {
+                assert_one_yocto();
+                self.unregister_sponsored(sponsored, force.unwrap_or(false))
+            }
+            None => self.token.storage_unregister(force),
+        }
     }
 
     fn storage_balance_bounds(&self) -> StorageBalanceBounds {
@@ -0,0 +1,88 @@
+//! Id-keyed record storage and event logs shared by the healthcare modules.
+//!
+//! This module is not available for WASM targets
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::collections::BTreeMap;
+use std::ops::Index;
+use serde::{Deserialize, Serialize};
+
+/// Records keyed by sequential ids, the first being 1
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Records<T> {
+    entries: BTreeMap<u64, T>,
+    next_id: u64,
+}
+
+impl<T> Default for Records<T> {
+    fn default() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            next_id: 1,
+        }
+    }
+}
+
+impl<T> Records<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores the record built for the next id and returns that id
+    pub fn insert_with(&mut self, build: impl FnOnce(u64) -> T) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.entries.insert(id, build(id));
+        id
+    }
+
+    pub fn get(&self, id: u64) -> Option<&T> {
+        self.entries.get(&id)
+    }
+
+    pub fn get_mut(&mut self, id: u64) -> Option<&mut T> {
+        self.entries.get_mut(&id)
+    }
+
+    /// Records in id order
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.entries.values()
+    }
+}
+
+impl<T> Index<u64> for Records<T> {
+    type Output = T;
+
+    fn index(&self, id: u64) -> &T {
+        self.entries
+            .get(&id)
+            .unwrap_or_else(|| panic!("no record with id {}", id))
+    }
+}
+
+/// Events in the order they were emitted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventLog<E> {
+    events: Vec<E>,
+}
+
+impl<E> Default for EventLog<E> {
+    fn default() -> Self {
+        Self { events: Vec::new() }
+    }
+}
+
+impl<E> EventLog<E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn emit(&mut self, event: E) {
+        self.events.push(event);
+    }
+
+    /// Gets all events, oldest first
+    pub fn all(&self) -> &[E] {
+        &self.events
+    }
+}
@@ -0,0 +1,234 @@
+//! This module is not available for WASM targets
+#![cfg(not(target_arch = "wasm32"))]
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use crate::abi::get_meditoken_abi;
+use crate::deployment::{DeployedContract, Network};
+use crate::utils::Utils;
+
+/// Format version written to registry files
+pub const REGISTRY_FORMAT_VERSION: u32 = 1;
+/// Version of the MediToken contract deployed by this crate
+pub const CONTRACT_VERSION: &str = "1.0.0";
+/// Registry file used by the `deploy` binary
+pub const DEFAULT_REGISTRY_PATH: &str = "deployments.json";
+
+/// A single MediToken deployment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeploymentRecord {
+    pub network: Network,
+    pub contract_version: String,
+    pub address: String,
+    /// Unknown for deployments that predate the registry
+    pub transaction_hash: Option<String>,
+    pub block_number: Option<u64>,
+    pub deployer: Option<String>,
+    /// Keccak-256 of the ABI the contract was deployed with
+    pub abi_hash: String,
+}
+
+/// Difference between two registries for one (network, version) key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryChange {
+    Added(DeploymentRecord),
+    Removed(DeploymentRecord),
+    Changed {
+        before: DeploymentRecord,
+        after: DeploymentRecord,
+    },
+}
+
+/// Deployments keyed by network and contract version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeploymentRegistry {
+    version: u32,
+    deployments: Vec<DeploymentRecord>,
+}
+
+impl DeploymentRecord {
+    /// Builds a record for a deployment made with the current ABI
+    pub fn from_deployed(deployed: &DeployedContract, contract_version: &str) -> Self {
+        Self {
+            network: deployed.network.clone(),
+            contract_version: contract_version.to_string(),
+            address: deployed.address.clone(),
+            transaction_hash: Some(deployed.transaction_hash.clone()),
+            block_number: Some(deployed.block_number),
+            deployer: Some(deployed.deployer.clone()),
+            abi_hash: abi_hash(),
+        }
+    }
+
+    fn key(&self) -> (String, String) {
+        (self.network.name().to_string(), self.contract_version.clone())
+    }
+}
+
+impl Default for DeploymentRegistry {
+    fn default() -> Self {
+        Self {
+            version: REGISTRY_FORMAT_VERSION,
+            deployments: Vec::new(),
+        }
+    }
+}
+
+impl DeploymentRegistry {
+    /// Creates an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry containing the built-in testnet deployments
+    pub fn seed() -> Self {
+        let seeds = [
+            (Network::OpSepolia, "0xc898870DF59123F346a0e3787966023e0ED78B93"),
+            (Network::ArbitrumSepolia, "0x89E4F30AFB281689632535e1657D15243a83b802"),
+            (Network::Sepolia, "0x3B550adA770897B0b215e414e45354861357788c"),
+            (Network::Amoy, "0x7aD0A9dB054101be9428fa89bB1194506586D1aD"),
+            (Network::Cardona, "0x4216a9c6EB59FcA323169Ef3194783d3dC9b7F23"),
+            (Network::ScrollSepolia, "0x6e650a339AbE4D9cf0aa8091fB2099284968beFf"),
+        ];
+
+        let mut registry = Self::new();
+        for (network, address) in seeds {
+            registry.add(DeploymentRecord {
+                network,
+                contract_version: CONTRACT_VERSION.to_string(),
+                address: address.to_string(),
+                transaction_hash: None,
+                block_number: None,
+                deployer: None,
+                abi_hash: abi_hash(),
+            });
+        }
+        registry
+    }
+
+    /// Loads a registry file, falling back to the seed entries if it does not exist
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::seed());
+        }
+
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut registry: Self = serde_json::from_str(&contents)
+            .with_context(|| format!("{} is not a valid deployments registry", path.display()))?;
+        if registry.version > REGISTRY_FORMAT_VERSION {
+            bail!(
+                "{} uses registry format {} but only up to {} is supported",
+                path.display(),
+                registry.version,
+                REGISTRY_FORMAT_VERSION
+            );
+        }
+
+        registry.version = REGISTRY_FORMAT_VERSION;
+        registry.sort();
+        Ok(registry)
+    }
+
+    /// Writes the registry as pretty-printed JSON
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Adds a record, returning the one it replaced for the same network and version
+    pub fn add(&mut self, record: DeploymentRecord) -> Option<DeploymentRecord> {
+        let previous = match self.deployments.iter().position(|r| r.key() == record.key()) {
+            Some(index) => Some(std::mem::replace(&mut self.deployments[index], record)),
+            None => {
+                self.deployments.push(record);
+                None
+            }
+        };
+        self.sort();
+        previous
+    }
+
+    /// Looks up the deployment of a specific contract version
+    pub fn get(&self, network: &Network, contract_version: &str) -> Option<&DeploymentRecord> {
+        self.deployments
+            .iter()
+            .find(|r| &r.network == network && r.contract_version == contract_version)
+    }
+
+    /// Looks up the newest contract version deployed on a network
+    pub fn latest(&self, network: &Network) -> Option<&DeploymentRecord> {
+        self.deployments
+            .iter()
+            .filter(|r| &r.network == network)
+            .max_by_key(|r| version_key(&r.contract_version))
+    }
+
+    /// All records, ordered by network then contract version
+    pub fn records(&self) -> &[DeploymentRecord] {
+        &self.deployments
+    }
+
+    /// Latest address per network
+    pub fn addresses(&self) -> HashMap<Network, String> {
+        Network::all()
+            .into_iter()
+            .filter_map(|network| {
+                let address = self.latest(&network)?.address.clone();
+                Some((network, address))
+            })
+            .collect()
+    }
+
+    /// Changes needed to turn `self` into `other`
+    pub fn diff(&self, other: &Self) -> Vec<RegistryChange> {
+        let before: BTreeMap<_, _> = self.deployments.iter().map(|r| (r.key(), r)).collect();
+        let after: BTreeMap<_, _> = other.deployments.iter().map(|r| (r.key(), r)).collect();
+
+        let mut changes = Vec::new();
+        for (key, record) in &before {
+            match after.get(key) {
+                None => changes.push(RegistryChange::Removed((*record).clone())),
+                Some(updated) if updated != record => changes.push(RegistryChange::Changed {
+                    before: (*record).clone(),
+                    after: (*updated).clone(),
+                }),
+                Some(_) => {}
+            }
+        }
+        for (key, record) in &after {
+            if !before.contains_key(key) {
+                changes.push(RegistryChange::Added((*record).clone()));
+            }
+        }
+        changes
+    }
+
+    fn sort(&mut self) {
+        self.deployments.sort_by(|a, b| {
+            (a.network.name(), version_key(&a.contract_version))
+                .cmp(&(b.network.name(), version_key(&b.contract_version)))
+        });
+    }
+}
+
+/// 0x-prefixed Keccak-256 hash of the bundled MediToken ABI
+pub fn abi_hash() -> String {
+    let abi = serde_json::to_vec(&get_meditoken_abi()).expect("ABI serializes to JSON");
+    format!("0x{}", hex::encode(Utils::keccak256(&abi)))
+}
+
+/// Orders dotted versions numerically (`1.10.0` after `1.9.0`), falling back to text
+fn version_key(version: &str) -> (Vec<u64>, String) {
+    let numeric = version
+        .trim_start_matches('v')
+        .split('.')
+        .map(|part| part.parse::<u64>().unwrap_or(0))
+        .collect();
+    (numeric, version.to_string())
+}
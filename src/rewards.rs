@@ -0,0 +1,316 @@
+//! Health-activity rewards: patients claim tokens for checkups, vaccinations and medication
+//! adherence by presenting attestations signed by a provider, within per-period caps.
+//!
+//! This module is not available for WASM targets
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::collections::{HashMap, HashSet};
+use k256::ecdsa::{RecoveryId, Signature, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use primitive_types::U256;
+use crate::access_control::{DEFAULT_ADMIN_ROLE, PROVIDER_ROLE};
+use crate::clock::{Clock, SystemClock};
+use crate::error::{MediTokenError, Result};
+use crate::records::{EventLog, Records};
+use crate::token::MediToken;
+use crate::transaction::public_key_address;
+use crate::utils::Utils;
+
+/// Prefix of every attestation digest, so provider signatures cannot be reused elsewhere
+const ATTESTATION_DOMAIN: &[u8] = b"MediToken reward attestation";
+/// How old an activity can be and still be claimed, in seconds
+pub const DEFAULT_MAX_ATTESTATION_AGE: u64 = 90 * 24 * 60 * 60;
+
+/// Health activity a program rewards
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ActivityKind {
+    CheckupCompleted,
+    Vaccination,
+    /// Paid once per `streak_days` consecutive days of adherence
+    MedicationAdherence { streak_days: u32 },
+}
+
+/// Where payouts come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RewardFunding {
+    /// Minted to the patient; the rewards account needs the minter role
+    Mint,
+    /// Transferred from the rewards account balance
+    Pool,
+}
+
+/// A reward program and its per-patient limits
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RewardProgram {
+    pub name: String,
+    pub activity: ActivityKind,
+    /// Reward per checkup, vaccination or completed adherence streak
+    pub reward: U256,
+    /// Length of a cap window in seconds
+    pub period: u64,
+    /// Most a patient can earn from the program in one window
+    pub period_cap: U256,
+    pub funding: RewardFunding,
+    pub active: bool,
+}
+
+/// A provider's statement that a patient completed a program activity
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attestation {
+    pub program_id: u64,
+    pub patient: String,
+    /// Days of adherence; ignored by checkup and vaccination programs
+    pub streak_days: u32,
+    /// When the activity took place
+    pub timestamp: u64,
+    /// Unique per provider; a nonce can be claimed once
+    pub nonce: u64,
+}
+
+impl Attestation {
+    /// Keccak-256 digest signed by the provider, bound to the rewards account `rewards_address`
+    pub fn digest(&self, rewards_address: &str) -> [u8; 32] {
+        let mut message = ATTESTATION_DOMAIN.to_vec();
+        for text in [rewards_address, self.patient.as_str()] {
+            message.extend_from_slice(&(text.len() as u32).to_be_bytes());
+            message.extend_from_slice(text.as_bytes());
+        }
+        message.extend_from_slice(&self.program_id.to_be_bytes());
+        message.extend_from_slice(&self.streak_days.to_be_bytes());
+        message.extend_from_slice(&self.timestamp.to_be_bytes());
+        message.extend_from_slice(&self.nonce.to_be_bytes());
+        Utils::keccak256(&message)
+    }
+
+    /// Signs the attestation with a provider key
+    pub fn sign(self, key: &SigningKey, rewards_address: &str) -> Result<SignedAttestation> {
+        let (signature, recovery_id) = key
+            .sign_prehash_recoverable(&self.digest(rewards_address))
+            .map_err(|e| rewards_error(&format!("Signing failed: {}", e)))?;
+        let mut bytes = signature.to_bytes().to_vec();
+        bytes.push(recovery_id.to_byte());
+        Ok(SignedAttestation {
+            attestation: self,
+            signature: bytes,
+        })
+    }
+}
+
+/// An attestation with its 65-byte recoverable signature (r, s, recovery id)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedAttestation {
+    pub attestation: Attestation,
+    pub signature: Vec<u8>,
+}
+
+impl SignedAttestation {
+    /// Address of the key that signed the attestation
+    pub fn signer(&self, rewards_address: &str) -> Result<String> {
+        if self.signature.len() != 65 {
+            return Err(rewards_error("Signature must be 65 bytes"));
+        }
+        let signature = Signature::from_slice(&self.signature[..64])
+            .map_err(|_| rewards_error("Malformed signature"))?;
+        let recovery_id = RecoveryId::from_byte(self.signature[64])
+            .ok_or_else(|| rewards_error("Malformed recovery id"))?;
+        let key = VerifyingKey::recover_from_prehash(
+            &self.attestation.digest(rewards_address),
+            &signature,
+            recovery_id,
+        )
+        .map_err(|_| rewards_error("Signature does not match the attestation"))?;
+        Ok(public_key_address(&key))
+    }
+}
+
+/// Events emitted by the rewards engine
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RewardEvent {
+    ProgramCreated {
+        id: u64,
+        name: String,
+    },
+    ProgramUpdated {
+        id: u64,
+    },
+    RewardPaid {
+        program_id: u64,
+        patient: String,
+        provider: String,
+        nonce: u64,
+        amount: U256,
+        funding: RewardFunding,
+    },
+}
+
+/// Pays `MediToken` rewards against provider-signed attestations.
+/// Attestations count only when signed by a holder of the provider role and bound to
+/// `address`, which mints `Mint` rewards and pays `Pool` rewards from its balance.
+#[derive(Debug, Clone)]
+pub struct Rewards<C: Clock = SystemClock> {
+    pub address: String,
+    clock: C,
+    pub max_attestation_age: u64,
+    programs: Records<RewardProgram>,
+    /// (provider, nonce) pairs already claimed
+    used_nonces: HashSet<(String, u64)>,
+    /// Paid so far per (program, patient, period index)
+    period_totals: HashMap<(u64, String, u64), U256>,
+    events: EventLog<RewardEvent>,
+}
+
+impl Rewards<SystemClock> {
+    /// Creates a rewards engine that follows the system clock
+    pub fn new(address: String) -> Self {
+        Self::with_clock(address, SystemClock)
+    }
+}
+
+impl<C: Clock> Rewards<C> {
+    /// Creates a rewards engine that reads time from `clock`
+    pub fn with_clock(address: String, clock: C) -> Self {
+        Self {
+            address,
+            clock,
+            max_attestation_age: DEFAULT_MAX_ATTESTATION_AGE,
+            programs: Records::new(),
+            used_nonces: HashSet::new(),
+            period_totals: HashMap::new(),
+            events: EventLog::new(),
+        }
+    }
+
+    /// Adds a program and returns its id (token admin only)
+    pub fn create_program(&mut self, token: &MediToken, caller: &str, program: RewardProgram) -> Result<u64> {
+        token.roles.check_role(DEFAULT_ADMIN_ROLE, caller)?;
+        validate_program(&program)?;
+
+        let name = program.name.clone();
+        let id = self.programs.insert_with(|_| program);
+        self.events.emit(RewardEvent::ProgramCreated { id, name });
+        Ok(id)
+    }
+
+    /// Replaces a program's settings (token admin only)
+    pub fn update_program(&mut self, token: &MediToken, caller: &str, id: u64, program: RewardProgram) -> Result<()> {
+        token.roles.check_role(DEFAULT_ADMIN_ROLE, caller)?;
+        self.program(id)?;
+        validate_program(&program)?;
+
+        *self.programs.get_mut(id).expect("program exists") = program;
+        self.events.emit(RewardEvent::ProgramUpdated { id });
+        Ok(())
+    }
+
+    /// Gets a program by id
+    pub fn program(&self, id: u64) -> Result<&RewardProgram> {
+        self.programs
+            .get(id)
+            .ok_or(MediTokenError::RewardProgramNotFound { id })
+    }
+
+    /// Verifies an attestation and pays its reward to the patient, returning the amount.
+    /// Payouts are clipped to what is left of the patient's cap for the period the activity
+    /// took place in.
+    pub fn claim(&mut self, token: &mut MediToken, signed: &SignedAttestation) -> Result<U256> {
+        let attestation = &signed.attestation;
+        let provider = signed.signer(&self.address)?;
+        if !token.has_role(PROVIDER_ROLE, &provider) {
+            return Err(rewards_error(&format!("Signer {} is not a provider", provider)));
+        }
+        if self.used_nonces.contains(&(provider.clone(), attestation.nonce)) {
+            return Err(MediTokenError::NonceAlreadyUsed {
+                signer: provider,
+                nonce: attestation.nonce,
+            });
+        }
+
+        let program = self.program(attestation.program_id)?;
+        if !program.active {
+            return Err(rewards_error("Program is not active"));
+        }
+        let now = self.clock.now();
+        if attestation.timestamp > now {
+            return Err(rewards_error("Activity is in the future"));
+        }
+        if now - attestation.timestamp > self.max_attestation_age {
+            return Err(rewards_error("Activity is too old to claim"));
+        }
+        let earned = match program.activity {
+            ActivityKind::MedicationAdherence { streak_days } => program
+                .reward
+                .checked_mul(U256::from(attestation.streak_days / streak_days))
+                .ok_or(MediTokenError::ArithmeticOverflow)?,
+            ActivityKind::CheckupCompleted | ActivityKind::Vaccination => program.reward,
+        };
+        if earned.is_zero() {
+            return Err(rewards_error("Streak is too short for a reward"));
+        }
+
+        let period_key = (attestation.program_id, attestation.patient.clone(), attestation.timestamp / program.period);
+        let paid = self.period_totals.get(&period_key).copied().unwrap_or_default();
+        let amount = earned.min(program.period_cap.saturating_sub(paid));
+        if amount.is_zero() {
+            return Err(MediTokenError::RewardCapReached {
+                program_id: attestation.program_id,
+                patient: attestation.patient.clone(),
+            });
+        }
+
+        let funding = program.funding;
+        match funding {
+            RewardFunding::Mint => token.mint(&self.address, &attestation.patient, amount)?,
+            RewardFunding::Pool => token.transfer(&self.address, &attestation.patient, amount)?,
+        };
+
+        self.used_nonces.insert((provider.clone(), attestation.nonce));
+        self.period_totals.insert(period_key, paid + amount);
+        self.events.emit(RewardEvent::RewardPaid {
+            program_id: attestation.program_id,
+            patient: attestation.patient.clone(),
+            provider,
+            nonce: attestation.nonce,
+            amount,
+            funding,
+        });
+        Ok(amount)
+    }
+
+    /// Whether a provider's nonce has been claimed
+    pub fn is_nonce_used(&self, provider: &str, nonce: u64) -> bool {
+        self.used_nonces.contains(&(provider.to_string(), nonce))
+    }
+
+    /// Amount a patient has earned from a program in the current period
+    pub fn earned_this_period(&self, program_id: u64, patient: &str) -> Result<U256> {
+        let program = self.program(program_id)?;
+        let key = (program_id, patient.to_string(), self.clock.now() / program.period);
+        Ok(self.period_totals.get(&key).copied().unwrap_or_default())
+    }
+
+    /// Gets all events
+    pub fn get_events(&self) -> &[RewardEvent] {
+        self.events.all()
+    }
+}
+
+fn validate_program(program: &RewardProgram) -> Result<()> {
+    if program.period == 0 {
+        return Err(rewards_error("Program period must be positive"));
+    }
+    if let ActivityKind::MedicationAdherence { streak_days: 0 } = program.activity {
+        return Err(rewards_error("Adherence streak must be at least one day"));
+    }
+    if program.reward.is_zero() {
+        return Err(MediTokenError::InvalidAmount {
+            amount: program.reward.to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn rewards_error(message: &str) -> MediTokenError {
+    MediTokenError::RewardError {
+        message: message.to_string(),
+    }
+}
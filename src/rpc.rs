@@ -0,0 +1,175 @@
+//! This module is not available for WASM targets
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use primitive_types::U256;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use crate::error::{MediTokenError, Result};
+use crate::transaction::SignedTransaction;
+
+/// Log entry emitted by a transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Log {
+    pub address: String,
+    pub topics: Vec<String>,
+    pub data: String,
+}
+
+/// Receipt returned by `eth_getTransactionReceipt`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionReceipt {
+    pub transaction_hash: String,
+    pub block_number: String,
+    pub gas_used: String,
+    pub contract_address: Option<String>,
+    pub status: Option<String>,
+    #[serde(default)]
+    pub logs: Vec<Log>,
+}
+
+impl TransactionReceipt {
+    pub fn block_number(&self) -> Result<u64> {
+        parse_quantity(&self.block_number).map(|n| n.low_u64())
+    }
+
+    pub fn gas_used(&self) -> Result<u64> {
+        parse_quantity(&self.gas_used).map(|n| n.low_u64())
+    }
+
+    /// Whether the transaction executed successfully (pre-Byzantium receipts have no status)
+    pub fn succeeded(&self) -> bool {
+        self.status
+            .as_deref()
+            .map(|status| parse_quantity(status).map(|s| s == U256::one()).unwrap_or(false))
+            .unwrap_or(true)
+    }
+}
+
+/// Minimal Ethereum JSON-RPC client
+pub struct RpcClient {
+    url: String,
+    http: reqwest::Client,
+    next_id: AtomicU64,
+}
+
+impl RpcClient {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            http: reqwest::Client::new(),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Sends a JSON-RPC request and decodes its `result`
+    pub async fn request<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": self.next_id.fetch_add(1, Ordering::Relaxed),
+            "method": method,
+            "params": params,
+        });
+
+        let response: Value = self
+            .http
+            .post(&self.url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| MediTokenError::NetworkError { message: e.to_string() })?
+            .json()
+            .await
+            .map_err(|e| MediTokenError::NetworkError { message: e.to_string() })?;
+
+        if let Some(error) = response.get("error") {
+            return Err(MediTokenError::RpcError {
+                code: error["code"].as_i64().unwrap_or_default(),
+                message: error["message"].as_str().unwrap_or_default().to_string(),
+                data: error.get("data").and_then(Value::as_str).map(str::to_string),
+            });
+        }
+
+        Ok(serde_json::from_value(response.get("result").cloned().unwrap_or(Value::Null))?)
+    }
+
+    pub async fn chain_id(&self) -> Result<u64> {
+        let chain_id: String = self.request("eth_chainId", json!([])).await?;
+        parse_quantity(&chain_id).map(|n| n.low_u64())
+    }
+
+    /// Next nonce for an address, including pending transactions
+    pub async fn get_transaction_count(&self, address: &str) -> Result<U256> {
+        let count: String = self
+            .request("eth_getTransactionCount", json!([address, "pending"]))
+            .await?;
+        parse_quantity(&count)
+    }
+
+    pub async fn gas_price(&self) -> Result<U256> {
+        let price: String = self.request("eth_gasPrice", json!([])).await?;
+        parse_quantity(&price)
+    }
+
+    pub async fn estimate_gas(&self, tx: Value) -> Result<U256> {
+        let gas: String = self.request("eth_estimateGas", json!([tx])).await?;
+        parse_quantity(&gas)
+    }
+
+    /// Submits a signed transaction and returns its hash
+    pub async fn send_raw_transaction(&self, tx: &SignedTransaction) -> Result<String> {
+        self.request("eth_sendRawTransaction", json!([tx.raw_hex()])).await
+    }
+
+    /// Gets a receipt, or `None` while the transaction is pending
+    pub async fn get_transaction_receipt(&self, hash: &str) -> Result<Option<TransactionReceipt>> {
+        self.request("eth_getTransactionReceipt", json!([hash])).await
+    }
+
+    /// Polls until the transaction is mined
+    pub async fn wait_for_receipt(
+        &self,
+        hash: &str,
+        poll_interval: Duration,
+        max_polls: u32,
+    ) -> Result<TransactionReceipt> {
+        for _ in 0..max_polls {
+            if let Some(receipt) = self.get_transaction_receipt(hash).await? {
+                return Ok(receipt);
+            }
+            tokio::time::sleep(poll_interval).await;
+        }
+        Err(MediTokenError::NetworkError {
+            message: format!("Timed out waiting for receipt of {} after {} polls", hash, max_polls),
+        })
+    }
+
+    /// Executes a read-only call against the latest block and returns the raw output
+    pub async fn call(&self, tx: Value) -> Result<Vec<u8>> {
+        let output: String = self.request("eth_call", json!([tx, "latest"])).await?;
+        hex::decode(output.trim_start_matches("0x"))
+            .map_err(|_| MediTokenError::ContractError { message: format!("Invalid call output: {}", output) })
+    }
+}
+
+/// Parses a 0x-prefixed hex quantity
+pub fn parse_quantity(value: &str) -> Result<U256> {
+    let digits = value.trim_start_matches("0x");
+    if digits.is_empty() {
+        return Ok(U256::zero());
+    }
+    U256::from_str_radix(digits, 16).map_err(|_| MediTokenError::InvalidAmount { amount: value.to_string() })
+}
+
+/// Formats a value as a 0x-prefixed hex quantity
+pub fn to_quantity(value: U256) -> String {
+    format!("{:#x}", value)
+}
@@ -0,0 +1,96 @@
+//! This module is not available for WASM targets
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use primitive_types::U256;
+use crate::error::{MediTokenError, Result};
+
+/// Values of one balance at the snapshots taken before each of its changes.
+/// `values[i]` is the value at every snapshot up to and including `ids[i]`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Checkpoints {
+    ids: Vec<u64>,
+    values: Vec<U256>,
+}
+
+impl Checkpoints {
+    /// Value at `snapshot_id`, or `None` if it has not changed since
+    fn value_at(&self, snapshot_id: u64) -> Option<U256> {
+        let index = self.ids.partition_point(|&id| id < snapshot_id);
+        self.values.get(index).copied()
+    }
+
+    /// Records `current` as the value at `snapshot_id` unless it was already recorded
+    fn update(&mut self, snapshot_id: u64, current: U256) {
+        if snapshot_id > 0 && self.ids.last().is_none_or(|&last| last < snapshot_id) {
+            self.ids.push(snapshot_id);
+            self.values.push(current);
+        }
+    }
+}
+
+/// ERC20Snapshot-style history of account balances and total supply.
+/// Balances are recorded lazily, the first time they change after a snapshot.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Snapshots {
+    current_id: u64,
+    accounts: HashMap<String, Checkpoints>,
+    total_supply: Checkpoints,
+}
+
+impl Snapshots {
+    /// Creates a history with no snapshots taken
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Id of the latest snapshot, 0 if none has been taken
+    pub fn current_id(&self) -> u64 {
+        self.current_id
+    }
+
+    /// Starts a new snapshot and returns its id
+    pub fn take(&mut self) -> u64 {
+        self.current_id += 1;
+        self.current_id
+    }
+
+    /// Records an account balance that is about to change
+    pub fn update_account(&mut self, account: &str, balance: U256) {
+        if self.current_id > 0 {
+            self.accounts
+                .entry(account.to_string())
+                .or_default()
+                .update(self.current_id, balance);
+        }
+    }
+
+    /// Records a total supply that is about to change
+    pub fn update_total_supply(&mut self, total_supply: U256) {
+        self.total_supply.update(self.current_id, total_supply);
+    }
+
+    /// Balance of `account` at `snapshot_id`, given its current balance
+    pub fn balance_at(&self, account: &str, snapshot_id: u64, current: U256) -> Result<U256> {
+        self.check_id(snapshot_id)?;
+        Ok(self
+            .accounts
+            .get(account)
+            .and_then(|checkpoints| checkpoints.value_at(snapshot_id))
+            .unwrap_or(current))
+    }
+
+    /// Total supply at `snapshot_id`, given the current total supply
+    pub fn total_supply_at(&self, snapshot_id: u64, current: U256) -> Result<U256> {
+        self.check_id(snapshot_id)?;
+        Ok(self.total_supply.value_at(snapshot_id).unwrap_or(current))
+    }
+
+    fn check_id(&self, snapshot_id: u64) -> Result<()> {
+        if snapshot_id == 0 || snapshot_id > self.current_id {
+            return Err(MediTokenError::SnapshotNotFound { id: snapshot_id });
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,262 @@
+use medi_token::abi_codec::{decode, decode_meditoken_event, encode, Abi, AbiType, AbiValue};
+use medi_token::rpc::Log;
+use medi_token::token::MediTokenEvent;
+use medi_token::{MediTokenError, U256};
+use serde_json::json;
+
+#[cfg(test)]
+mod abi_codec_tests {
+    use super::*;
+
+    const ALICE: &str = "0x1111111111111111111111111111111111111111";
+    const BOB: &str = "0x2222222222222222222222222222222222222222";
+
+    fn word(value: U256) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        value.to_big_endian(&mut word);
+        word
+    }
+
+    fn topic_for(address: &str) -> String {
+        format!("0x{:0>64}", &address[2..])
+    }
+
+    #[test]
+    fn test_selectors_from_abi() {
+        let abi = Abi::meditoken();
+        let selector = |name: &str| hex::encode(abi.function(name).unwrap().selector());
+
+        assert_eq!(abi.function("transferFrom").unwrap().signature(), "transferFrom(address,address,uint256)");
+        assert_eq!(selector("balanceOf"), "70a08231");
+        assert_eq!(selector("allowance"), "dd62ed3e");
+        assert_eq!(selector("transfer"), "a9059cbb");
+        assert_eq!(selector("approve"), "095ea7b3");
+        assert_eq!(selector("transferFrom"), "23b872dd");
+        assert_eq!(selector("totalSupply"), "18160ddd");
+        assert_eq!(selector("name"), "06fdde03");
+        assert_eq!(selector("symbol"), "95d89b41");
+        assert_eq!(selector("decimals"), "313ce567");
+        assert_eq!(hex::encode(abi.error("ERC20InsufficientBalance").unwrap().selector()), "e450d38c");
+        assert_eq!(hex::encode(abi.error("ERC20InsufficientAllowance").unwrap().selector()), "fb8f41b2");
+        assert!(matches!(abi.function("mint").unwrap_err(), MediTokenError::AbiError { .. }));
+    }
+
+    #[test]
+    fn test_event_topics() {
+        let abi = Abi::meditoken();
+        assert_eq!(
+            hex::encode(abi.event("Transfer").unwrap().topic()),
+            "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+        );
+        assert_eq!(
+            hex::encode(abi.event("Approval").unwrap().topic()),
+            "8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925"
+        );
+    }
+
+    #[test]
+    fn test_parse_types() {
+        assert_eq!(AbiType::parse("uint256").unwrap(), AbiType::Uint(256));
+        assert_eq!(AbiType::parse("uint").unwrap(), AbiType::Uint(256));
+        assert_eq!(AbiType::parse("uint8").unwrap(), AbiType::Uint(8));
+        assert_eq!(
+            AbiType::parse("address[][]").unwrap(),
+            AbiType::Array(Box::new(AbiType::Array(Box::new(AbiType::Address))))
+        );
+        assert_eq!(AbiType::parse("string[]").unwrap().canonical(), "string[]");
+        assert!(AbiType::parse("bytes32").is_err());
+        assert!(AbiType::parse("uint7").is_err());
+    }
+
+    #[test]
+    fn test_encode_call() {
+        let abi = Abi::meditoken();
+        let data = abi
+            .function("transfer")
+            .unwrap()
+            .encode_call(&[AbiValue::Address(BOB.to_string()), AbiValue::Uint(U256::from(1000))])
+            .unwrap();
+
+        assert_eq!(
+            hex::encode(&data),
+            "a9059cbb\
+             0000000000000000000000002222222222222222222222222222222222222222\
+             00000000000000000000000000000000000000000000000000000000000003e8"
+        );
+        assert_eq!(
+            abi.function("transfer").unwrap().decode_input(&data).unwrap(),
+            vec![AbiValue::Address(BOB.to_string()), AbiValue::Uint(U256::from(1000))]
+        );
+    }
+
+    #[test]
+    fn test_encode_call_checks_arguments() {
+        let transfer = Abi::meditoken().function("transfer").unwrap().clone();
+
+        let wrong_count = transfer.encode_call(&[AbiValue::Address(BOB.to_string())]);
+        assert!(matches!(wrong_count.unwrap_err(), MediTokenError::AbiError { .. }));
+
+        let wrong_type = transfer.encode_call(&[AbiValue::Bool(true), AbiValue::Uint(U256::one())]);
+        assert!(matches!(wrong_type.unwrap_err(), MediTokenError::AbiError { .. }));
+
+        let bad_address = transfer.encode_call(&[AbiValue::Address("0x123".to_string()), AbiValue::Uint(U256::one())]);
+        assert!(matches!(bad_address.unwrap_err(), MediTokenError::InvalidAddress { .. }));
+    }
+
+    #[test]
+    fn test_string_encoding() {
+        let encoded = encode(&[AbiValue::String("MediToken".to_string())]).unwrap();
+        assert_eq!(
+            hex::encode(&encoded),
+            "0000000000000000000000000000000000000000000000000000000000000020\
+             0000000000000000000000000000000000000000000000000000000000000009\
+             4d656469546f6b656e0000000000000000000000000000000000000000000000"
+        );
+        assert_eq!(
+            decode(&[AbiType::String], &encoded).unwrap(),
+            vec![AbiValue::String("MediToken".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_dynamic_array_round_trip() {
+        let values = vec![
+            AbiValue::Uint(U256::from(7)),
+            AbiValue::Array(vec![AbiValue::Address(ALICE.to_string()), AbiValue::Address(BOB.to_string())]),
+            AbiValue::Array(vec![AbiValue::String("a".to_string()), AbiValue::String("bc".to_string())]),
+            AbiValue::Bool(true),
+        ];
+        let types = vec![
+            AbiType::Uint(256),
+            AbiType::parse("address[]").unwrap(),
+            AbiType::parse("string[]").unwrap(),
+            AbiType::Bool,
+        ];
+
+        let encoded = encode(&values).unwrap();
+        // Offsets of the two arrays follow the four head words
+        assert_eq!(U256::from_big_endian(&encoded[32..64]), U256::from(128));
+        assert_eq!(U256::from_big_endian(&encoded[64..96]), U256::from(224));
+        assert_eq!(decode(&types, &encoded).unwrap(), values);
+    }
+
+    #[test]
+    fn test_encode_rejects_malformed_address() {
+        for address in ["0x123", "1111111111111111111111111111111111111111", "0xzz11111111111111111111111111111111111111"] {
+            let result = encode(&[AbiValue::Address(address.to_string())]);
+            assert!(matches!(result.unwrap_err(), MediTokenError::InvalidAddress { .. }));
+        }
+        let nested = encode(&[AbiValue::Array(vec![AbiValue::Address("0x".to_string())])]);
+        assert!(matches!(nested.unwrap_err(), MediTokenError::InvalidAddress { .. }));
+    }
+
+    #[test]
+    fn test_decode_rejects_malformed_data() {
+        assert!(decode(&[AbiType::Uint(256)], &[0u8; 31]).is_err());
+        assert!(decode(&[AbiType::Bool], &[0xff; 32]).is_err());
+        assert!(decode(&[AbiType::Uint(8)], &word(U256::from(256))).is_err());
+        assert!(decode(&[AbiType::String], &word(U256::from(4096))).is_err());
+    }
+
+    #[test]
+    fn test_decode_transfer_log() {
+        let abi = Abi::meditoken();
+        let log = Log {
+            address: ALICE.to_string(),
+            topics: vec![
+                format!("0x{}", hex::encode(abi.event("Transfer").unwrap().topic())),
+                topic_for(ALICE),
+                topic_for(BOB),
+            ],
+            data: format!("0x{}", hex::encode(encode(&[AbiValue::Uint(U256::from(500))]).unwrap())),
+        };
+
+        assert_eq!(
+            decode_meditoken_event(&abi, &log).unwrap(),
+            MediTokenEvent::Transfer {
+                from: ALICE.to_string(),
+                to: BOB.to_string(),
+                value: U256::from(500),
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_approval_log() {
+        let abi = Abi::meditoken();
+        let log = Log {
+            address: ALICE.to_string(),
+            topics: vec![
+                format!("0x{}", hex::encode(abi.event("Approval").unwrap().topic())),
+                topic_for(ALICE),
+                topic_for(BOB),
+            ],
+            data: format!("0x{}", hex::encode(encode(&[AbiValue::Uint(U256::MAX)]).unwrap())),
+        };
+
+        assert_eq!(
+            decode_meditoken_event(&abi, &log).unwrap(),
+            MediTokenEvent::Approval {
+                owner: ALICE.to_string(),
+                spender: BOB.to_string(),
+                value: U256::MAX,
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_unknown_log() {
+        let abi = Abi::meditoken();
+        let log = Log {
+            address: ALICE.to_string(),
+            topics: vec![format!("0x{}", "00".repeat(32))],
+            data: "0x".to_string(),
+        };
+        assert!(matches!(decode_meditoken_event(&abi, &log).unwrap_err(), MediTokenError::AbiError { .. }));
+    }
+
+    #[test]
+    fn test_decode_event_with_missing_inputs() {
+        let abi = Abi::from_json(&json!([{
+            "type": "event",
+            "name": "Transfer",
+            "inputs": [
+                { "name": "from", "type": "address", "indexed": true },
+                { "name": "to", "type": "address", "indexed": true }
+            ],
+            "anonymous": false
+        }]))
+        .unwrap();
+        let log = Log {
+            address: ALICE.to_string(),
+            topics: vec![
+                format!("0x{}", hex::encode(abi.event("Transfer").unwrap().topic())),
+                topic_for(ALICE),
+                topic_for(BOB),
+            ],
+            data: "0x".to_string(),
+        };
+        assert!(matches!(decode_meditoken_event(&abi, &log).unwrap_err(), MediTokenError::AbiError { .. }));
+    }
+
+    #[test]
+    fn test_custom_abi() {
+        let abi = Abi::from_json(&json!([{
+            "type": "function",
+            "name": "holders",
+            "inputs": [{ "name": "limit", "type": "uint32" }],
+            "outputs": [{ "name": "", "type": "address[]" }],
+            "stateMutability": "view"
+        }]))
+        .unwrap();
+
+        let holders = abi.function("holders").unwrap();
+        assert_eq!(holders.signature(), "holders(uint32)");
+        let output = encode(&[AbiValue::Array(vec![AbiValue::Address(ALICE.to_string())])]).unwrap();
+        assert_eq!(
+            holders.decode_output(&output).unwrap(),
+            vec![AbiValue::Array(vec![AbiValue::Address(ALICE.to_string())])]
+        );
+        assert!(holders.encode_call(&[AbiValue::Uint(U256::from(u64::MAX))]).is_err());
+    }
+}
@@ -0,0 +1,124 @@
+use medi_token::{MediToken, utils::Utils, MediTokenError, token::MediTokenEvent, U256};
+use medi_token::access_control::{DEFAULT_ADMIN_ROLE, MINTER_ROLE, PAUSER_ROLE, PROVIDER_ROLE, PATIENT_ROLE};
+
+#[cfg(test)]
+mod access_control_tests {
+    use super::*;
+
+    #[test]
+    fn test_owner_initial_roles() {
+        let owner = Utils::generate_random_address();
+        let token = MediToken::new(U256::from(1000), owner.clone());
+
+        assert!(token.has_role(DEFAULT_ADMIN_ROLE, &owner));
+        assert!(token.has_role(MINTER_ROLE, &owner));
+        assert!(token.has_role(PAUSER_ROLE, &owner));
+        assert!(!token.has_role(PROVIDER_ROLE, &owner));
+        assert_eq!(token.get_role_admin(PROVIDER_ROLE), DEFAULT_ADMIN_ROLE);
+    }
+
+    #[test]
+    fn test_grant_and_revoke_role() {
+        let owner = Utils::generate_random_address();
+        let doctor = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner.clone());
+
+        token.grant_role(&owner, PROVIDER_ROLE, &doctor).unwrap();
+        assert!(token.has_role(PROVIDER_ROLE, &doctor));
+        match token.get_events().last().unwrap() {
+            MediTokenEvent::RoleGranted { role, account, sender } => {
+                assert_eq!(role, PROVIDER_ROLE);
+                assert_eq!(account, &doctor);
+                assert_eq!(sender, &owner);
+            }
+            _ => panic!("Expected RoleGranted event"),
+        }
+
+        // Granting an existing role emits nothing
+        let events_before = token.get_events().len();
+        token.grant_role(&owner, PROVIDER_ROLE, &doctor).unwrap();
+        assert_eq!(token.get_events().len(), events_before);
+
+        token.revoke_role(&owner, PROVIDER_ROLE, &doctor).unwrap();
+        assert!(!token.has_role(PROVIDER_ROLE, &doctor));
+        assert!(matches!(
+            token.get_events().last().unwrap(),
+            MediTokenEvent::RoleRevoked { .. }
+        ));
+    }
+
+    #[test]
+    fn test_grant_role_requires_admin() {
+        let owner = Utils::generate_random_address();
+        let patient = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner);
+
+        let result = token.grant_role(&patient, PROVIDER_ROLE, &patient);
+        match result.unwrap_err() {
+            MediTokenError::MissingRole { account, role } => {
+                assert_eq!(account, patient);
+                assert_eq!(role, DEFAULT_ADMIN_ROLE);
+            }
+            _ => panic!("Expected MissingRole error"),
+        }
+        assert!(!token.has_role(PROVIDER_ROLE, &patient));
+    }
+
+    #[test]
+    fn test_renounce_role() {
+        let owner = Utils::generate_random_address();
+        let doctor = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner.clone());
+        token.grant_role(&owner, PROVIDER_ROLE, &doctor).unwrap();
+
+        // Cannot renounce on behalf of someone else
+        let result = token.renounce_role(&owner, PROVIDER_ROLE, &doctor);
+        assert!(matches!(result.unwrap_err(), MediTokenError::Unauthorized { .. }));
+
+        token.renounce_role(&doctor, PROVIDER_ROLE, &doctor).unwrap();
+        assert!(!token.has_role(PROVIDER_ROLE, &doctor));
+    }
+
+    #[test]
+    fn test_role_admin_hierarchy() {
+        let owner = Utils::generate_random_address();
+        let doctor = Utils::generate_random_address();
+        let patient = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner.clone());
+
+        // Providers administer the patient role
+        token.set_role_admin(&owner, PATIENT_ROLE, PROVIDER_ROLE).unwrap();
+        match token.get_events().last().unwrap() {
+            MediTokenEvent::RoleAdminChanged { role, previous_admin_role, new_admin_role } => {
+                assert_eq!(role, PATIENT_ROLE);
+                assert_eq!(previous_admin_role, DEFAULT_ADMIN_ROLE);
+                assert_eq!(new_admin_role, PROVIDER_ROLE);
+            }
+            _ => panic!("Expected RoleAdminChanged event"),
+        }
+
+        // The default admin no longer administers patients directly
+        let result = token.grant_role(&owner, PATIENT_ROLE, &patient);
+        assert!(matches!(result.unwrap_err(), MediTokenError::MissingRole { .. }));
+
+        token.grant_role(&owner, PROVIDER_ROLE, &doctor).unwrap();
+        token.grant_role(&doctor, PATIENT_ROLE, &patient).unwrap();
+        assert!(token.has_role(PATIENT_ROLE, &patient));
+    }
+
+    #[test]
+    fn test_minter_role_controls_mint() {
+        let owner = Utils::generate_random_address();
+        let minter = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner.clone());
+
+        assert!(token.mint(&minter, &minter, U256::from(1)).is_err());
+
+        token.grant_role(&owner, MINTER_ROLE, &minter).unwrap();
+        token.mint(&minter, &minter, U256::from(1)).unwrap();
+        assert_eq!(token.balance_of(&minter), U256::from(1));
+
+        token.revoke_role(&owner, MINTER_ROLE, &owner).unwrap();
+        assert!(token.mint(&owner, &owner, U256::from(1)).is_err());
+    }
+}
@@ -0,0 +1,195 @@
+use medi_token::{MediTokenError, U256};
+use medi_token::access_control::PROVIDER_ROLE;
+use medi_token::claims::{ClaimEvent, ClaimStatus, Claims};
+
+mod common;
+use common::fixtures::{account, Fixture};
+
+#[cfg(test)]
+mod claims_tests {
+    use super::*;
+
+    struct Setup {
+        fx: Fixture,
+        claims: Claims,
+        provider: String,
+        patient: String,
+        insurer: String,
+    }
+
+    /// The admin is the insurer, holding 1000 to the patient's 100; both allow the claims
+    /// account to pay for them
+    fn setup() -> Setup {
+        let mut fx = Fixture::new(1100);
+        let insurer = fx.admin.clone();
+        let patient = fx.funded_account(100);
+        let provider = account();
+        fx.token.grant_role(&insurer, PROVIDER_ROLE, &provider).unwrap();
+
+        let claims = Claims::new(account());
+        fx.token.approve(&insurer, &claims.address, U256::from(1000)).unwrap();
+        fx.token.approve(&patient, &claims.address, U256::from(100)).unwrap();
+        Setup { fx, claims, provider, patient, insurer }
+    }
+
+    fn adjudicated(s: &mut Setup, billed: u64) -> u64 {
+        let id = s.claims.submit(&s.fx.token, &s.provider, &s.patient, &s.insurer, U256::from(billed), "Consultation").unwrap();
+        s.claims.adjudicate(&s.insurer, id).unwrap();
+        id
+    }
+
+    #[test]
+    fn test_full_approval_splits_coverage_and_copay() {
+        let mut s = setup();
+        let id = adjudicated(&mut s, 250);
+
+        let status = s.claims.approve(&s.insurer, id, U256::from(200), U256::from(50)).unwrap();
+        assert_eq!(status, ClaimStatus::Approved);
+        s.claims.pay(&mut s.fx.token, id).unwrap();
+
+        assert_eq!(s.claims.claim(id).unwrap().status, ClaimStatus::Paid);
+        assert_eq!(s.fx.token.balance_of(&s.provider), U256::from(250));
+        assert_eq!(s.fx.token.balance_of(&s.insurer), U256::from(800));
+        assert_eq!(s.fx.token.balance_of(&s.patient), U256::from(50));
+        assert_eq!(s.fx.token.allowance(&s.insurer, &s.claims.address), U256::from(800));
+        assert_eq!(
+            s.claims.get_events().last().unwrap(),
+            &ClaimEvent::ClaimPaid { id, coverage: U256::from(200), copay: U256::from(50) }
+        );
+    }
+
+    #[test]
+    fn test_partial_approval() {
+        let mut s = setup();
+        let id = adjudicated(&mut s, 300);
+
+        let status = s.claims.approve(&s.insurer, id, U256::from(160), U256::from(40)).unwrap();
+        assert_eq!(status, ClaimStatus::PartiallyApproved);
+        s.claims.pay(&mut s.fx.token, id).unwrap();
+        assert_eq!(s.fx.token.balance_of(&s.provider), U256::from(200));
+    }
+
+    #[test]
+    fn test_denial() {
+        let mut s = setup();
+        let id = adjudicated(&mut s, 300);
+
+        s.claims.deny(&s.insurer, id, "Not covered").unwrap();
+        let claim = s.claims.claim(id).unwrap();
+        assert_eq!(claim.status, ClaimStatus::Denied);
+        assert_eq!(claim.reason.as_deref(), Some("Not covered"));
+
+        let result = s.claims.pay(&mut s.fx.token, id);
+        assert!(matches!(
+            result.unwrap_err(),
+            MediTokenError::InvalidClaimTransition { from, to, .. } if from == "Denied" && to == "Paid"
+        ));
+        assert_eq!(s.fx.token.balance_of(&s.provider), U256::zero());
+    }
+
+    #[test]
+    fn test_reversal_refunds_both_payers() {
+        let mut s = setup();
+        let id = adjudicated(&mut s, 250);
+        s.claims.approve(&s.insurer, id, U256::from(200), U256::from(50)).unwrap();
+        s.claims.pay(&mut s.fx.token, id).unwrap();
+
+        // Reversals need the provider's allowance
+        let result = s.claims.reverse(&mut s.fx.token, &s.insurer, id, "Duplicate billing");
+        assert!(matches!(result.unwrap_err(), MediTokenError::InsufficientAllowance { .. }));
+        assert_eq!(s.claims.claim(id).unwrap().status, ClaimStatus::Paid);
+
+        s.fx.token.approve(&s.provider, &s.claims.address, U256::from(250)).unwrap();
+        s.claims.reverse(&mut s.fx.token, &s.insurer, id, "Duplicate billing").unwrap();
+        assert_eq!(s.claims.claim(id).unwrap().status, ClaimStatus::Reversed);
+        assert_eq!(s.fx.token.balance_of(&s.provider), U256::zero());
+        assert_eq!(s.fx.token.balance_of(&s.insurer), U256::from(1000));
+        assert_eq!(s.fx.token.balance_of(&s.patient), U256::from(100));
+
+        let result = s.claims.reverse(&mut s.fx.token, &s.insurer, id, "Again");
+        assert!(matches!(result.unwrap_err(), MediTokenError::InvalidClaimTransition { .. }));
+    }
+
+    #[test]
+    fn test_payment_is_atomic() {
+        let mut s = setup();
+        let id = adjudicated(&mut s, 250);
+        s.claims.approve(&s.insurer, id, U256::from(100), U256::from(150)).unwrap();
+
+        // The patient only allowed 100, so the copay fails after the coverage would have moved
+        let result = s.claims.pay(&mut s.fx.token, id);
+        assert!(matches!(result.unwrap_err(), MediTokenError::InsufficientAllowance { .. }));
+        assert_eq!(s.fx.token.balance_of(&s.provider), U256::zero());
+        assert_eq!(s.fx.token.balance_of(&s.insurer), U256::from(1000));
+        assert_eq!(s.claims.claim(id).unwrap().status, ClaimStatus::Approved);
+    }
+
+    #[test]
+    fn test_invalid_transitions() {
+        let mut s = setup();
+        let id = s.claims.submit(&s.fx.token, &s.provider, &s.patient, &s.insurer, U256::from(100), "X-ray").unwrap();
+
+        let result = s.claims.approve(&s.insurer, id, U256::from(100), U256::zero());
+        assert!(matches!(
+            result.unwrap_err(),
+            MediTokenError::InvalidClaimTransition { from, to, .. } if from == "Submitted" && to == "Approved"
+        ));
+        assert!(s.claims.deny(&s.insurer, id, "No").is_err());
+        assert!(s.claims.pay(&mut s.fx.token, id).is_err());
+
+        s.claims.adjudicate(&s.insurer, id).unwrap();
+        let result = s.claims.adjudicate(&s.insurer, id);
+        assert!(matches!(result.unwrap_err(), MediTokenError::InvalidClaimTransition { .. }));
+        s.claims.approve(&s.insurer, id, U256::from(100), U256::zero()).unwrap();
+        assert!(s.claims.deny(&s.insurer, id, "Changed mind").is_err());
+    }
+
+    #[test]
+    fn test_only_providers_submit() {
+        let mut s = setup();
+        let result = s.claims.submit(&s.fx.token, &s.patient, &s.patient, &s.insurer, U256::from(100), "X-ray");
+        assert!(matches!(result.unwrap_err(), MediTokenError::MissingRole { .. }));
+        assert!(s.claims.claims_for_patient(&s.patient).is_empty());
+
+        let id = s.claims.submit(&s.fx.token, &s.provider, &s.patient, &s.insurer, U256::from(100), "X-ray").unwrap();
+        assert_eq!(s.claims.claim(id).unwrap().provider, s.provider);
+        assert_eq!(
+            s.claims.get_events().last().unwrap(),
+            &ClaimEvent::ClaimSubmitted {
+                id,
+                provider: s.provider.clone(),
+                patient: s.patient.clone(),
+                insurer: s.insurer.clone(),
+                billed: U256::from(100),
+            }
+        );
+    }
+
+    #[test]
+    fn test_only_insurer_adjudicates() {
+        let mut s = setup();
+        let id = s.claims.submit(&s.fx.token, &s.provider, &s.patient, &s.insurer, U256::from(100), "X-ray").unwrap();
+
+        let result = s.claims.adjudicate(&s.provider, id);
+        assert!(matches!(result.unwrap_err(), MediTokenError::Unauthorized { .. }));
+        s.claims.adjudicate(&s.insurer, id).unwrap();
+        let result = s.claims.approve(&s.patient, id, U256::from(100), U256::zero());
+        assert!(matches!(result.unwrap_err(), MediTokenError::Unauthorized { .. }));
+        let result = s.claims.adjudicate(&s.insurer, 42);
+        assert!(matches!(result.unwrap_err(), MediTokenError::ClaimNotFound { id: 42 }));
+    }
+
+    #[test]
+    fn test_invalid_amounts() {
+        let mut s = setup();
+        let result = s.claims.submit(&s.fx.token, &s.provider, &s.patient, &s.insurer, U256::zero(), "Nothing");
+        assert!(matches!(result.unwrap_err(), MediTokenError::InvalidAmount { .. }));
+
+        let id = adjudicated(&mut s, 100);
+        let result = s.claims.approve(&s.insurer, id, U256::from(90), U256::from(20));
+        assert!(matches!(result.unwrap_err(), MediTokenError::InvalidAmount { .. }));
+        assert!(s.claims.approve(&s.insurer, id, U256::zero(), U256::zero()).is_err());
+        assert_eq!(s.claims.claim(id).unwrap().status, ClaimStatus::Adjudicated);
+        assert_eq!(s.claims.claims_for_patient(&s.patient).len(), 1);
+    }
+}
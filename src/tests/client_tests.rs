@@ -0,0 +1,211 @@
+use medi_token::rpc::{Log, TransactionReceipt};
+use medi_token::token::MediTokenEvent;
+use medi_token::{MediTokenClient, MediTokenError, utils::Utils, U256};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+mod common;
+use common::mock_rpc::{MockRpcServer, RpcResult};
+
+#[cfg(test)]
+mod client_tests {
+    use super::*;
+
+    const TOKEN: &str = "0x5fbdb2315678afecb367f032d93f642f64180aa3";
+    const HOLDER: &str = "0x70997970c51812dc3a010c7d01b50e0d17dc79c8";
+    const SIGNER_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    fn client(server: &MockRpcServer) -> MediTokenClient {
+        MediTokenClient::new(server.url(), TOKEN)
+            .unwrap()
+            .with_receipt_polling(Duration::from_millis(10), 5)
+    }
+
+    fn word(value: U256) -> String {
+        format!("{:064x}", value)
+    }
+
+    fn calldata_selector(params: &Value) -> String {
+        params[0]["data"].as_str().unwrap()[2..10].to_string()
+    }
+
+    fn node(method: &str, params: &Value) -> RpcResult {
+        match method {
+            "eth_call" => match calldata_selector(params).as_str() {
+                // balanceOf(address)
+                "70a08231" => Ok(json!(format!("0x{}", word(U256::from(1500))))),
+                // totalSupply()
+                "18160ddd" => Ok(json!(format!("0x{}", word(U256::from(1000) * U256::exp10(18))))),
+                // decimals()
+                "313ce567" => Ok(json!(format!("0x{}", word(U256::from(18))))),
+                // name()
+                "06fdde03" => Ok(json!(format!(
+                    "0x{}{}{:0<64}",
+                    word(U256::from(32)),
+                    word(U256::from(9)),
+                    hex::encode("MediToken")
+                ))),
+                // allowance(address,address)
+                "dd62ed3e" => Ok(json!(format!("0x{}", word(U256::from(42))))),
+                other => panic!("Unexpected selector {}", other),
+            },
+            "eth_chainId" => Ok(json!("0x7a69")),
+            "eth_getTransactionCount" => Ok(json!("0x3")),
+            "eth_gasPrice" => Ok(json!("0x3b9aca00")),
+            "eth_estimateGas" => {
+                // transfer(address,uint256) of more than 1000 units reverts
+                let data = params[0]["data"].as_str().unwrap();
+                let amount = U256::from_str_radix(&data[data.len() - 64..], 16).unwrap();
+                if amount > U256::from(1000) {
+                    return Err(json!({
+                        "code": 3,
+                        "message": "execution reverted",
+                        "data": format!(
+                            "0xe450d38c{:0>64}{}{}",
+                            HOLDER.trim_start_matches("0x"),
+                            word(U256::from(1000)),
+                            word(amount)
+                        ),
+                    }));
+                }
+                Ok(json!("0xea60"))
+            }
+            "eth_sendRawTransaction" => Ok(json!(format!("0x{}", "ab".repeat(32)))),
+            "eth_getTransactionReceipt" => Ok(json!({
+                "transactionHash": params[0],
+                "blockNumber": "0x10",
+                "gasUsed": "0xb411",
+                "contractAddress": null,
+                "status": "0x1",
+                "logs": [],
+            })),
+            other => panic!("Unexpected method {}", other),
+        }
+    }
+
+    #[test]
+    fn test_rejects_invalid_contract_address() {
+        let result = MediTokenClient::new("http://127.0.0.1:1", "0x123");
+        assert!(matches!(result.err().unwrap(), MediTokenError::InvalidAddress { .. }));
+    }
+
+    #[test]
+    fn test_events_from_receipt() {
+        let transfer_topic = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+        let log = |address: &str| Log {
+            address: address.to_string(),
+            topics: vec![
+                transfer_topic.to_string(),
+                format!("0x{:0>64}", &HOLDER[2..]),
+                format!("0x{:0>64}", &TOKEN[2..]),
+            ],
+            data: format!("0x{}", word(U256::from(25))),
+        };
+        let receipt = TransactionReceipt {
+            transaction_hash: format!("0x{}", "ab".repeat(32)),
+            block_number: "0x1".to_string(),
+            gas_used: "0x5208".to_string(),
+            contract_address: None,
+            status: Some("0x1".to_string()),
+            logs: vec![log(&TOKEN.to_uppercase().replace("0X", "0x")), log(HOLDER)],
+        };
+
+        let client = MediTokenClient::new("http://127.0.0.1:1", TOKEN).unwrap();
+        assert_eq!(
+            client.events(&receipt).unwrap(),
+            vec![MediTokenEvent::Transfer {
+                from: HOLDER.to_string(),
+                to: TOKEN.to_string(),
+                value: U256::from(25),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_view_calls() {
+        let server = MockRpcServer::start(node).await;
+        let client = client(&server);
+
+        assert_eq!(client.balance_of(HOLDER).await.unwrap(), U256::from(1500));
+        assert_eq!(client.total_supply().await.unwrap(), U256::from(1000) * U256::exp10(18));
+        assert_eq!(client.decimals().await.unwrap(), 18);
+        assert_eq!(client.name().await.unwrap(), "MediToken");
+        assert_eq!(client.allowance(HOLDER, TOKEN).await.unwrap(), U256::from(42));
+
+        let calls = server.calls("eth_call");
+        assert_eq!(calls[0][0]["to"], TOKEN);
+        assert_eq!(
+            calls[0][0]["data"],
+            format!("0x70a08231{:0>64}", HOLDER.trim_start_matches("0x"))
+        );
+        assert_eq!(calls[0][1], "latest");
+    }
+
+    #[tokio::test]
+    async fn test_transfer_signs_and_waits_for_receipt() {
+        let server = MockRpcServer::start(node).await;
+        let client = client(&server).with_signer(SIGNER_KEY).unwrap();
+        assert_eq!(client.signer_address().unwrap(), "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266");
+
+        let receipt = client.transfer(HOLDER, U256::from(500)).await.unwrap();
+        assert!(receipt.succeeded());
+        assert_eq!(receipt.block_number().unwrap(), 16);
+
+        let raw = server.calls("eth_sendRawTransaction");
+        assert_eq!(raw.len(), 1);
+        let raw = raw[0][0].as_str().unwrap();
+        // The signed transaction carries transfer(HOLDER, 500) calldata
+        assert!(raw.contains(&format!("a9059cbb{:0>64}{}", HOLDER.trim_start_matches("0x"), word(U256::from(500)))));
+    }
+
+    #[tokio::test]
+    async fn test_custom_error_decoding() {
+        let server = MockRpcServer::start(node).await;
+        let client = client(&server).with_signer(SIGNER_KEY).unwrap();
+
+        match client.transfer(HOLDER, U256::from(5000)).await.unwrap_err() {
+            MediTokenError::InsufficientBalance { required, available } => {
+                assert_eq!(required, U256::from(5000));
+                assert_eq!(available, U256::from(1000));
+            }
+            other => panic!("Expected InsufficientBalance, got {:?}", other),
+        }
+        assert!(server.calls("eth_sendRawTransaction").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_out_of_range_decimals() {
+        let server = MockRpcServer::start(|method: &str, params: &Value| match method {
+            "eth_call" if calldata_selector(params) == "313ce567" => Ok(json!(format!("0x{}", word(U256::from(100))))),
+            _ => node(method, params),
+        })
+        .await;
+        let client = client(&server);
+
+        assert!(matches!(client.decimals().await.unwrap_err(), MediTokenError::ContractError { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_gas_buffer_overflow_is_an_error() {
+        let server = MockRpcServer::start(|method: &str, params: &Value| match method {
+            "eth_estimateGas" => Ok(json!(format!("0x{}", word(U256::MAX)))),
+            _ => node(method, params),
+        })
+        .await;
+        let client = client(&server).with_signer(SIGNER_KEY).unwrap();
+
+        let result = client.transfer(HOLDER, U256::from(500)).await;
+        assert!(matches!(result.unwrap_err(), MediTokenError::ContractError { .. }));
+        assert!(server.calls("eth_sendRawTransaction").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_write_requires_signer() {
+        let server = MockRpcServer::start(node).await;
+        let client = client(&server);
+
+        let result = client.approve(HOLDER, U256::from(1)).await;
+        assert!(matches!(result.unwrap_err(), MediTokenError::ContractError { .. }));
+        assert!(Utils::is_valid_address(client.address()));
+    }
+}
@@ -0,0 +1,40 @@
+//! Token and clock shared by the healthcare module suites
+
+use medi_token::clock::ManualClock;
+use medi_token::{utils::Utils, MediToken, U256};
+
+/// Time every fixture clock starts at
+pub const NOW: u64 = 1_700_000_000;
+pub const HOUR: u64 = 60 * 60;
+pub const DAY: u64 = 24 * HOUR;
+
+/// A token whose whole supply starts with `admin`, and a clock for the module under test
+pub struct Fixture {
+    pub token: MediToken,
+    pub clock: ManualClock,
+    pub admin: String,
+}
+
+impl Fixture {
+    /// Mints `supply` tokens to a new admin account and starts the clock at `NOW`
+    pub fn new(supply: u64) -> Self {
+        let admin = account();
+        Self {
+            token: MediToken::new(U256::from(supply), admin.clone()),
+            clock: ManualClock::new(NOW),
+            admin,
+        }
+    }
+
+    /// Creates an account holding `amount` tokens taken from the admin
+    pub fn funded_account(&mut self, amount: u64) -> String {
+        let account = account();
+        self.token.transfer(&self.admin, &account, U256::from(amount)).unwrap();
+        account
+    }
+}
+
+/// A new random account
+pub fn account() -> String {
+    Utils::generate_random_address()
+}
@@ -0,0 +1,98 @@
+//! Minimal HTTP JSON-RPC server standing in for an EVM node
+
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// JSON-RPC `result` on success, `error` object on failure
+pub type RpcResult = std::result::Result<Value, Value>;
+
+/// Serves every request through a handler and records what it was asked
+pub struct MockRpcServer {
+    addr: SocketAddr,
+    requests: Arc<Mutex<Vec<Value>>>,
+}
+
+impl MockRpcServer {
+    /// Starts answering requests with `handler(method, params)`
+    pub async fn start<F>(handler: F) -> Self
+    where
+        F: Fn(&str, &Value) -> RpcResult + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let handler = Arc::new(handler);
+
+        let server_requests = requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let requests = server_requests.clone();
+                let handler = handler.clone();
+                tokio::spawn(async move {
+                    let Some(request) = read_request(&mut stream).await else {
+                        return;
+                    };
+                    requests.lock().unwrap().push(request.clone());
+                    let body = match handler(request["method"].as_str().unwrap(), &request["params"]) {
+                        Ok(result) => json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }),
+                        Err(error) => json!({ "jsonrpc": "2.0", "id": request["id"], "error": error }),
+                    }
+                    .to_string();
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+                    stream.write_all(response.as_bytes()).await.unwrap();
+                });
+            }
+        });
+
+        Self { addr, requests }
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Params of every request made for `method`, oldest first
+    pub fn calls(&self, method: &str) -> Vec<Value> {
+        self.requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|request| request["method"] == method)
+            .map(|request| request["params"].clone())
+            .collect()
+    }
+}
+
+/// Reads one HTTP request body, or `None` if the peer closes the connection first
+async fn read_request(stream: &mut TcpStream) -> Option<Value> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        let n = stream.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        let text = String::from_utf8_lossy(&buf).to_string();
+        if let Some(header_end) = text.find("\r\n\r\n") {
+            let content_length = text[..header_end]
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length").then(|| value.trim().parse::<usize>().unwrap())
+                })
+                .unwrap_or(0);
+            if buf.len() >= header_end + 4 + content_length {
+                return serde_json::from_slice(&buf[header_end + 4..header_end + 4 + content_length]).ok();
+            }
+        }
+    }
+}
@@ -0,0 +1,5 @@
+//! Helpers shared by the suites under `src/tests`; each suite uses only some of them
+#![allow(dead_code)]
+
+pub mod fixtures;
+pub mod mock_rpc;
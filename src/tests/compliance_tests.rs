@@ -0,0 +1,125 @@
+use medi_token::{MediToken, utils::Utils, MediTokenError, token::MediTokenEvent, U256};
+use medi_token::access_control::{COMPLIANCE_ROLE, MINTER_ROLE};
+
+#[cfg(test)]
+mod compliance_tests {
+    use super::*;
+
+    #[test]
+    fn test_freeze_blocks_sender_and_receiver() {
+        let owner = Utils::generate_random_address();
+        let suspect = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner.clone());
+        token.transfer(&owner, &suspect, U256::from(100)).unwrap();
+
+        token.freeze(&owner, &suspect).unwrap();
+        assert!(token.is_frozen(&suspect));
+        assert_eq!(
+            token.get_events().last().unwrap(),
+            &MediTokenEvent::AccountFrozen { account: suspect.clone(), sender: owner.clone() }
+        );
+
+        let result = token.transfer(&suspect, &owner, U256::from(10));
+        assert!(matches!(result.unwrap_err(), MediTokenError::AccountFrozen { account } if account == suspect));
+        let result = token.transfer(&owner, &suspect, U256::from(10));
+        assert!(matches!(result.unwrap_err(), MediTokenError::AccountFrozen { .. }));
+        assert_eq!(token.balance_of(&suspect), U256::from(100));
+    }
+
+    #[test]
+    fn test_freeze_blocks_transfer_from_and_mint() {
+        let owner = Utils::generate_random_address();
+        let spender = Utils::generate_random_address();
+        let suspect = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner.clone());
+        token.transfer(&owner, &suspect, U256::from(100)).unwrap();
+        token.approve(&suspect, &spender, U256::from(50)).unwrap();
+        token.freeze(&owner, &suspect).unwrap();
+
+        let result = token.transfer_from(&spender, &suspect, &spender, U256::from(50));
+        assert!(matches!(result.unwrap_err(), MediTokenError::AccountFrozen { .. }));
+        assert_eq!(token.allowance(&suspect, &spender), U256::from(50));
+
+        let result = token.mint(&owner, &suspect, U256::from(10));
+        assert!(matches!(result.unwrap_err(), MediTokenError::AccountFrozen { .. }));
+
+        // Frozen balances can still be burned by a minter
+        assert!(token.has_role(MINTER_ROLE, &owner));
+        token.burn(&owner, &suspect, U256::from(100)).unwrap();
+        assert_eq!(token.balance_of(&suspect), U256::zero());
+    }
+
+    #[test]
+    fn test_unfreeze_restores_transfers() {
+        let owner = Utils::generate_random_address();
+        let suspect = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner.clone());
+
+        token.freeze(&owner, &suspect).unwrap();
+        token.unfreeze(&owner, &suspect).unwrap();
+        assert!(!token.is_frozen(&suspect));
+        assert!(matches!(
+            token.get_events().last().unwrap(),
+            MediTokenEvent::AccountUnfrozen { .. }
+        ));
+
+        token.transfer(&owner, &suspect, U256::from(10)).unwrap();
+        assert_eq!(token.balance_of(&suspect), U256::from(10));
+    }
+
+    #[test]
+    fn test_freeze_requires_compliance_role() {
+        let owner = Utils::generate_random_address();
+        let officer = Utils::generate_random_address();
+        let suspect = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner.clone());
+
+        let result = token.freeze(&officer, &suspect);
+        assert!(matches!(result.unwrap_err(), MediTokenError::MissingRole { .. }));
+        assert!(!token.is_frozen(&suspect));
+
+        token.grant_role(&owner, COMPLIANCE_ROLE, &officer).unwrap();
+        token.freeze(&officer, &suspect).unwrap();
+        assert!(token.is_frozen(&suspect));
+        let result = token.unfreeze(&suspect, &suspect);
+        assert!(matches!(result.unwrap_err(), MediTokenError::MissingRole { .. }));
+    }
+
+    #[test]
+    fn test_freeze_is_idempotent() {
+        let owner = Utils::generate_random_address();
+        let suspect = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner.clone());
+
+        token.freeze(&owner, &suspect).unwrap();
+        let events = token.get_events().len();
+        token.freeze(&owner, &suspect).unwrap();
+        assert_eq!(token.get_events().len(), events);
+
+        token.unfreeze(&owner, &suspect).unwrap();
+        token.unfreeze(&owner, &suspect).unwrap();
+        assert_eq!(token.get_events().len(), events + 1);
+    }
+
+    #[test]
+    fn test_frozen_accounts_are_listed_in_order() {
+        let owner = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner.clone());
+        let mut suspects: Vec<String> = (0..3).map(|_| Utils::generate_random_address()).collect();
+        for suspect in &suspects {
+            token.freeze(&owner, suspect).unwrap();
+        }
+        token.unfreeze(&owner, &suspects[1]).unwrap();
+
+        let removed = suspects.remove(1);
+        suspects.sort();
+        assert_eq!(token.frozen_accounts(), suspects);
+        assert!(!token.frozen_accounts().contains(&removed));
+    }
+
+    #[test]
+    fn test_error_display() {
+        let error = MediTokenError::AccountFrozen { account: "0x123".to_string() };
+        assert_eq!(error.to_string(), "Account 0x123 is frozen");
+    }
+}
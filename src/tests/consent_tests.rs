@@ -0,0 +1,186 @@
+use std::collections::BTreeSet;
+use medi_token::MediTokenError;
+use medi_token::access_control::PROVIDER_ROLE;
+use medi_token::clock::ManualClock;
+use medi_token::consent::{AccessLevel, ConsentEvent, ConsentRegistry, DataCategory};
+
+mod common;
+use common::fixtures::{account, Fixture, HOUR, NOW};
+
+#[cfg(test)]
+mod consent_tests {
+    use super::*;
+
+    struct Setup {
+        fx: Fixture,
+        registry: ConsentRegistry<ManualClock>,
+    }
+
+    /// `doctor`, `lab.testnet` and `radiologist` hold the provider role
+    fn setup() -> Setup {
+        let mut fx = Fixture::new(1000);
+        for provider in ["doctor", "lab.testnet", "radiologist"] {
+            fx.token.grant_role(&fx.admin, PROVIDER_ROLE, provider).unwrap();
+        }
+        let registry = ConsentRegistry::with_clock(fx.clock.clone());
+        Setup { fx, registry }
+    }
+
+    fn categories(list: &[DataCategory]) -> BTreeSet<DataCategory> {
+        list.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_scoped_consent() {
+        let mut s = setup();
+        let patient = account();
+        let doctor = account();
+        s.fx.token.grant_role(&s.fx.admin, PROVIDER_ROLE, &doctor).unwrap();
+        let scope = categories(&[DataCategory::Diagnoses, DataCategory::Medications]);
+        s.registry.grant(&s.fx.token, &patient, &patient, &doctor, scope, AccessLevel::Read, None).unwrap();
+
+        assert!(s.registry.has_access(&patient, &doctor, &DataCategory::Diagnoses, AccessLevel::Read));
+        assert!(!s.registry.has_access(&patient, &doctor, &DataCategory::Diagnoses, AccessLevel::Write));
+        assert!(!s.registry.has_access(&patient, &doctor, &DataCategory::MentalHealth, AccessLevel::Read));
+        assert!(!s.registry.has_access(&patient, &account(), &DataCategory::Diagnoses, AccessLevel::Read));
+
+        s.registry.access(&patient, &doctor, DataCategory::Medications, AccessLevel::Read).unwrap();
+        let result = s.registry.access(&patient, &doctor, DataCategory::Genomics, AccessLevel::Read);
+        assert!(matches!(result.unwrap_err(), MediTokenError::AccessDenied { .. }));
+    }
+
+    #[test]
+    fn test_write_consent_includes_read() {
+        let mut s = setup();
+        let scope = categories(&[DataCategory::LabResults]);
+        s.registry.grant(&s.fx.token, "alice.testnet", "alice.testnet", "lab.testnet", scope, AccessLevel::Write, None).unwrap();
+
+        assert!(s.registry.has_access("alice.testnet", "lab.testnet", &DataCategory::LabResults, AccessLevel::Read));
+        assert!(s.registry.has_access("alice.testnet", "lab.testnet", &DataCategory::LabResults, AccessLevel::Write));
+    }
+
+    #[test]
+    fn test_consent_expires() {
+        let mut s = setup();
+        let scope = categories(&[DataCategory::Imaging]);
+        s.registry.grant(&s.fx.token, "patient", "patient", "radiologist", scope, AccessLevel::Read, Some(NOW + HOUR)).unwrap();
+
+        assert!(s.registry.has_access("patient", "radiologist", &DataCategory::Imaging, AccessLevel::Read));
+        s.fx.clock.advance(HOUR);
+        assert!(!s.registry.has_access("patient", "radiologist", &DataCategory::Imaging, AccessLevel::Read));
+
+        let result = s.registry.grant(&s.fx.token, "patient", "patient", "radiologist", categories(&[DataCategory::Imaging]), AccessLevel::Read, Some(NOW));
+        assert!(matches!(result.unwrap_err(), MediTokenError::ConsentError { .. }));
+        let result = s.registry.grant(&s.fx.token, "patient", "patient", "radiologist", BTreeSet::new(), AccessLevel::Read, None);
+        assert!(matches!(result.unwrap_err(), MediTokenError::ConsentError { .. }));
+    }
+
+    #[test]
+    fn test_only_patient_grants_to_providers() {
+        let mut s = setup();
+        let scope = categories(&[DataCategory::Diagnoses]);
+
+        let result = s.registry.grant(&s.fx.token, "doctor", "patient", "doctor", scope.clone(), AccessLevel::Write, None);
+        assert!(matches!(result.unwrap_err(), MediTokenError::Unauthorized { .. }));
+        assert!(s.registry.grants_for_patient("patient").is_empty());
+        assert!(s.registry.history("patient").is_empty());
+
+        // Consent can only go to providers
+        let result = s.registry.grant(&s.fx.token, "patient", "patient", &account(), scope.clone(), AccessLevel::Read, None);
+        assert!(matches!(result.unwrap_err(), MediTokenError::MissingRole { .. }));
+        assert!(s.registry.grants_for_patient("patient").is_empty());
+
+        s.registry.grant(&s.fx.token, "patient", "patient", "doctor", scope, AccessLevel::Write, None).unwrap();
+        assert!(s.registry.has_access("patient", "doctor", &DataCategory::Diagnoses, AccessLevel::Write));
+    }
+
+    #[test]
+    fn test_revoke() {
+        let mut s = setup();
+        let id = s.registry
+            .grant(&s.fx.token, "patient", "patient", "doctor", categories(&[DataCategory::Diagnoses]), AccessLevel::Read, None)
+            .unwrap();
+
+        let result = s.registry.revoke("doctor", id);
+        assert!(matches!(result.unwrap_err(), MediTokenError::Unauthorized { .. }));
+        s.registry.revoke("patient", id).unwrap();
+        assert!(!s.registry.has_access("patient", "doctor", &DataCategory::Diagnoses, AccessLevel::Read));
+        assert_eq!(s.registry.grant_by_id(id).unwrap().revoked_at, Some(NOW));
+
+        assert!(s.registry.revoke("patient", id).is_err());
+        assert!(matches!(s.registry.revoke("patient", 7).unwrap_err(), MediTokenError::ConsentNotFound { id: 7 }));
+    }
+
+    #[test]
+    fn test_access_history() {
+        let mut s = setup();
+        let id = s.registry
+            .grant(&s.fx.token, "patient", "patient", "doctor", categories(&[DataCategory::Medications]), AccessLevel::Read, None)
+            .unwrap();
+        s.registry.access("patient", "doctor", DataCategory::Medications, AccessLevel::Read).unwrap();
+        s.registry.revoke("patient", id).unwrap();
+        let _ = s.registry.access("patient", "doctor", DataCategory::Medications, AccessLevel::Read);
+        s.registry.grant(&s.fx.token, "someone-else", "someone-else", "doctor", categories(&[DataCategory::Imaging]), AccessLevel::Read, None).unwrap();
+
+        let history = s.registry.history("patient");
+        assert_eq!(history.len(), 4);
+        assert!(matches!(history[0], ConsentEvent::ConsentGranted { .. }));
+        assert!(matches!(history[1], ConsentEvent::DataAccessed { emergency_id: None, .. }));
+        assert!(matches!(history[2], ConsentEvent::ConsentRevoked { .. }));
+        assert!(matches!(history[3], ConsentEvent::AccessDenied { .. }));
+        assert_eq!(s.registry.grants_for_patient("patient").len(), 1);
+    }
+
+    #[test]
+    fn test_break_glass_access() {
+        let mut s = setup();
+        let responder = account();
+        let patient = account();
+
+        let result = s.registry.break_glass(&s.fx.token, &responder, &patient, "Unconscious patient");
+        assert!(matches!(result.unwrap_err(), MediTokenError::MissingRole { .. }));
+
+        s.fx.token.grant_role(&s.fx.admin, PROVIDER_ROLE, &responder).unwrap();
+        let id = s.registry.break_glass(&s.fx.token, &responder, &patient, "Unconscious patient").unwrap();
+        assert!(s.registry.has_access(&patient, &responder, &DataCategory::MentalHealth, AccessLevel::Read));
+        assert!(!s.registry.has_access(&patient, &responder, &DataCategory::MentalHealth, AccessLevel::Write));
+
+        s.registry.access(&patient, &responder, DataCategory::Medications, AccessLevel::Read).unwrap();
+        assert!(matches!(
+            s.registry.history(&patient).last().unwrap(),
+            ConsentEvent::DataAccessed { emergency_id: Some(e), .. } if *e == id
+        ));
+
+        s.fx.clock.advance(s.registry.emergency_window);
+        assert!(!s.registry.has_access(&patient, &responder, &DataCategory::Medications, AccessLevel::Read));
+    }
+
+    #[test]
+    fn test_break_glass_must_be_justified() {
+        let mut s = setup();
+        let responder = account();
+        s.fx.token.grant_role(&s.fx.admin, PROVIDER_ROLE, &responder).unwrap();
+
+        let id = s.registry.break_glass(&s.fx.token, &responder, "patient", "Cardiac arrest").unwrap();
+        assert!(s.registry.overdue_justifications().is_empty());
+
+        s.fx.clock.advance(s.registry.emergency_window + s.registry.justification_period);
+        assert_eq!(s.registry.overdue_justifications().len(), 1);
+        let result = s.registry.break_glass(&s.fx.token, &responder, "another patient", "Trauma");
+        assert!(matches!(result.unwrap_err(), MediTokenError::ConsentError { .. }));
+
+        assert!(matches!(s.registry.justify(&s.fx.admin, id, "Reason").unwrap_err(), MediTokenError::Unauthorized { .. }));
+        assert!(s.registry.justify(&responder, id, "  ").is_err());
+        s.registry.justify(&responder, id, "Patient arrived unresponsive, allergies needed").unwrap();
+        assert!(s.registry.overdue_justifications().is_empty());
+        assert!(s.registry.justify(&responder, id, "Again").is_err());
+        assert!(matches!(
+            s.registry.history("patient").last().unwrap(),
+            ConsentEvent::EmergencyAccessJustified { .. }
+        ));
+
+        s.registry.break_glass(&s.fx.token, &responder, "another patient", "Trauma").unwrap();
+        let emergency = s.registry.emergencies_for_patient("patient")[0];
+        assert_eq!(emergency.justification.as_deref(), Some("Patient arrived unresponsive, allergies needed"));
+    }
+}
@@ -1,5 +1,12 @@
-use medi_token::deployment::{Network, Deployer, get_deployed_addresses};
+use medi_token::deployment::{Network, Deployer, DeploymentConfig, get_deployed_addresses};
+use medi_token::{utils::Utils, U256};
+use serde_json::{json, Value};
 use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+mod common;
+use common::mock_rpc::MockRpcServer;
 
 #[cfg(test)]
 mod deployment_tests {
@@ -27,6 +34,24 @@ mod deployment_tests {
         assert_eq!(Network::Local.rpc_url_env_var(), "LOCAL_RPC_URL");
     }
 
+    #[test]
+    fn test_network_aliases() {
+        assert_eq!("sepolia".parse::<Network>().unwrap(), Network::Sepolia);
+        assert_eq!("amoy".parse::<Network>().unwrap(), Network::Amoy);
+        assert_eq!("arbitrum".parse::<Network>().unwrap(), Network::ArbitrumSepolia);
+        assert_eq!("optimism".parse::<Network>().unwrap(), Network::OpSepolia);
+        assert_eq!("cardona".parse::<Network>().unwrap(), Network::Cardona);
+        assert_eq!("scroll".parse::<Network>().unwrap(), Network::ScrollSepolia);
+        assert_eq!("local".parse::<Network>().unwrap(), Network::Local);
+        assert_eq!("OP_SEPOLIA".parse::<Network>().unwrap(), Network::OpSepolia);
+        assert!("mainnet".parse::<Network>().is_err());
+
+        // Canonical names round-trip
+        for network in Network::all() {
+            assert_eq!(network.to_string().parse::<Network>().unwrap(), network);
+        }
+    }
+
     #[test]
     fn test_deployed_addresses() {
         let addresses = get_deployed_addresses();
@@ -78,25 +103,159 @@ mod deployment_tests {
         }
     }
 
+    #[test]
+    fn test_with_initial_supply() {
+        let deployer = Deployer::with_config(mock_config("http://127.0.0.1:1".to_string()))
+            .with_initial_supply(U256::from(5));
+        assert_eq!(deployer.config().initial_supply, U256::from(5));
+        assert!(hex::encode(deployer.init_code()).ends_with(&format!("{:064x}", 5)));
+    }
+
+    fn mock_config(rpc_url: String) -> DeploymentConfig {
+        DeploymentConfig {
+            network: Network::Local,
+            initial_supply: U256::from(1000) * U256::exp10(18),
+            // Well-known Hardhat/Anvil account #0 key
+            private_key: "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".to_string(),
+            rpc_url,
+            bytecode: vec![0x60, 0x80, 0x60, 0x40, 0x52],
+            gas_buffer_percent: 20,
+            receipt_poll_interval: Duration::from_millis(10),
+            receipt_max_polls: 10,
+        }
+    }
+
     #[tokio::test]
-    async fn test_deployment_simulation() {
-        // Set up mock environment variables for testing
-        env::set_var("PRIVATE_KEY", "0x1234567890123456789012345678901234567890123456789012345678901234");
-        env::set_var("SEPOLIA_RPC_URL", "https://eth-sepolia.g.alchemy.com/v2/test");
+    async fn test_deploy_against_mock_rpc() {
+        let server = MockNode::start(31337, "0x1").await;
+        let deployer = Deployer::with_config(mock_config(server.url()));
 
-        let deployer = Deployer::new(Network::Sepolia).unwrap();
         let deployed = deployer.deploy().await.unwrap();
 
-        // Check deployment result
-        assert!(deployed.address.starts_with("0x"));
-        assert_eq!(deployed.address.len(), 42);
-        assert!(deployed.transaction_hash.starts_with("0x"));
-        assert_eq!(deployed.transaction_hash.len(), 66);
-        assert!(deployed.gas_used > 0);
-        assert!(deployed.block_number > 0);
+        assert_eq!(deployed.address, "0x5fbdb2315678afecb367f032d93f642f64180aa3");
+        assert_eq!(deployed.network, Network::Local);
+        assert_eq!(deployed.block_number, 7);
+        assert_eq!(deployed.gas_used, 654321);
+        assert_eq!(deployed.deployer, "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266");
 
-        // Clean up
-        env::remove_var("PRIVATE_KEY");
-        env::remove_var("SEPOLIA_RPC_URL");
+        let state = server.state.lock().unwrap();
+        assert_eq!(state.raw_transactions.len(), 1);
+        let raw = &state.raw_transactions[0];
+        assert_eq!(deployed.transaction_hash, format!("0x{}", hex::encode(Utils::keccak256(raw))));
+
+        // Creation code followed by the uint256 constructor argument
+        let init_code = hex::encode(deployer.init_code());
+        assert!(hex::encode(raw).contains(&init_code));
+        assert!(init_code.ends_with("00000000000000000000000000000000000000000000003635c9adc5dea00000"));
+
+        // The deployer polled at least once while the transaction was pending
+        assert!(state.receipt_polls >= 2);
+        // Gas limit is the estimate plus the 20% buffer
+        assert_eq!(state.estimate_requests, 1);
+    }
+
+    #[tokio::test]
+    async fn test_prepare_does_not_send() {
+        let server = MockNode::start(31337, "0x1").await;
+        let deployer = Deployer::with_config(mock_config(server.url()));
+
+        let signed = deployer.prepare().await.unwrap();
+        assert_eq!(signed.raw[0], 0xf8);
+        assert_eq!(signed.hash, Utils::keccak256(&signed.raw));
+        assert!(server.state.lock().unwrap().raw_transactions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_deploy_fails_on_revert() {
+        let server = MockNode::start(31337, "0x0").await;
+        let deployer = Deployer::with_config(mock_config(server.url()));
+
+        let error = deployer.deploy().await.unwrap_err();
+        assert!(error.to_string().contains("reverted"));
+    }
+
+    #[tokio::test]
+    async fn test_deploy_rejects_wrong_chain() {
+        let server = MockNode::start(1, "0x1").await;
+        let deployer = Deployer::with_config(mock_config(server.url()));
+
+        let error = deployer.deploy().await.unwrap_err();
+        assert!(error.to_string().contains("chain id 1"));
+        assert!(server.state.lock().unwrap().raw_transactions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_deploy_rejects_overflowing_gas_estimate() {
+        let server = MockNode::start(31337, "0x1").await;
+        server.state.lock().unwrap().gas_estimate = Some("0x8000000000000000000000000000000000000000000000000000000000000000");
+        let deployer = Deployer::with_config(mock_config(server.url()));
+
+        let error = deployer.deploy().await.unwrap_err();
+        assert!(error.to_string().contains("overflows"));
+        assert!(server.state.lock().unwrap().raw_transactions.is_empty());
+    }
+
+    #[derive(Default)]
+    struct MockState {
+        raw_transactions: Vec<Vec<u8>>,
+        receipt_polls: u32,
+        estimate_requests: u32,
+        gas_estimate: Option<&'static str>,
+    }
+
+    /// Mock RPC answering like a local dev node
+    struct MockNode {
+        rpc: MockRpcServer,
+        state: Arc<Mutex<MockState>>,
+    }
+
+    impl MockNode {
+        async fn start(chain_id: u64, receipt_status: &'static str) -> Self {
+            let state = Arc::new(Mutex::new(MockState::default()));
+            let node_state = state.clone();
+            let rpc = MockRpcServer::start(move |method, params| {
+                Ok(handle(&node_state, chain_id, receipt_status, method, params))
+            })
+            .await;
+            Self { rpc, state }
+        }
+
+        fn url(&self) -> String {
+            self.rpc.url()
+        }
+    }
+
+    fn handle(state: &Mutex<MockState>, chain_id: u64, receipt_status: &str, method: &str, params: &Value) -> Value {
+        let mut state = state.lock().unwrap();
+        match method {
+            "eth_chainId" => json!(format!("{:#x}", chain_id)),
+            "eth_getTransactionCount" => json!("0x0"),
+            "eth_gasPrice" => json!("0x3b9aca00"),
+            "eth_estimateGas" => {
+                state.estimate_requests += 1;
+                json!(state.gas_estimate.unwrap_or("0x7a120"))
+            }
+            "eth_sendRawTransaction" => {
+                let raw = hex::decode(params[0].as_str().unwrap().trim_start_matches("0x")).unwrap();
+                let hash = format!("0x{}", hex::encode(Utils::keccak256(&raw)));
+                state.raw_transactions.push(raw);
+                json!(hash)
+            }
+            "eth_getTransactionReceipt" => {
+                state.receipt_polls += 1;
+                if state.receipt_polls < 2 {
+                    return Value::Null;
+                }
+                json!({
+                    "transactionHash": params[0],
+                    "blockNumber": "0x7",
+                    "gasUsed": "0x9fbf1",
+                    "contractAddress": "0x5fbdb2315678afecb367f032d93f642f64180aa3",
+                    "status": receipt_status,
+                    "logs": [],
+                })
+            }
+            method => panic!("Unexpected RPC method {}", method),
+        }
     }
 }
@@ -1,4 +1,4 @@
-use medi_token::{MediTokenError, utils::Utils};
+use medi_token::{MediTokenError, utils::Utils, U256};
 
 #[cfg(test)]
 mod error_tests {
@@ -7,8 +7,8 @@ mod error_tests {
     #[test]
     fn test_error_display() {
         let insufficient_balance = MediTokenError::InsufficientBalance {
-            required: 1000,
-            available: 500,
+            required: U256::from(1000),
+            available: U256::from(500),
         };
         assert_eq!(
             insufficient_balance.to_string(),
@@ -17,8 +17,8 @@ mod error_tests {
 
         let insufficient_allowance = MediTokenError::InsufficientAllowance {
             spender: "0x123".to_string(),
-            allowance: 100,
-            needed: 200,
+            allowance: U256::from(100),
+            needed: U256::from(200),
         };
         assert_eq!(
             insufficient_allowance.to_string(),
@@ -53,7 +53,7 @@ mod error_tests {
     #[test]
     fn test_error_from_conversions() {
         // Test error conversions
-        let json_error = serde_json::Error::syntax(serde_json::error::ErrorCode::ExpectedColon, 1, 1);
+        let json_error = serde_json::from_str::<serde_json::Value>("{\"key\" 1}").unwrap_err();
         let converted: MediTokenError = json_error.into();
         match converted {
             MediTokenError::SerializationError(_) => {}, // Expected
@@ -64,12 +64,12 @@ mod error_tests {
     #[test]
     fn test_arithmetic_errors() {
         // Test overflow
-        let overflow_result = Utils::check_overflow(u64::MAX, 1);
+        let overflow_result = Utils::check_overflow(U256::MAX, U256::one());
         assert!(overflow_result.is_err());
         assert!(matches!(overflow_result.unwrap_err(), MediTokenError::ArithmeticOverflow));
 
         // Test underflow
-        let underflow_result = Utils::check_underflow(0, 1);
+        let underflow_result = Utils::check_underflow(U256::zero(), U256::one());
         assert!(underflow_result.is_err());
         assert!(matches!(underflow_result.unwrap_err(), MediTokenError::ArithmeticUnderflow));
     }
@@ -0,0 +1,158 @@
+use medi_token::{MediTokenError, U256};
+use medi_token::access_control::ARBITER_ROLE;
+use medi_token::clock::ManualClock;
+use medi_token::escrow::{AppointmentEscrow, BookingStatus, EscrowEvent};
+
+mod common;
+use common::fixtures::{account, Fixture, DAY, NOW};
+
+#[cfg(test)]
+mod escrow_tests {
+    use super::*;
+
+    struct Setup {
+        fx: Fixture,
+        escrow: AppointmentEscrow<ManualClock>,
+        patient: String,
+        provider: String,
+    }
+
+    /// Patient holds 100 tokens and books a 40 token appointment expiring in a week
+    fn booked() -> (Setup, u64) {
+        let mut fx = Fixture::new(1000);
+        let patient = fx.funded_account(100);
+        let provider = account();
+        let mut escrow = AppointmentEscrow::with_clock(account(), fx.clock.clone());
+
+        let id = escrow
+            .book(&mut fx.token, &patient, &provider, U256::from(40), NOW + 7 * DAY, "Dermatology consult")
+            .unwrap();
+        (Setup { fx, escrow, patient, provider }, id)
+    }
+
+    #[test]
+    fn test_booking_locks_funds_in_ledger() {
+        let (s, id) = booked();
+
+        assert_eq!(s.fx.token.balance_of(&s.patient), U256::from(60));
+        assert_eq!(s.fx.token.balance_of(&s.escrow.address), U256::from(40));
+        assert_eq!(s.escrow.locked_balance(), U256::from(40));
+        assert_eq!(s.escrow.booking(id).unwrap().status, BookingStatus::Booked);
+        assert_eq!(
+            s.escrow.get_events().last().unwrap(),
+            &EscrowEvent::Booked {
+                id,
+                patient: s.patient.clone(),
+                provider: s.provider.clone(),
+                amount: U256::from(40),
+                expires_at: NOW + 7 * DAY,
+            }
+        );
+    }
+
+    #[test]
+    fn test_provider_releases_on_completion() {
+        let (mut s, id) = booked();
+
+        let result = s.escrow.complete(&mut s.fx.token, &s.patient, id);
+        assert!(matches!(result.unwrap_err(), MediTokenError::Unauthorized { .. }));
+
+        s.escrow.complete(&mut s.fx.token, &s.provider, id).unwrap();
+        assert_eq!(s.fx.token.balance_of(&s.provider), U256::from(40));
+        assert_eq!(s.fx.token.balance_of(&s.escrow.address), U256::zero());
+        assert_eq!(s.escrow.booking(id).unwrap().status, BookingStatus::Completed);
+        assert!(matches!(s.escrow.get_events().last().unwrap(), EscrowEvent::Released { .. }));
+
+        let result = s.escrow.complete(&mut s.fx.token, &s.provider, id);
+        assert!(matches!(result.unwrap_err(), MediTokenError::InvalidEscrowState { .. }));
+    }
+
+    #[test]
+    fn test_provider_cancellation_refunds_patient() {
+        let (mut s, id) = booked();
+
+        s.escrow.cancel(&mut s.fx.token, &s.provider, id).unwrap();
+        assert_eq!(s.fx.token.balance_of(&s.patient), U256::from(100));
+        assert_eq!(s.escrow.booking(id).unwrap().status, BookingStatus::Refunded);
+        assert_eq!(
+            s.escrow.get_events().last().unwrap(),
+            &EscrowEvent::Refunded { id, patient: s.patient.clone(), amount: U256::from(40) }
+        );
+    }
+
+    #[test]
+    fn test_refund_after_timeout() {
+        let (mut s, id) = booked();
+
+        let result = s.escrow.refund_expired(&mut s.fx.token, id);
+        assert!(matches!(result.unwrap_err(), MediTokenError::EscrowNotExpired { .. }));
+
+        s.fx.clock.advance(7 * DAY);
+        s.escrow.refund_expired(&mut s.fx.token, id).unwrap();
+        assert_eq!(s.fx.token.balance_of(&s.patient), U256::from(100));
+        assert_eq!(s.escrow.locked_balance(), U256::zero());
+
+        // Once refunded the provider can no longer claim the funds
+        assert!(s.escrow.complete(&mut s.fx.token, &s.provider, id).is_err());
+    }
+
+    #[test]
+    fn test_dispute_resolved_by_arbiter() {
+        let (mut s, id) = booked();
+        let arbiter = account();
+        s.fx.token.grant_role(&s.fx.admin, ARBITER_ROLE, &arbiter).unwrap();
+
+        s.escrow.dispute(&s.patient, id, "Appointment was cut short").unwrap();
+        assert_eq!(s.escrow.booking(id).unwrap().status, BookingStatus::Disputed);
+
+        // Disputed funds neither release nor time out
+        assert!(s.escrow.complete(&mut s.fx.token, &s.provider, id).is_err());
+        s.fx.clock.advance(30 * DAY);
+        assert!(s.escrow.refund_expired(&mut s.fx.token, id).is_err());
+        assert_eq!(s.escrow.locked_balance(), U256::from(40));
+
+        let result = s.escrow.resolve(&mut s.fx.token, &s.provider, id, U256::from(40));
+        assert!(matches!(result.unwrap_err(), MediTokenError::MissingRole { .. }));
+        let result = s.escrow.resolve(&mut s.fx.token, &arbiter, id, U256::from(41));
+        assert!(matches!(result.unwrap_err(), MediTokenError::InvalidAmount { .. }));
+
+        s.escrow.resolve(&mut s.fx.token, &arbiter, id, U256::from(25)).unwrap();
+        assert_eq!(s.fx.token.balance_of(&s.provider), U256::from(25));
+        assert_eq!(s.fx.token.balance_of(&s.patient), U256::from(75));
+        assert_eq!(s.fx.token.balance_of(&s.escrow.address), U256::zero());
+        assert_eq!(
+            s.escrow.get_events().last().unwrap(),
+            &EscrowEvent::Resolved { id, arbiter, to_provider: U256::from(25), to_patient: U256::from(15) }
+        );
+    }
+
+    #[test]
+    fn test_dispute_rules() {
+        let (mut s, id) = booked();
+        let outsider = account();
+
+        let result = s.escrow.dispute(&outsider, id, "Spam");
+        assert!(matches!(result.unwrap_err(), MediTokenError::Unauthorized { .. }));
+        s.escrow.dispute(&s.provider, id, "No-show").unwrap();
+        let result = s.escrow.dispute(&s.patient, id, "Again");
+        assert!(matches!(
+            result.unwrap_err(),
+            MediTokenError::InvalidEscrowState { state, expected, .. } if state == "Disputed" && expected == "Booked"
+        ));
+        let result = s.escrow.dispute(&s.patient, 9, "Unknown");
+        assert!(matches!(result.unwrap_err(), MediTokenError::EscrowNotFound { id: 9 }));
+    }
+
+    #[test]
+    fn test_invalid_bookings() {
+        let (mut s, _) = booked();
+
+        let result = s.escrow.book(&mut s.fx.token, &s.patient, &s.provider, U256::zero(), NOW + DAY, "Free");
+        assert!(matches!(result.unwrap_err(), MediTokenError::InvalidAmount { .. }));
+        let result = s.escrow.book(&mut s.fx.token, &s.patient, &s.provider, U256::from(10), NOW, "Past");
+        assert!(matches!(result.unwrap_err(), MediTokenError::EscrowError { .. }));
+        let result = s.escrow.book(&mut s.fx.token, &s.patient, &s.provider, U256::from(61), NOW + DAY, "Too much");
+        assert!(matches!(result.unwrap_err(), MediTokenError::InsufficientBalance { .. }));
+        assert_eq!(s.escrow.locked_balance(), U256::from(40));
+    }
+}
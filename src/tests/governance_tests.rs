@@ -0,0 +1,306 @@
+use medi_token::{MediTokenError, U256};
+use medi_token::access_control::{DEFAULT_ADMIN_ROLE, MINTER_ROLE, PAUSER_ROLE, SNAPSHOT_ROLE};
+use medi_token::governance::{
+    Governance, GovernanceAction, GovernanceConfig, GovernanceEvent, ProposalState, VoteType,
+};
+
+mod common;
+use common::fixtures::{account, Fixture, DAY};
+
+#[cfg(test)]
+mod governance_tests {
+    use super::*;
+
+    struct Setup {
+        fx: Fixture,
+        governance: Governance,
+        alice: String,
+        bob: String,
+    }
+
+    /// Token with 1000 supply split admin 600 / alice 300 / bob 100, governed by default settings
+    fn setup() -> Setup {
+        let mut fx = Fixture::new(1000);
+        let alice = fx.funded_account(300);
+        let bob = fx.funded_account(100);
+
+        let governance = Governance::new(account(), GovernanceConfig::default()).unwrap();
+        for role in [DEFAULT_ADMIN_ROLE, MINTER_ROLE, PAUSER_ROLE, SNAPSHOT_ROLE] {
+            fx.token.grant_role(&fx.admin, role, &governance.address).unwrap();
+        }
+        Setup { fx, governance, alice, bob }
+    }
+
+    fn pass(s: &mut Setup, actions: Vec<GovernanceAction>) -> u64 {
+        let id = s.governance.propose(&mut s.fx.token, &s.alice, actions, "proposal", 0).unwrap();
+        s.governance.cast_vote(&s.fx.token, id, &s.alice, VoteType::For, 1).unwrap();
+        id
+    }
+
+    #[test]
+    fn test_proposal_lifecycle_mints() {
+        let mut s = setup();
+        let recipient = account();
+        let id = pass(&mut s, vec![GovernanceAction::Mint { to: recipient.clone(), amount: U256::from(50) }]);
+        assert_eq!(s.governance.state(id, 1).unwrap(), ProposalState::Active);
+
+        let end = 3 * DAY;
+        assert_eq!(s.governance.state(id, end).unwrap(), ProposalState::Succeeded);
+        let eta = s.governance.queue(id, end).unwrap();
+        assert_eq!(eta, end + 2 * DAY);
+        assert_eq!(s.governance.state(id, end).unwrap(), ProposalState::Queued);
+
+        s.governance.execute(&mut s.fx.token, id, eta).unwrap();
+        assert_eq!(s.governance.state(id, eta).unwrap(), ProposalState::Executed);
+        assert_eq!(s.fx.token.balance_of(&recipient), U256::from(50));
+        assert_eq!(s.governance.get_events().last().unwrap(), &GovernanceEvent::ProposalExecuted { id });
+
+        let result = s.governance.execute(&mut s.fx.token, id, eta);
+        assert!(matches!(result.unwrap_err(), MediTokenError::InvalidProposalState { .. }));
+    }
+
+    #[test]
+    fn test_execute_pause_role_and_cap_actions() {
+        let mut s = setup();
+        let newcomer = account();
+        let id = pass(&mut s, vec![
+            GovernanceAction::Pause,
+            GovernanceAction::GrantRole { role: MINTER_ROLE.to_string(), account: newcomer.clone() },
+            GovernanceAction::SetCap { cap: Some(U256::from(2000)) },
+        ]);
+        let eta = s.governance.queue(id, 3 * DAY).unwrap();
+        s.governance.execute(&mut s.fx.token, id, eta).unwrap();
+
+        assert!(s.fx.token.is_paused());
+        assert!(s.fx.token.has_role(MINTER_ROLE, &newcomer));
+        assert_eq!(s.fx.token.cap, Some(U256::from(2000)));
+    }
+
+    #[test]
+    fn test_update_config_applies_to_new_proposals() {
+        let mut s = setup();
+        let config = GovernanceConfig { voting_period: DAY, timelock_delay: 0, ..GovernanceConfig::default() };
+        let id = pass(&mut s, vec![GovernanceAction::UpdateConfig { config: config.clone() }]);
+        let eta = s.governance.queue(id, 3 * DAY).unwrap();
+        s.governance.execute(&mut s.fx.token, id, eta).unwrap();
+        assert_eq!(s.governance.config, config);
+
+        let id = s.governance.propose(&mut s.fx.token, &s.bob, vec![GovernanceAction::Pause], "pause", 0).unwrap();
+        assert_eq!(s.governance.proposal(id).unwrap().end_time, DAY);
+    }
+
+    #[test]
+    fn test_timelock_must_expire() {
+        let mut s = setup();
+        let id = pass(&mut s, vec![GovernanceAction::Pause]);
+        let eta = s.governance.queue(id, 3 * DAY).unwrap();
+
+        let result = s.governance.execute(&mut s.fx.token, id, eta - 1);
+        assert!(matches!(result.unwrap_err(), MediTokenError::TimelockNotExpired { eta: e, .. } if e == eta));
+        assert!(!s.fx.token.is_paused());
+    }
+
+    #[test]
+    fn test_cannot_queue_before_voting_ends() {
+        let mut s = setup();
+        let id = pass(&mut s, vec![GovernanceAction::Pause]);
+
+        let result = s.governance.queue(id, 3 * DAY - 1);
+        assert!(matches!(
+            result.unwrap_err(),
+            MediTokenError::InvalidProposalState { state, expected, .. } if state == "Active" && expected == "Succeeded"
+        ));
+    }
+
+    #[test]
+    fn test_defeated_without_quorum() {
+        let mut s = setup();
+        let small = account();
+        s.fx.token.transfer(&s.fx.admin, &small, U256::from(39)).unwrap();
+        let id = s.governance.propose(&mut s.fx.token, &small, vec![GovernanceAction::Pause], "pause", 0).unwrap();
+        assert_eq!(s.governance.proposal(id).unwrap().quorum_votes, U256::from(40));
+
+        s.governance.cast_vote(&s.fx.token, id, &small, VoteType::For, 1).unwrap();
+        assert_eq!(s.governance.state(id, 3 * DAY).unwrap(), ProposalState::Defeated);
+        assert!(s.governance.queue(id, 3 * DAY).is_err());
+    }
+
+    #[test]
+    fn test_abstain_counts_toward_quorum_only() {
+        let mut s = setup();
+        let small = account();
+        s.fx.token.transfer(&s.fx.admin, &small, U256::from(10)).unwrap();
+        let id = s.governance.propose(&mut s.fx.token, &small, vec![GovernanceAction::Pause], "pause", 0).unwrap();
+
+        s.governance.cast_vote(&s.fx.token, id, &small, VoteType::For, 1).unwrap();
+        s.governance.cast_vote(&s.fx.token, id, &s.alice, VoteType::Abstain, 1).unwrap();
+        assert_eq!(s.governance.state(id, 3 * DAY).unwrap(), ProposalState::Succeeded);
+    }
+
+    #[test]
+    fn test_defeated_by_majority_against() {
+        let mut s = setup();
+        let id = pass(&mut s, vec![GovernanceAction::Pause]);
+        s.governance.cast_vote(&s.fx.token, id, &s.fx.admin, VoteType::Against, 1).unwrap();
+
+        let proposal = s.governance.proposal(id).unwrap();
+        assert_eq!(proposal.for_votes, U256::from(300));
+        assert_eq!(proposal.against_votes, U256::from(600));
+        assert_eq!(s.governance.state(id, 3 * DAY).unwrap(), ProposalState::Defeated);
+    }
+
+    #[test]
+    fn test_tie_is_defeated() {
+        let mut s = setup();
+        s.fx.token.transfer(&s.fx.admin, &s.bob, U256::from(200)).unwrap();
+        let id = pass(&mut s, vec![GovernanceAction::Pause]);
+        s.governance.cast_vote(&s.fx.token, id, &s.bob, VoteType::Against, 1).unwrap();
+        assert_eq!(s.governance.state(id, 3 * DAY).unwrap(), ProposalState::Defeated);
+    }
+
+    #[test]
+    fn test_vote_rules() {
+        let mut s = setup();
+        let id = pass(&mut s, vec![GovernanceAction::Pause]);
+
+        let result = s.governance.cast_vote(&s.fx.token, id, &s.alice, VoteType::Against, 2);
+        assert!(matches!(result.unwrap_err(), MediTokenError::AlreadyVoted { .. }));
+
+        let nobody = account();
+        let result = s.governance.cast_vote(&s.fx.token, id, &nobody, VoteType::For, 2);
+        assert!(matches!(result.unwrap_err(), MediTokenError::InsufficientVotingPower { .. }));
+
+        let result = s.governance.cast_vote(&s.fx.token, id, &s.bob, VoteType::For, 3 * DAY);
+        assert!(matches!(result.unwrap_err(), MediTokenError::InvalidProposalState { .. }));
+
+        let result = s.governance.cast_vote(&s.fx.token, 99, &s.bob, VoteType::For, 1);
+        assert!(matches!(result.unwrap_err(), MediTokenError::ProposalNotFound { id: 99 }));
+    }
+
+    #[test]
+    fn test_votes_use_snapshot_balances() {
+        let mut s = setup();
+        let id = s.governance.propose(&mut s.fx.token, &s.bob, vec![GovernanceAction::Pause], "pause", 0).unwrap();
+
+        // Tokens moved after the snapshot cannot be voted twice
+        s.fx.token.transfer(&s.alice, &s.bob, U256::from(300)).unwrap();
+        assert_eq!(s.governance.cast_vote(&s.fx.token, id, &s.bob, VoteType::For, 1).unwrap(), U256::from(100));
+        assert_eq!(s.governance.cast_vote(&s.fx.token, id, &s.alice, VoteType::For, 1).unwrap(), U256::from(300));
+    }
+
+    #[test]
+    fn test_delegation() {
+        let mut s = setup();
+        s.governance.delegate(&s.fx.token, &s.alice, &s.bob);
+        assert_eq!(
+            s.governance.get_events().last().unwrap(),
+            &GovernanceEvent::DelegateChanged {
+                delegator: s.alice.clone(),
+                from_delegate: s.alice.clone(),
+                to_delegate: s.bob.clone(),
+            }
+        );
+
+        let id = s.governance.propose(&mut s.fx.token, &s.bob, vec![GovernanceAction::Pause], "pause", 0).unwrap();
+        assert_eq!(s.governance.voting_power(&s.fx.token, &s.bob, 1).unwrap(), U256::from(400));
+        assert_eq!(s.governance.cast_vote(&s.fx.token, id, &s.bob, VoteType::For, 1).unwrap(), U256::from(400));
+
+        let result = s.governance.cast_vote(&s.fx.token, id, &s.alice, VoteType::For, 1);
+        assert!(matches!(result.unwrap_err(), MediTokenError::InsufficientVotingPower { .. }));
+    }
+
+    #[test]
+    fn test_delegation_does_not_affect_open_proposals() {
+        let mut s = setup();
+        let id = s.governance.propose(&mut s.fx.token, &s.bob, vec![GovernanceAction::Pause], "pause", 0).unwrap();
+        s.governance.delegate(&s.fx.token, &s.alice, &s.bob);
+
+        assert_eq!(s.governance.cast_vote(&s.fx.token, id, &s.bob, VoteType::For, 1).unwrap(), U256::from(100));
+        assert_eq!(s.governance.cast_vote(&s.fx.token, id, &s.alice, VoteType::For, 1).unwrap(), U256::from(300));
+
+        let next = s.governance.propose(&mut s.fx.token, &s.bob, vec![GovernanceAction::Pause], "pause", 0).unwrap();
+        assert_eq!(s.governance.cast_vote(&s.fx.token, next, &s.bob, VoteType::For, 1).unwrap(), U256::from(400));
+
+        // Delegating back to oneself restores direct voting for later proposals
+        s.governance.delegate(&s.fx.token, &s.alice, &s.alice);
+        let last = s.governance.propose(&mut s.fx.token, &s.bob, vec![GovernanceAction::Pause], "pause", 0).unwrap();
+        assert_eq!(s.governance.cast_vote(&s.fx.token, last, &s.alice, VoteType::For, 1).unwrap(), U256::from(300));
+    }
+
+    #[test]
+    fn test_failed_action_leaves_token_unchanged() {
+        let mut s = setup();
+        let recipient = account();
+        let id = pass(&mut s, vec![
+            GovernanceAction::Mint { to: recipient.clone(), amount: U256::from(50) },
+            GovernanceAction::SetCap { cap: Some(U256::from(1020)) },
+        ]);
+        let eta = s.governance.queue(id, 3 * DAY).unwrap();
+
+        let result = s.governance.execute(&mut s.fx.token, id, eta);
+        assert!(matches!(result.unwrap_err(), MediTokenError::SupplyCapExceeded { .. }));
+        assert_eq!(s.fx.token.balance_of(&recipient), U256::zero());
+        assert_eq!(s.fx.token.total_supply, U256::from(1000));
+        assert_eq!(s.governance.state(id, eta).unwrap(), ProposalState::Queued);
+    }
+
+    #[test]
+    fn test_execute_requires_governance_roles() {
+        let mut s = setup();
+        let address = s.governance.address.clone();
+        s.fx.token.revoke_role(&s.fx.admin, PAUSER_ROLE, &address).unwrap();
+        let id = pass(&mut s, vec![GovernanceAction::Pause]);
+        let eta = s.governance.queue(id, 3 * DAY).unwrap();
+
+        let result = s.governance.execute(&mut s.fx.token, id, eta);
+        assert!(matches!(result.unwrap_err(), MediTokenError::MissingRole { .. }));
+        assert!(!s.fx.token.is_paused());
+    }
+
+    #[test]
+    fn test_cancel() {
+        let mut s = setup();
+        let id = pass(&mut s, vec![GovernanceAction::Pause]);
+
+        let result = s.governance.cancel(&s.bob, id);
+        assert!(matches!(result.unwrap_err(), MediTokenError::Unauthorized { .. }));
+
+        s.governance.cancel(&s.alice, id).unwrap();
+        assert_eq!(s.governance.state(id, 3 * DAY).unwrap(), ProposalState::Canceled);
+        assert!(s.governance.queue(id, 3 * DAY).is_err());
+        assert!(s.governance.cancel(&s.alice, id).is_err());
+    }
+
+    #[test]
+    fn test_proposal_threshold() {
+        let mut s = setup();
+        s.governance.config.proposal_threshold = U256::from(200);
+
+        let result = s.governance.propose(&mut s.fx.token, &s.bob, vec![GovernanceAction::Pause], "pause", 0);
+        assert!(matches!(
+            result.unwrap_err(),
+            MediTokenError::InsufficientVotingPower { required, available }
+                if required == U256::from(200) && available == U256::from(100)
+        ));
+        // A rejected proposal takes no snapshot
+        assert_eq!(s.fx.token.current_snapshot_id(), 0);
+        assert!(s.governance.propose(&mut s.fx.token, &s.alice, vec![GovernanceAction::Pause], "pause", 0).is_ok());
+        assert_eq!(s.fx.token.current_snapshot_id(), 1);
+
+        // Delegated balances count towards the threshold
+        s.governance.delegate(&s.fx.token, &s.alice, &s.bob);
+        assert!(s.governance.propose(&mut s.fx.token, &s.bob, vec![GovernanceAction::Pause], "pause", 0).is_ok());
+
+        let result = s.governance.propose(&mut s.fx.token, &s.alice, vec![], "empty", 0);
+        assert!(matches!(result.unwrap_err(), MediTokenError::GovernanceError { .. }));
+    }
+
+    #[test]
+    fn test_config_validation() {
+        let address = account();
+        let config = GovernanceConfig { quorum_bps: 10_001, ..GovernanceConfig::default() };
+        assert!(Governance::new(address.clone(), config).is_err());
+        let config = GovernanceConfig { voting_period: 0, ..GovernanceConfig::default() };
+        assert!(Governance::new(address, config).is_err());
+    }
+}
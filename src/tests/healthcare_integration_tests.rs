@@ -1,4 +1,5 @@
-use medi_token::{MediToken, utils::Utils, MediTokenError};
+use medi_token::{MediToken, utils::Utils, U256};
+use medi_token::access_control::{PROVIDER_ROLE, PATIENT_ROLE};
 
 #[cfg(test)]
 mod healthcare_integration_tests {
@@ -12,31 +13,37 @@ mod healthcare_integration_tests {
         let patient = Utils::generate_random_address();
         let unauthorized_user = Utils::generate_random_address();
         
-        let mut token = MediToken::new(Utils::ether_to_wei(10000.0), system_admin.clone());
+        let mut token = MediToken::new(Utils::ether_to_wei("10000").unwrap(), system_admin.clone());
         
         // Distribute tokens to authorized users
-        let doctor_tokens = Utils::ether_to_wei(100.0);
-        let patient_tokens = Utils::ether_to_wei(50.0);
+        let doctor_tokens = Utils::ether_to_wei("100").unwrap();
+        let patient_tokens = Utils::ether_to_wei("50").unwrap();
         
         token.transfer(&system_admin, &doctor, doctor_tokens).unwrap();
         token.transfer(&system_admin, &patient, patient_tokens).unwrap();
         
+        // Register explicit healthcare roles
+        token.grant_role(&system_admin, PROVIDER_ROLE, &doctor).unwrap();
+        token.grant_role(&system_admin, PATIENT_ROLE, &patient).unwrap();
+        
         // Simulate healthcare access control
-        fn can_access_medical_records(token: &MediToken, user: &str, required_tokens: u64) -> bool {
-            token.balance_of(user) >= required_tokens
+        fn can_access_medical_records(token: &MediToken, user: &str) -> bool {
+            token.has_role(PROVIDER_ROLE, user) || token.has_role(PATIENT_ROLE, user)
         }
         
         fn can_write_prescription(token: &MediToken, user: &str) -> bool {
-            token.balance_of(user) >= Utils::ether_to_wei(50.0) 
+            token.has_role(PROVIDER_ROLE, user)
         }
         
         // Test access control
-        assert!(can_access_medical_records(&token, &doctor, Utils::ether_to_wei(10.0)));
-        assert!(can_access_medical_records(&token, &patient, Utils::ether_to_wei(10.0)));
-        assert!(!can_access_medical_records(&token, &unauthorized_user, Utils::ether_to_wei(10.0)));
+        assert!(can_access_medical_records(&token, &doctor));
+        assert!(can_access_medical_records(&token, &patient));
+        assert!(!can_access_medical_records(&token, &unauthorized_user));
         
         assert!(can_write_prescription(&token, &doctor));
-        assert!(can_write_prescription(&token, &patient));
+        // Holding tokens does not make a patient a prescriber
+        assert!(token.balance_of(&patient) >= Utils::ether_to_wei("50").unwrap());
+        assert!(!can_write_prescription(&token, &patient));
         assert!(!can_write_prescription(&token, &unauthorized_user));
     }
 
@@ -47,12 +54,12 @@ mod healthcare_integration_tests {
         let healthcare_provider = Utils::generate_random_address();
         let insurance_company = Utils::generate_random_address();
         
-        let mut token = MediToken::new(Utils::ether_to_wei(1000.0), patient.clone());
+        let mut token = MediToken::new(Utils::ether_to_wei("1000").unwrap(), patient.clone());
         
         // Simulate a medical bill payment
-        let consultation_fee = Utils::ether_to_wei(25.0);
-        let insurance_coverage = Utils::ether_to_wei(20.0);
-        let patient_copay = Utils::ether_to_wei(5.0);
+        let consultation_fee = Utils::ether_to_wei("25").unwrap();
+        let insurance_coverage = Utils::ether_to_wei("20").unwrap();
+        let patient_copay = Utils::ether_to_wei("5").unwrap();
         
         // Patient approves insurance to pay on their behalf
         token.approve(&patient, &insurance_company, insurance_coverage).unwrap();
@@ -64,12 +71,12 @@ mod healthcare_integration_tests {
         token.transfer(&patient, &healthcare_provider, patient_copay).unwrap();
         
         // Verify final balances
-        let expected_patient_balance = Utils::ether_to_wei(1000.0) - consultation_fee;
+        let expected_patient_balance = Utils::ether_to_wei("1000").unwrap() - consultation_fee;
         let expected_provider_balance = consultation_fee;
         
         assert_eq!(token.balance_of(&patient), expected_patient_balance);
         assert_eq!(token.balance_of(&healthcare_provider), expected_provider_balance);
-        assert_eq!(token.balance_of(&insurance_company), 0);
+        assert_eq!(token.balance_of(&insurance_company), U256::zero());
     }
 
     /// Test health rewards system
@@ -80,50 +87,52 @@ mod healthcare_integration_tests {
         let patient2 = Utils::generate_random_address();
         let patient3 = Utils::generate_random_address();
         
-        let mut token = MediToken::new(Utils::ether_to_wei(10000.0), health_system.clone());
+        let mut token = MediToken::new(Utils::ether_to_wei("10000").unwrap(), health_system.clone());
         
         // Simulate health rewards for different activities
         struct HealthActivity {
             patient: String,
             activity: String,
-            reward: u64,
+            reward: U256,
         }
         
         let activities = vec![
             HealthActivity {
                 patient: patient1.clone(),
                 activity: "Annual checkup".to_string(),
-                reward: Utils::ether_to_wei(10.0),
+                reward: Utils::ether_to_wei("10").unwrap(),
             },
             HealthActivity {
                 patient: patient2.clone(),
                 activity: "Vaccination".to_string(),
-                reward: Utils::ether_to_wei(15.0),
+                reward: Utils::ether_to_wei("15").unwrap(),
             },
             HealthActivity {
                 patient: patient3.clone(),
                 activity: "Health screening".to_string(),
-                reward: Utils::ether_to_wei(20.0),
+                reward: Utils::ether_to_wei("20").unwrap(),
             },
             HealthActivity {
                 patient: patient1.clone(),
                 activity: "Wellness program completion".to_string(),
-                reward: Utils::ether_to_wei(25.0),
+                reward: Utils::ether_to_wei("25").unwrap(),
             },
         ];
         
         // Distribute rewards
         for activity in &activities {
-            token.transfer(&health_system, &activity.patient, activity.reward).unwrap();
+            token
+                .transfer(&health_system, &activity.patient, activity.reward)
+                .unwrap_or_else(|e| panic!("{} reward failed: {}", activity.activity, e));
         }
         
         // Verify patient balances
-        assert_eq!(token.balance_of(&patient1), Utils::ether_to_wei(35.0)); // 10 + 25
-        assert_eq!(token.balance_of(&patient2), Utils::ether_to_wei(15.0));
-        assert_eq!(token.balance_of(&patient3), Utils::ether_to_wei(20.0));
+        assert_eq!(token.balance_of(&patient1), Utils::ether_to_wei("35").unwrap()); // 10 + 25
+        assert_eq!(token.balance_of(&patient2), Utils::ether_to_wei("15").unwrap());
+        assert_eq!(token.balance_of(&patient3), Utils::ether_to_wei("20").unwrap());
         
-        let total_rewards = Utils::ether_to_wei(70.0);
-        let expected_system_balance = Utils::ether_to_wei(10000.0) - total_rewards;
+        let total_rewards = Utils::ether_to_wei("70").unwrap();
+        let expected_system_balance = Utils::ether_to_wei("10000").unwrap() - total_rewards;
         assert_eq!(token.balance_of(&health_system), expected_system_balance);
     }
 
@@ -135,20 +144,20 @@ mod healthcare_integration_tests {
         let donor2 = Utils::generate_random_address();
         let research_project = Utils::generate_random_address();
         
-        let mut token = MediToken::new(Utils::ether_to_wei(0.0), research_foundation.clone());
+        let mut token = MediToken::new(Utils::ether_to_wei("0").unwrap(), research_foundation.clone());
         
         // Give tokens to donors
-        let donor1_amount = Utils::ether_to_wei(500.0);
-        let donor2_amount = Utils::ether_to_wei(300.0);
+        let donor1_amount = Utils::ether_to_wei("500").unwrap();
+        let donor2_amount = Utils::ether_to_wei("300").unwrap();
         
         // Simulate initial token distribution (e.g., from token sale)
-        token.balances.insert(donor1.clone(), donor1_amount);
-        token.balances.insert(donor2.clone(), donor2_amount);
-        token.total_supply = donor1_amount + donor2_amount;
+        token.mint(&research_foundation, &donor1, donor1_amount).unwrap();
+        token.mint(&research_foundation, &donor2, donor2_amount).unwrap();
+        assert_eq!(token.total_supply, donor1_amount + donor2_amount);
         
         // Donors fund research project
-        let donation1 = Utils::ether_to_wei(100.0);
-        let donation2 = Utils::ether_to_wei(150.0);
+        let donation1 = Utils::ether_to_wei("100").unwrap();
+        let donation2 = Utils::ether_to_wei("150").unwrap();
         
         token.transfer(&donor1, &research_project, donation1).unwrap();
         token.transfer(&donor2, &research_project, donation2).unwrap();
@@ -167,27 +176,27 @@ mod healthcare_integration_tests {
         let pharmacy = Utils::generate_random_address();
         let medical_supplier = Utils::generate_random_address();
         
-        let mut token = MediToken::new(Utils::ether_to_wei(5000.0), hospital.clone());
+        let mut token = MediToken::new(Utils::ether_to_wei("5000").unwrap(), hospital.clone());
         
         // Hospital purchases medical supplies
         let supply_orders = vec![
-            ("Surgical masks", Utils::ether_to_wei(50.0)),
-            ("Medications", Utils::ether_to_wei(200.0)),
-            ("Medical equipment", Utils::ether_to_wei(500.0)),
+            ("Surgical masks", Utils::ether_to_wei("50").unwrap()),
+            ("Medications", Utils::ether_to_wei("200").unwrap()),
+            ("Medical equipment", Utils::ether_to_wei("500").unwrap()),
         ];
         
-        let mut total_cost = 0u64;
+        let mut total_cost = U256::zero();
         for (_item, cost) in &supply_orders {
             token.transfer(&hospital, &medical_supplier, *cost).unwrap();
-            total_cost += cost;
+            total_cost += *cost;
         }
         
         // Pharmacy orders from hospital
-        let pharmacy_order = Utils::ether_to_wei(75.0);
+        let pharmacy_order = Utils::ether_to_wei("75").unwrap();
         token.transfer(&hospital, &pharmacy, pharmacy_order).unwrap();
         
         // Verify balances
-        let expected_hospital_balance = Utils::ether_to_wei(5000.0) - total_cost - pharmacy_order;
+        let expected_hospital_balance = Utils::ether_to_wei("5000").unwrap() - total_cost - pharmacy_order;
         assert_eq!(token.balance_of(&hospital), expected_hospital_balance);
         assert_eq!(token.balance_of(&medical_supplier), total_cost);
         assert_eq!(token.balance_of(&pharmacy), pharmacy_order);
@@ -201,34 +210,38 @@ mod healthcare_integration_tests {
         let stakeholder2 = Utils::generate_random_address();
         let stakeholder3 = Utils::generate_random_address();
         
-        let mut token = MediToken::new(Utils::ether_to_wei(1000.0), governance_contract.clone());
+        let mut token = MediToken::new(Utils::ether_to_wei("1000").unwrap(), governance_contract.clone());
         
         // Distribute voting tokens
-        let voting_power1 = Utils::ether_to_wei(300.0);
-        let voting_power2 = Utils::ether_to_wei(250.0);
-        let voting_power3 = Utils::ether_to_wei(200.0);
+        let voting_power1 = Utils::ether_to_wei("300").unwrap();
+        let voting_power2 = Utils::ether_to_wei("250").unwrap();
+        let voting_power3 = Utils::ether_to_wei("200").unwrap();
         
         token.transfer(&governance_contract, &stakeholder1, voting_power1).unwrap();
         token.transfer(&governance_contract, &stakeholder2, voting_power2).unwrap();
         token.transfer(&governance_contract, &stakeholder3, voting_power3).unwrap();
         
-        // Simulate voting power calculation
-        fn calculate_voting_power(token: &MediToken, voter: &str) -> f64 {
-            let balance = token.balance_of(voter);
-            Utils::wei_to_ether(balance)
+        // Voting power is fixed when the proposal's snapshot is taken
+        let snapshot_id = token.snapshot(&governance_contract).unwrap();
+        fn calculate_voting_power(token: &MediToken, voter: &str, snapshot_id: u64) -> U256 {
+            token.balance_of_at(voter, snapshot_id).unwrap()
         }
         
-        let total_voting_power = calculate_voting_power(&token, &stakeholder1) +
-                               calculate_voting_power(&token, &stakeholder2) +
-                               calculate_voting_power(&token, &stakeholder3);
+        // Moving tokens to another voter after the snapshot does not add votes
+        token.transfer(&stakeholder1, &stakeholder2, voting_power1).unwrap();
+        
+        let total_voting_power = calculate_voting_power(&token, &stakeholder1, snapshot_id) +
+                               calculate_voting_power(&token, &stakeholder2, snapshot_id) +
+                               calculate_voting_power(&token, &stakeholder3, snapshot_id);
         
-        assert_eq!(total_voting_power, 750.0); // 300 + 250 + 200
+        assert_eq!(Utils::wei_to_ether(total_voting_power), "750"); // 300 + 250 + 200
+        assert_eq!(Utils::wei_to_ether(token.balance_of(&stakeholder2)), "550");
         
         // Test minimum voting threshold
-        let min_voting_threshold = 100.0; // 100 tokens minimum to vote
-        assert!(calculate_voting_power(&token, &stakeholder1) >= min_voting_threshold);
-        assert!(calculate_voting_power(&token, &stakeholder2) >= min_voting_threshold);
-        assert!(calculate_voting_power(&token, &stakeholder3) >= min_voting_threshold);
+        let min_voting_threshold = Utils::ether_to_wei("100").unwrap(); // 100 tokens minimum to vote
+        assert!(calculate_voting_power(&token, &stakeholder1, snapshot_id) >= min_voting_threshold);
+        assert!(calculate_voting_power(&token, &stakeholder2, snapshot_id) >= min_voting_threshold);
+        assert!(calculate_voting_power(&token, &stakeholder3, snapshot_id) >= min_voting_threshold);
     }
 
     /// Test patient data ownership and access
@@ -239,11 +252,11 @@ mod healthcare_integration_tests {
         let doctor2 = Utils::generate_random_address();
         let researcher = Utils::generate_random_address();
         
-        let mut token = MediToken::new(Utils::ether_to_wei(100.0), patient.clone());
+        let mut token = MediToken::new(Utils::ether_to_wei("100").unwrap(), patient.clone());
         
         // Patient grants access to doctors (tokens represent access rights)
-        let doctor_access_fee = Utils::ether_to_wei(10.0);
-        let research_access_fee = Utils::ether_to_wei(5.0);
+        let doctor_access_fee = Utils::ether_to_wei("10").unwrap();
+        let research_access_fee = Utils::ether_to_wei("5").unwrap();
         
         // Doctor 1 pays for access
         token.balances.insert(doctor1.clone(), doctor_access_fee);
@@ -257,13 +270,13 @@ mod healthcare_integration_tests {
         token.balances.insert(researcher.clone(), research_access_fee);
         token.transfer(&researcher, &patient, research_access_fee).unwrap();
         
-        let total_access_payments = doctor_access_fee * 2 + research_access_fee;
-        let expected_patient_balance = Utils::ether_to_wei(100.0) + total_access_payments;
+        let total_access_payments = doctor_access_fee * 2u64 + research_access_fee;
+        let expected_patient_balance = Utils::ether_to_wei("100").unwrap() + total_access_payments;
         
         assert_eq!(token.balance_of(&patient), expected_patient_balance);
-        assert_eq!(token.balance_of(&doctor1), 0);
-        assert_eq!(token.balance_of(&doctor2), 0);
-        assert_eq!(token.balance_of(&researcher), 0);
+        assert_eq!(token.balance_of(&doctor1), U256::zero());
+        assert_eq!(token.balance_of(&doctor2), U256::zero());
+        assert_eq!(token.balance_of(&researcher), U256::zero());
     }
 
     /// Test emergency access override system
@@ -273,25 +286,25 @@ mod healthcare_integration_tests {
         let patient = Utils::generate_random_address();
         let hospital_admin = Utils::generate_random_address();
         
-        let mut token = MediToken::new(Utils::ether_to_wei(1000.0), hospital_admin.clone());
+        let mut token = MediToken::new(Utils::ether_to_wei("1000").unwrap(), hospital_admin.clone());
         
         // Give patient some tokens
-        let patient_tokens = Utils::ether_to_wei(50.0);
+        let patient_tokens = Utils::ether_to_wei("50").unwrap();
         token.transfer(&hospital_admin, &patient, patient_tokens).unwrap();
         
         // Emergency responder needs immediate access
-        let emergency_access_tokens = Utils::ether_to_wei(100.0);
+        let emergency_access_tokens = Utils::ether_to_wei("100").unwrap();
         
         // Hospital admin grants emergency access
         token.transfer(&hospital_admin, &emergency_responder, emergency_access_tokens).unwrap();
         
         // Verify emergency responder has sufficient access
-        assert!(token.balance_of(&emergency_responder) >= Utils::ether_to_wei(50.0));
+        assert!(token.balance_of(&emergency_responder) >= Utils::ether_to_wei("50").unwrap());
         
         // After emergency, tokens can be reclaimed
         let remaining_tokens = token.balance_of(&emergency_responder);
         token.transfer(&emergency_responder, &hospital_admin, remaining_tokens).unwrap();
         
-        assert_eq!(token.balance_of(&emergency_responder), 0);
+        assert_eq!(token.balance_of(&emergency_responder), U256::zero());
     }
 }
\ No newline at end of file
@@ -0,0 +1,172 @@
+use medi_token::{MediTokenError, U256};
+use medi_token::clock::ManualClock;
+use medi_token::marketplace::{FeeSplit, Marketplace, MarketplaceEvent, OfferTerms};
+
+mod common;
+use common::fixtures::{account, Fixture, DAY, NOW};
+
+#[cfg(test)]
+mod marketplace_tests {
+    use super::*;
+
+    struct Setup {
+        fx: Fixture,
+        market: Marketplace<ManualClock>,
+        patient: String,
+        custodian: String,
+        researcher: String,
+    }
+
+    /// Platform takes 5% and the custodian 10%; the researcher holds all 1000 tokens
+    fn setup() -> Setup {
+        let mut fx = Fixture::new(1000);
+        let researcher = fx.funded_account(1000);
+        let fee_split = FeeSplit { platform_bps: 500, custodian_bps: 1000 };
+        let market = Marketplace::with_clock(account(), fee_split, fx.clock.clone()).unwrap();
+        Setup { fx, market, patient: account(), custodian: account(), researcher }
+    }
+
+    fn terms(s: &Setup, price: u64, access_duration: Option<u64>) -> OfferTerms {
+        OfferTerms {
+            dataset: "Anonymized glucose readings 2023".to_string(),
+            price: U256::from(price),
+            custodian: s.custodian.clone(),
+            terms: "Non-commercial research only".to_string(),
+            access_duration,
+        }
+    }
+
+    #[test]
+    fn test_purchase_splits_payment() {
+        let mut s = setup();
+        let id = s.market.list_offer(&s.patient, terms(&s, 200, None)).unwrap();
+
+        let grant = s.market.purchase(&mut s.fx.token, &s.researcher, id).unwrap();
+        assert_eq!(grant.price, U256::from(200));
+        assert_eq!(grant.purchased_at, NOW);
+
+        assert_eq!(s.fx.token.balance_of(&s.patient), U256::from(170));
+        assert_eq!(s.fx.token.balance_of(&s.market.platform_account), U256::from(10));
+        assert_eq!(s.fx.token.balance_of(&s.custodian), U256::from(20));
+        assert_eq!(s.fx.token.balance_of(&s.researcher), U256::from(800));
+        assert!(s.market.has_access(&s.researcher, id));
+        assert_eq!(
+            s.market.get_events().last().unwrap(),
+            &MarketplaceEvent::DatasetPurchased {
+                offer_id: id,
+                researcher: s.researcher.clone(),
+                to_patient: U256::from(170),
+                to_platform: U256::from(10),
+                to_custodian: U256::from(20),
+            }
+        );
+    }
+
+    #[test]
+    fn test_split_rounding_conserves_tokens() {
+        let split = FeeSplit { platform_bps: 333, custodian_bps: 1667 };
+        for price in [1u64, 7, 99, 101, 9_999, 123_457] {
+            let (patient, platform, custodian) = split.split(U256::from(price));
+            assert_eq!(patient + platform + custodian, U256::from(price));
+        }
+        assert_eq!(split.split(U256::from(1)), (U256::from(1), U256::zero(), U256::zero()));
+
+        let (patient, platform, custodian) = split.split(U256::MAX);
+        assert_eq!(patient + platform + custodian, U256::MAX);
+    }
+
+    #[test]
+    fn test_odd_price_purchase_keeps_supply() {
+        let mut s = setup();
+        let id = s.market.list_offer(&s.patient, terms(&s, 333, None)).unwrap();
+        s.market.purchase(&mut s.fx.token, &s.researcher, id).unwrap();
+
+        let paid = s.fx.token.balance_of(&s.patient)
+            + s.fx.token.balance_of(&s.market.platform_account)
+            + s.fx.token.balance_of(&s.custodian);
+        assert_eq!(paid, U256::from(333));
+        assert_eq!(s.fx.token.total_supply, U256::from(1000));
+        assert_eq!(s.fx.token.balance_of(&s.market.platform_account), U256::from(16));
+        assert_eq!(s.fx.token.balance_of(&s.custodian), U256::from(33));
+    }
+
+    #[test]
+    fn test_fee_split_is_configurable() {
+        let mut s = setup();
+        let split = FeeSplit { platform_bps: 0, custodian_bps: 2500 };
+
+        let result = s.market.set_fee_split(&s.fx.token, &s.researcher, split);
+        assert!(matches!(result.unwrap_err(), MediTokenError::MissingRole { .. }));
+        let invalid = FeeSplit { platform_bps: 5000, custodian_bps: 5001 };
+        assert!(matches!(
+            s.market.set_fee_split(&s.fx.token, &s.fx.admin, invalid).unwrap_err(),
+            MediTokenError::MarketplaceError { .. }
+        ));
+
+        s.market.set_fee_split(&s.fx.token, &s.fx.admin, split).unwrap();
+        let id = s.market.list_offer(&s.patient, terms(&s, 100, None)).unwrap();
+        s.market.purchase(&mut s.fx.token, &s.researcher, id).unwrap();
+        assert_eq!(s.fx.token.balance_of(&s.patient), U256::from(75));
+        assert_eq!(s.fx.token.balance_of(&s.custodian), U256::from(25));
+        assert_eq!(s.fx.token.balance_of(&s.market.platform_account), U256::zero());
+    }
+
+    #[test]
+    fn test_access_expires_and_can_be_renewed() {
+        let mut s = setup();
+        let id = s.market.list_offer(&s.patient, terms(&s, 100, Some(30 * DAY))).unwrap();
+        s.market.purchase(&mut s.fx.token, &s.researcher, id).unwrap();
+
+        let result = s.market.purchase(&mut s.fx.token, &s.researcher, id);
+        assert!(matches!(result.unwrap_err(), MediTokenError::MarketplaceError { .. }));
+
+        s.fx.clock.advance(30 * DAY);
+        assert!(!s.market.has_access(&s.researcher, id));
+        s.market.purchase(&mut s.fx.token, &s.researcher, id).unwrap();
+        assert!(s.market.has_access(&s.researcher, id));
+        assert_eq!(s.market.grants_for_offer(id).len(), 2);
+    }
+
+    #[test]
+    fn test_failed_purchase_moves_nothing() {
+        let mut s = setup();
+        let id = s.market.list_offer(&s.patient, terms(&s, 2000, None)).unwrap();
+
+        let result = s.market.purchase(&mut s.fx.token, &s.researcher, id);
+        assert!(matches!(result.unwrap_err(), MediTokenError::InsufficientBalance { .. }));
+        assert_eq!(s.fx.token.balance_of(&s.researcher), U256::from(1000));
+        assert_eq!(s.fx.token.balance_of(&s.patient), U256::zero());
+        assert!(!s.market.has_access(&s.researcher, id));
+    }
+
+    #[test]
+    fn test_delist() {
+        let mut s = setup();
+        let id = s.market.list_offer(&s.patient, terms(&s, 100, None)).unwrap();
+        s.market.purchase(&mut s.fx.token, &s.researcher, id).unwrap();
+
+        let result = s.market.delist_offer(&s.researcher, id);
+        assert!(matches!(result.unwrap_err(), MediTokenError::Unauthorized { .. }));
+        s.market.delist_offer(&s.patient, id).unwrap();
+        assert!(s.market.active_offers().is_empty());
+        assert!(s.market.has_access(&s.researcher, id));
+
+        let other = account();
+        s.fx.token.transfer(&s.researcher, &other, U256::from(100)).unwrap();
+        let result = s.market.purchase(&mut s.fx.token, &other, id);
+        assert!(matches!(result.unwrap_err(), MediTokenError::MarketplaceError { .. }));
+        let result = s.market.purchase(&mut s.fx.token, &other, 5);
+        assert!(matches!(result.unwrap_err(), MediTokenError::OfferNotFound { id: 5 }));
+    }
+
+    #[test]
+    fn test_invalid_offers() {
+        let mut s = setup();
+        let result = s.market.list_offer(&s.patient, terms(&s, 0, None));
+        assert!(matches!(result.unwrap_err(), MediTokenError::InvalidAmount { .. }));
+        assert!(s.market.list_offer(&s.patient, terms(&s, 10, Some(0))).is_err());
+
+        let invalid = FeeSplit { platform_bps: 10_001, custodian_bps: 0 };
+        assert!(Marketplace::new(account(), invalid).is_err());
+    }
+}
@@ -0,0 +1,371 @@
+use medi_token::near_payment_escrow::{InvoiceStatus, ProviderPaymentEscrow, MAX_INVOICE_PAYMENTS};
+use medi_token::MediTokenNEP141;
+use near_contract_standards::fungible_token::core::FungibleTokenCore;
+use near_contract_standards::fungible_token::receiver::FungibleTokenReceiver;
+use near_contract_standards::fungible_token::FungibleTokenResolver;
+use near_contract_standards::storage_management::StorageManagement;
+use near_sdk::json_types::U128;
+use near_sdk::mock::MockAction;
+use near_sdk::serde_json::{self, json, Value};
+use near_sdk::test_utils::{accounts, get_created_receipts, get_logs, VMContextBuilder};
+use near_sdk::{testing_env, AccountId, NearToken, PromiseOrValue, PromiseResult, RuntimeFeesConfig};
+use std::panic::{self, AssertUnwindSafe};
+
+#[cfg(test)]
+mod near_payment_escrow_tests {
+    use super::*;
+
+    const TOTAL_SUPPLY: u128 = 1_000_000;
+    /// More than an invoice's storage costs; the rest is refunded
+    const INVOICE_DEPOSIT: NearToken = NearToken::from_millinear(100);
+
+    fn token_id() -> AccountId {
+        "meditoken.near".parse().unwrap()
+    }
+
+    fn escrow_id() -> AccountId {
+        "escrow.near".parse().unwrap()
+    }
+
+    fn patient() -> AccountId {
+        accounts(1)
+    }
+
+    fn provider() -> AccountId {
+        accounts(2)
+    }
+
+    fn context(current: AccountId, predecessor: AccountId, deposit: NearToken) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .current_account_id(current)
+            .predecessor_account_id(predecessor.clone())
+            .signer_account_id(predecessor)
+            .attached_deposit(deposit);
+        builder
+    }
+
+    fn call_token(predecessor: AccountId, deposit: NearToken) {
+        testing_env!(context(token_id(), predecessor, deposit).build());
+    }
+
+    fn call_escrow(predecessor: AccountId) {
+        call_escrow_with_deposit(predecessor, NearToken::from_yoctonear(0));
+    }
+
+    fn call_escrow_with_deposit(predecessor: AccountId, deposit: NearToken) {
+        testing_env!(context(escrow_id(), predecessor, deposit).build());
+    }
+
+    /// Token with the whole supply held by the patient, and an escrow registered with it
+    fn setup() -> (MediTokenNEP141, ProviderPaymentEscrow) {
+        call_token(patient(), NearToken::from_yoctonear(0));
+        let mut token = MediTokenNEP141::new(patient(), U128(TOTAL_SUPPLY), None);
+        call_token(escrow_id(), token.storage_balance_bounds().min);
+        token.storage_deposit(None, None);
+
+        call_escrow(provider());
+        let escrow = ProviderPaymentEscrow::new(token_id());
+        (token, escrow)
+    }
+
+    fn create_invoice(escrow: &mut ProviderPaymentEscrow, invoice_id: &str, amount: u128, patient_id: Option<AccountId>) {
+        call_escrow_with_deposit(provider(), INVOICE_DEPOSIT);
+        escrow.create_invoice(invoice_id.to_string(), U128(amount), patient_id, Some("Consultation".to_string()));
+    }
+
+    fn invoice_msg(invoice_id: &str) -> String {
+        json!({ "invoice_id": invoice_id }).to_string()
+    }
+
+    /// Method name and JSON arguments of the single function call in a receipt
+    fn function_call(actions: &[MockAction]) -> (String, Value) {
+        actions
+            .iter()
+            .find_map(|action| match action {
+                MockAction::FunctionCallWeight { method_name, args, .. } => Some((
+                    String::from_utf8(method_name.clone()).unwrap(),
+                    serde_json::from_slice(args).unwrap(),
+                )),
+                _ => None,
+            })
+            .expect("receipt has a function call")
+    }
+
+    /// Runs ft_transfer_call -> ft_on_transfer -> ft_resolve_transfer, returning the amount
+    /// the token resolved as used by the escrow
+    fn pay(
+        token: &mut MediTokenNEP141,
+        escrow: &mut ProviderPaymentEscrow,
+        payer: AccountId,
+        amount: u128,
+        msg: String,
+    ) -> u128 {
+        call_token(payer.clone(), NearToken::from_yoctonear(1));
+        let _ = token.ft_transfer_call(escrow_id(), U128(amount), None, msg);
+
+        let receipts = get_created_receipts();
+        assert_eq!(receipts[0].receiver_id, escrow_id());
+        let (method, args) = function_call(&receipts[0].actions);
+        assert_eq!(method, "ft_on_transfer");
+        assert_eq!(receipts[1].receiver_id, token_id());
+        let (callback, _) = function_call(&receipts[1].actions);
+        assert_eq!(callback, "ft_resolve_transfer");
+
+        call_escrow(token_id());
+        let sender_id: AccountId = serde_json::from_value(args["sender_id"].clone()).unwrap();
+        let sent: U128 = serde_json::from_value(args["amount"].clone()).unwrap();
+        let msg = args["msg"].as_str().unwrap().to_string();
+        let outcome = panic::catch_unwind(AssertUnwindSafe(|| escrow.ft_on_transfer(sender_id, sent, msg)));
+        let result = match outcome {
+            Ok(PromiseOrValue::Value(unused)) => PromiseResult::Successful(serde_json::to_vec(&unused).unwrap()),
+            Ok(PromiseOrValue::Promise(_)) => panic!("escrow returns its refund directly"),
+            Err(_) => PromiseResult::Failed,
+        };
+
+        testing_env!(
+            context(token_id(), token_id(), NearToken::from_yoctonear(0)).build(),
+            near_sdk::test_vm_config(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![result],
+        );
+        token.ft_resolve_transfer(payer, escrow_id(), U128(amount)).0
+    }
+
+    #[test]
+    fn test_exact_payment() {
+        let (mut token, mut escrow) = setup();
+        create_invoice(&mut escrow, "INV-1", 100, Some(patient()));
+
+        let used = pay(&mut token, &mut escrow, patient(), 100, invoice_msg("INV-1"));
+
+        assert_eq!(used, 100);
+        assert_eq!(token.ft_balance_of(patient()).0, TOTAL_SUPPLY - 100);
+        assert_eq!(token.ft_balance_of(escrow_id()).0, 100);
+        let invoice = escrow.get_invoice("INV-1".to_string()).unwrap();
+        assert_eq!(invoice.status, InvoiceStatus::Paid);
+        assert_eq!(invoice.paid, U128(100));
+        assert_eq!(invoice.payments[0].payer_id, patient());
+    }
+
+    #[test]
+    fn test_overpayment_is_refunded() {
+        let (mut token, mut escrow) = setup();
+        create_invoice(&mut escrow, "INV-1", 100, None);
+
+        let used = pay(&mut token, &mut escrow, patient(), 150, invoice_msg("INV-1"));
+
+        assert_eq!(used, 100);
+        assert_eq!(token.ft_balance_of(patient()).0, TOTAL_SUPPLY - 100);
+        assert_eq!(token.ft_balance_of(escrow_id()).0, 100);
+        assert_eq!(escrow.get_invoice("INV-1".to_string()).unwrap().paid, U128(100));
+        // The refund is logged as a transfer back to the payer
+        assert!(get_logs()[0].contains("\"event\":\"ft_transfer\""));
+    }
+
+    #[test]
+    fn test_partial_payments() {
+        let (mut token, mut escrow) = setup();
+        create_invoice(&mut escrow, "INV-1", 100, None);
+
+        assert_eq!(pay(&mut token, &mut escrow, patient(), 40, invoice_msg("INV-1")), 40);
+        let invoice = escrow.get_invoice("INV-1".to_string()).unwrap();
+        assert_eq!(invoice.status, InvoiceStatus::Open);
+        assert_eq!(invoice.paid, U128(40));
+
+        assert_eq!(pay(&mut token, &mut escrow, patient(), 100, invoice_msg("INV-1")), 60);
+        let invoice = escrow.get_invoice("INV-1".to_string()).unwrap();
+        assert_eq!(invoice.status, InvoiceStatus::Paid);
+        assert_eq!(invoice.payments.len(), 2);
+        assert_eq!(token.ft_balance_of(escrow_id()).0, 100);
+    }
+
+    #[test]
+    fn test_failed_payments_are_refunded_in_full() {
+        let (mut token, mut escrow) = setup();
+        create_invoice(&mut escrow, "INV-1", 100, Some(accounts(3)));
+        create_invoice(&mut escrow, "INV-2", 100, None);
+        call_escrow(provider());
+        escrow.cancel_invoice("INV-2".to_string());
+
+        for msg in [
+            "not json".to_string(),
+            invoice_msg("INV-404"),
+            // Billed to a different patient
+            invoice_msg("INV-1"),
+            // Cancelled
+            invoice_msg("INV-2"),
+        ] {
+            assert_eq!(pay(&mut token, &mut escrow, patient(), 100, msg), 0);
+        }
+
+        assert_eq!(token.ft_balance_of(patient()).0, TOTAL_SUPPLY);
+        assert_eq!(token.ft_balance_of(escrow_id()).0, 0);
+        assert_eq!(escrow.get_invoice("INV-1".to_string()).unwrap().paid, U128(0));
+    }
+
+    #[test]
+    fn test_paid_invoice_rejects_further_payment() {
+        let (mut token, mut escrow) = setup();
+        create_invoice(&mut escrow, "INV-1", 100, None);
+        pay(&mut token, &mut escrow, patient(), 100, invoice_msg("INV-1"));
+
+        assert_eq!(pay(&mut token, &mut escrow, patient(), 10, invoice_msg("INV-1")), 0);
+        assert_eq!(token.ft_balance_of(escrow_id()).0, 100);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only payments in the escrow token are accepted")]
+    fn test_rejects_other_tokens() {
+        let (_, mut escrow) = setup();
+        create_invoice(&mut escrow, "INV-1", 100, None);
+
+        call_escrow(accounts(4));
+        let _ = escrow.ft_on_transfer(patient(), U128(100), invoice_msg("INV-1"));
+    }
+
+    #[test]
+    fn test_payments_per_invoice_are_capped() {
+        let (mut token, mut escrow) = setup();
+        create_invoice(&mut escrow, "INV-1", 100, None);
+        for _ in 1..MAX_INVOICE_PAYMENTS {
+            assert_eq!(pay(&mut token, &mut escrow, patient(), 1, invoice_msg("INV-1")), 1);
+        }
+
+        // The last payment the invoice can record must settle it
+        assert_eq!(pay(&mut token, &mut escrow, patient(), 1, invoice_msg("INV-1")), 0);
+        assert_eq!(pay(&mut token, &mut escrow, patient(), 95, invoice_msg("INV-1")), 91);
+
+        let invoice = escrow.get_invoice("INV-1".to_string()).unwrap();
+        assert_eq!(invoice.status, InvoiceStatus::Paid);
+        assert_eq!(invoice.payments.len(), MAX_INVOICE_PAYMENTS);
+        assert_eq!(token.ft_balance_of(escrow_id()).0, 100);
+    }
+
+    #[test]
+    fn test_create_invoice_refunds_excess_deposit() {
+        let (_, mut escrow) = setup();
+        create_invoice(&mut escrow, "INV-1", 100, None);
+
+        let receipts = get_created_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].receiver_id, provider());
+        match &receipts[0].actions[..] {
+            [MockAction::Transfer { deposit, .. }] => {
+                assert!(*deposit > NearToken::from_yoctonear(0) && *deposit < INVOICE_DEPOSIT);
+            }
+            other => panic!("Expected a refund transfer, got {:?}", other),
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "to cover the invoice storage")]
+    fn test_create_invoice_requires_storage_deposit() {
+        let (_, mut escrow) = setup();
+        call_escrow(provider());
+        escrow.create_invoice("INV-1".to_string(), U128(100), None, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invoice already exists")]
+    fn test_duplicate_invoice() {
+        let (_, mut escrow) = setup();
+        create_invoice(&mut escrow, "INV-1", 100, None);
+        create_invoice(&mut escrow, "INV-1", 200, None);
+    }
+
+    #[test]
+    fn test_release_payment() {
+        let (mut token, mut escrow) = setup();
+        create_invoice(&mut escrow, "INV-1", 100, None);
+        pay(&mut token, &mut escrow, patient(), 100, invoice_msg("INV-1"));
+
+        call_escrow(provider());
+        let _ = escrow.release_payment("INV-1".to_string());
+        let receipts = get_created_receipts();
+        assert_eq!(receipts[0].receiver_id, token_id());
+        let (method, args) = function_call(&receipts[0].actions);
+        assert_eq!(method, "ft_transfer");
+        assert_eq!(args["receiver_id"], provider().to_string());
+        assert_eq!(args["amount"], "100");
+        assert_eq!(escrow.get_invoice("INV-1".to_string()).unwrap().status, InvoiceStatus::Released);
+
+        testing_env!(
+            context(escrow_id(), escrow_id(), NearToken::from_yoctonear(0)).build(),
+            near_sdk::test_vm_config(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Successful(Vec::new())],
+        );
+        assert!(escrow.on_payment_released("INV-1".to_string()));
+        assert!(get_logs()[0].contains("\"event\":\"payment_released\""));
+    }
+
+    #[test]
+    fn test_failed_release_reopens_payout() {
+        let (mut token, mut escrow) = setup();
+        create_invoice(&mut escrow, "INV-1", 100, None);
+        pay(&mut token, &mut escrow, patient(), 100, invoice_msg("INV-1"));
+
+        call_escrow(provider());
+        let _ = escrow.release_payment("INV-1".to_string());
+        testing_env!(
+            context(escrow_id(), escrow_id(), NearToken::from_yoctonear(0)).build(),
+            near_sdk::test_vm_config(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Failed],
+        );
+
+        assert!(!escrow.on_payment_released("INV-1".to_string()));
+        assert_eq!(escrow.get_invoice("INV-1".to_string()).unwrap().status, InvoiceStatus::Paid);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the invoice provider can do this")]
+    fn test_release_requires_provider() {
+        let (mut token, mut escrow) = setup();
+        create_invoice(&mut escrow, "INV-1", 100, None);
+        pay(&mut token, &mut escrow, patient(), 100, invoice_msg("INV-1"));
+
+        call_escrow(patient());
+        let _ = escrow.release_payment("INV-1".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Invoice is not fully paid")]
+    fn test_release_requires_full_payment() {
+        let (mut token, mut escrow) = setup();
+        create_invoice(&mut escrow, "INV-1", 100, None);
+        pay(&mut token, &mut escrow, patient(), 50, invoice_msg("INV-1"));
+
+        call_escrow(provider());
+        let _ = escrow.release_payment("INV-1".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot cancel an invoice that has received payments")]
+    fn test_cancel_partially_paid_invoice() {
+        let (mut token, mut escrow) = setup();
+        create_invoice(&mut escrow, "INV-1", 100, None);
+        pay(&mut token, &mut escrow, patient(), 50, invoice_msg("INV-1"));
+
+        call_escrow(provider());
+        escrow.cancel_invoice("INV-1".to_string());
+    }
+
+    #[test]
+    fn test_invoices_for_provider() {
+        let (_, mut escrow) = setup();
+        create_invoice(&mut escrow, "INV-1", 100, None);
+        create_invoice(&mut escrow, "INV-2", 200, None);
+        call_escrow_with_deposit(accounts(3), INVOICE_DEPOSIT);
+        escrow.create_invoice("OTHER-1".to_string(), U128(300), None, None);
+
+        let invoices = escrow.invoices_for_provider(provider(), None, None);
+        assert_eq!(invoices.len(), 2);
+        assert!(invoices.iter().all(|invoice| invoice.provider_id == provider()));
+        assert_eq!(escrow.invoices_for_provider(provider(), Some(1), Some(5)), vec![invoices[1].clone()]);
+    }
+}
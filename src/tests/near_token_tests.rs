@@ -0,0 +1,954 @@
+use medi_token::near_token::{
+    MediTokenNEP141V1, SponsoredAccount, SponsorshipConfig, COMPLIANCE_ROLE,
+    DEFAULT_ADMIN_ROLE, MAX_ICON_LENGTH, MINTER_ROLE, PATIENT_ROLE, PAUSER_ROLE, PROVIDER_ROLE, SNAPSHOT_ROLE,
+    SPONSOR_ROLE, STATE_VERSION, STATE_VERSION_KEY,
+};
+use medi_token::MediTokenNEP141;
+use near_sdk::json_types::{Base64VecU8, U128};
+use near_sdk::mock::MockAction;
+use near_sdk::test_utils::{accounts, get_created_receipts, get_logs, VMContextBuilder};
+use near_contract_standards::fungible_token::core::FungibleTokenCore;
+use near_contract_standards::fungible_token::metadata::{FungibleTokenMetadata, FungibleTokenMetadataProvider, FT_METADATA_SPEC};
+use near_contract_standards::fungible_token::FungibleToken;
+use near_contract_standards::storage_management::StorageManagement;
+use near_sdk::{env, testing_env, AccountId, NearToken};
+
+#[cfg(test)]
+mod near_token_tests {
+    use super::*;
+
+    const TOTAL_SUPPLY: u128 = 1_000_000_000_000_000_000_000_000;
+
+    fn set_caller(account_id: AccountId) {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .current_account_id(accounts(0))
+            .predecessor_account_id(account_id.clone())
+            .signer_account_id(account_id);
+        testing_env!(builder.build());
+    }
+
+    fn set_caller_with_deposit(account_id: AccountId, deposit: NearToken) {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .current_account_id(accounts(0))
+            .predecessor_account_id(account_id.clone())
+            .signer_account_id(account_id)
+            .attached_deposit(deposit);
+        testing_env!(builder.build());
+    }
+
+    fn register(contract: &mut MediTokenNEP141, account_id: AccountId) {
+        set_caller_with_deposit(account_id.clone(), contract.storage_balance_bounds().min);
+        contract.storage_deposit(Some(account_id), None);
+    }
+
+    fn setup() -> MediTokenNEP141 {
+        set_caller(accounts(1));
+        let contract = MediTokenNEP141::new(accounts(1), U128(TOTAL_SUPPLY), None);
+        // Start each test with the init logs cleared
+        set_caller(accounts(1));
+        contract
+    }
+
+    #[test]
+    fn test_owner_initial_roles() {
+        let contract = setup();
+
+        assert!(contract.has_role(DEFAULT_ADMIN_ROLE.to_string(), accounts(1)));
+        assert!(contract.has_role(MINTER_ROLE.to_string(), accounts(1)));
+        assert!(contract.has_role(SNAPSHOT_ROLE.to_string(), accounts(1)));
+        assert!(!contract.has_role(PROVIDER_ROLE.to_string(), accounts(1)));
+        assert_eq!(contract.get_role_admin(PROVIDER_ROLE.to_string()), DEFAULT_ADMIN_ROLE);
+    }
+
+    #[test]
+    fn test_grant_and_revoke_role() {
+        let mut contract = setup();
+
+        contract.grant_role(PROVIDER_ROLE.to_string(), accounts(2));
+        assert!(contract.has_role(PROVIDER_ROLE.to_string(), accounts(2)));
+        assert!(get_logs()[0].starts_with("EVENT_JSON:"));
+        assert!(get_logs()[0].contains("\"event\":\"role_granted\""));
+
+        contract.revoke_role(PROVIDER_ROLE.to_string(), accounts(2));
+        assert!(!contract.has_role(PROVIDER_ROLE.to_string(), accounts(2)));
+        assert!(get_logs()[1].contains("\"event\":\"role_revoked\""));
+    }
+
+    #[test]
+    #[should_panic(expected = "is missing role admin")]
+    fn test_grant_role_requires_admin() {
+        let mut contract = setup();
+
+        set_caller(accounts(2));
+        contract.grant_role(PROVIDER_ROLE.to_string(), accounts(2));
+    }
+
+    #[test]
+    fn test_renounce_role() {
+        let mut contract = setup();
+        contract.grant_role(PROVIDER_ROLE.to_string(), accounts(2));
+
+        set_caller(accounts(2));
+        contract.renounce_role(PROVIDER_ROLE.to_string());
+        assert!(!contract.has_role(PROVIDER_ROLE.to_string(), accounts(2)));
+    }
+
+    #[test]
+    fn test_role_admin_hierarchy() {
+        let mut contract = setup();
+        contract.set_role_admin(PATIENT_ROLE.to_string(), PROVIDER_ROLE.to_string());
+        contract.grant_role(PROVIDER_ROLE.to_string(), accounts(2));
+
+        set_caller(accounts(2));
+        contract.grant_role(PATIENT_ROLE.to_string(), accounts(3));
+        assert!(contract.has_role(PATIENT_ROLE.to_string(), accounts(3)));
+        assert_eq!(contract.get_role_admin(PATIENT_ROLE.to_string()), PROVIDER_ROLE);
+    }
+
+    #[test]
+    fn test_pause_and_unpause() {
+        let mut contract = setup();
+        assert!(!contract.is_paused());
+
+        contract.pause();
+        assert!(contract.is_paused());
+        assert!(get_logs()[0].contains("\"event\":\"paused\""));
+
+        contract.unpause();
+        assert!(!contract.is_paused());
+        assert!(get_logs()[1].contains("\"event\":\"unpaused\""));
+    }
+
+    #[test]
+    #[should_panic(expected = "Token transfers are paused")]
+    fn test_pause_blocks_ft_transfer() {
+        let mut contract = setup();
+        register(&mut contract, accounts(2));
+
+        set_caller(accounts(1));
+        contract.pause();
+
+        set_caller_with_deposit(accounts(1), NearToken::from_yoctonear(1));
+        contract.ft_transfer(accounts(2), U128(100), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Token transfers are paused")]
+    fn test_pause_blocks_ft_transfer_call() {
+        let mut contract = setup();
+        register(&mut contract, accounts(2));
+
+        set_caller(accounts(1));
+        contract.pause();
+
+        set_caller_with_deposit(accounts(1), NearToken::from_yoctonear(1));
+        let _ = contract.ft_transfer_call(accounts(2), U128(100), None, String::new());
+    }
+
+    #[test]
+    fn test_unpause_resumes_ft_transfer() {
+        let mut contract = setup();
+        register(&mut contract, accounts(2));
+
+        set_caller(accounts(1));
+        contract.pause();
+        contract.unpause();
+
+        set_caller_with_deposit(accounts(1), NearToken::from_yoctonear(1));
+        contract.ft_transfer(accounts(2), U128(100), None);
+        assert_eq!(contract.ft_balance_of(accounts(2)).0, 100);
+    }
+
+    #[test]
+    #[should_panic(expected = "is missing role pauser")]
+    fn test_pause_requires_pauser_role() {
+        let mut contract = setup();
+        assert!(!contract.has_role(PAUSER_ROLE.to_string(), accounts(2)));
+
+        set_caller(accounts(2));
+        contract.pause();
+    }
+
+    #[test]
+    fn test_new_stores_owner_and_logs_initial_mint() {
+        set_caller(accounts(1));
+        let contract = MediTokenNEP141::new(accounts(1), U128(TOTAL_SUPPLY), None);
+
+        assert_eq!(contract.get_owner(), accounts(1));
+        let logs = get_logs();
+        assert_eq!(logs.len(), 1);
+        assert!(logs[0].contains("\"standard\":\"nep141\""));
+        assert!(logs[0].contains("\"event\":\"ft_mint\""));
+        assert!(logs[0].contains(&format!("\"amount\":\"{}\"", TOTAL_SUPPLY)));
+    }
+
+    #[test]
+    fn test_ft_mint() {
+        let mut contract = setup();
+        register(&mut contract, accounts(2));
+
+        set_caller(accounts(1));
+        contract.ft_mint(accounts(2), U128(500), Some("Research grant".to_string()));
+
+        assert_eq!(contract.ft_balance_of(accounts(2)).0, 500);
+        assert_eq!(contract.ft_total_supply().0, TOTAL_SUPPLY + 500);
+        let logs = get_logs();
+        assert!(logs[0].starts_with("EVENT_JSON:"));
+        assert!(logs[0].contains("\"event\":\"ft_mint\""));
+        assert!(logs[0].contains(&format!("\"owner_id\":\"{}\"", accounts(2))));
+        assert!(logs[0].contains("\"memo\":\"Research grant\""));
+    }
+
+    #[test]
+    fn test_granted_minter_can_mint() {
+        let mut contract = setup();
+        register(&mut contract, accounts(2));
+
+        set_caller(accounts(1));
+        contract.grant_role(MINTER_ROLE.to_string(), accounts(3));
+
+        set_caller(accounts(3));
+        contract.ft_mint(accounts(2), U128(10), None);
+        assert_eq!(contract.ft_balance_of(accounts(2)).0, 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "is missing role minter")]
+    fn test_ft_mint_requires_minter_role() {
+        let mut contract = setup();
+        register(&mut contract, accounts(2));
+
+        set_caller(accounts(2));
+        contract.ft_mint(accounts(2), U128(500), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not registered")]
+    fn test_ft_mint_requires_registered_receiver() {
+        let mut contract = setup();
+        contract.ft_mint(accounts(2), U128(500), None);
+    }
+
+    #[test]
+    fn test_ft_burn() {
+        let mut contract = setup();
+
+        contract.ft_burn(accounts(1), U128(400), None);
+
+        assert_eq!(contract.ft_balance_of(accounts(1)).0, TOTAL_SUPPLY - 400);
+        assert_eq!(contract.ft_total_supply().0, TOTAL_SUPPLY - 400);
+        let logs = get_logs();
+        assert!(logs[0].contains("\"event\":\"ft_burn\""));
+        assert!(logs[0].contains("\"amount\":\"400\""));
+        assert!(!logs[0].contains("memo"));
+    }
+
+    #[test]
+    #[should_panic(expected = "doesn't have enough balance")]
+    fn test_ft_burn_exceeding_balance() {
+        let mut contract = setup();
+        register(&mut contract, accounts(2));
+
+        set_caller(accounts(1));
+        contract.ft_burn(accounts(2), U128(1), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "is missing role minter")]
+    fn test_ft_burn_requires_minter_role() {
+        let mut contract = setup();
+
+        set_caller(accounts(2));
+        contract.ft_burn(accounts(1), U128(1), None);
+    }
+
+    /// Writes state in the layout of the original testnet deployment
+    fn write_v1_state() {
+        set_caller(accounts(0));
+        let mut token = FungibleToken::new(b"t".to_vec());
+        token.internal_register_account(&accounts(1));
+        token.internal_deposit(&accounts(1), TOTAL_SUPPLY - 250);
+        token.internal_register_account(&accounts(2));
+        token.internal_deposit(&accounts(2), 250);
+        let metadata = FungibleTokenMetadata {
+            spec: FT_METADATA_SPEC.to_string(),
+            name: "MediToken".to_string(),
+            symbol: "MEDT".to_string(),
+            icon: None,
+            reference: None,
+            reference_hash: None,
+            decimals: 18,
+        };
+        env::state_write(&MediTokenNEP141V1 { token, metadata });
+    }
+
+    #[test]
+    fn test_new_writes_current_state_version() {
+        let contract = setup();
+        assert_eq!(contract.state_version(), STATE_VERSION);
+    }
+
+    #[test]
+    fn test_migrate_v1_state() {
+        write_v1_state();
+
+        let contract = MediTokenNEP141::migrate(Some(accounts(1)));
+
+        assert_eq!(contract.state_version(), STATE_VERSION);
+        assert_eq!(contract.get_owner(), accounts(1));
+        assert_eq!(contract.ft_total_supply().0, TOTAL_SUPPLY);
+        assert_eq!(contract.ft_balance_of(accounts(1)).0, TOTAL_SUPPLY - 250);
+        assert_eq!(contract.ft_balance_of(accounts(2)).0, 250);
+        assert_eq!(contract.ft_metadata().symbol, "MEDT");
+        assert!(contract.has_role(DEFAULT_ADMIN_ROLE.to_string(), accounts(1)));
+        assert!(contract.has_role(MINTER_ROLE.to_string(), accounts(1)));
+        assert!(contract.has_role(PAUSER_ROLE.to_string(), accounts(1)));
+        assert!(!contract.is_paused());
+        assert_eq!(contract.current_snapshot_id(), 0);
+        assert_eq!(contract.sponsorship_config(), SponsorshipConfig::default());
+        assert!(contract.sponsored_accounts(None, None).is_empty());
+        assert!(contract.frozen_accounts(None, None).is_empty());
+        assert!(get_logs()[0].contains("\"event\":\"state_migrated\""));
+        assert!(get_logs()[0].contains("\"from_version\":1"));
+    }
+
+    #[test]
+    fn test_migrate_v1_defaults_owner_to_contract_account() {
+        write_v1_state();
+        let contract = MediTokenNEP141::migrate(None);
+        assert_eq!(contract.get_owner(), accounts(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the contract account can migrate v1 state")]
+    fn test_migrate_v1_requires_contract_account() {
+        write_v1_state();
+        set_caller(accounts(1));
+        MediTokenNEP141::migrate(Some(accounts(1)));
+    }
+
+    #[test]
+    fn test_migrate_current_state_by_owner() {
+        let mut contract = setup();
+        contract.grant_role(PROVIDER_ROLE.to_string(), accounts(3));
+        env::state_write(&contract);
+
+        let migrated = MediTokenNEP141::migrate(None);
+        assert_eq!(migrated.get_owner(), accounts(1));
+        assert!(migrated.has_role(PROVIDER_ROLE.to_string(), accounts(3)));
+        assert_eq!(migrated.ft_total_supply().0, TOTAL_SUPPLY);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the owner can migrate the contract")]
+    fn test_migrate_current_state_requires_owner() {
+        let contract = setup();
+        env::state_write(&contract);
+
+        set_caller(accounts(2));
+        MediTokenNEP141::migrate(None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unsupported state version 3")]
+    fn test_migrate_rejects_unknown_state_version() {
+        write_v1_state();
+        env::storage_write(STATE_VERSION_KEY, &3u32.to_le_bytes());
+        MediTokenNEP141::migrate(None);
+    }
+
+    #[test]
+    fn test_upgrade_deploys_code_and_migrates() {
+        let mut contract = setup();
+        let code = b"\0asm new contract code".to_vec();
+
+        let mut builder = VMContextBuilder::new();
+        builder
+            .current_account_id(accounts(0))
+            .predecessor_account_id(accounts(1));
+        builder.context.input = code.clone();
+        testing_env!(builder.build());
+
+        let _ = contract.upgrade();
+
+        let receipts = get_created_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].receiver_id, accounts(0));
+        assert!(matches!(&receipts[0].actions[0], MockAction::DeployContract { code: deployed, .. } if *deployed == code));
+        assert!(matches!(
+            &receipts[0].actions[1],
+            MockAction::FunctionCallWeight { method_name, .. } if method_name == b"migrate"
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the owner can upgrade the contract")]
+    fn test_upgrade_requires_owner() {
+        let mut contract = setup();
+        set_caller(accounts(2));
+        let _ = contract.upgrade();
+    }
+
+    const ICON: &str = "data:image/svg+xml,%3Csvg xmlns='http://www.w3.org/2000/svg'/%3E";
+
+    fn metadata_with(icon: Option<&str>, reference: Option<&str>, reference_hash: Option<Vec<u8>>) -> FungibleTokenMetadata {
+        FungibleTokenMetadata {
+            spec: FT_METADATA_SPEC.to_string(),
+            name: "MediToken".to_string(),
+            symbol: "MEDT".to_string(),
+            icon: icon.map(str::to_string),
+            reference: reference.map(str::to_string),
+            reference_hash: reference_hash.map(Base64VecU8),
+            decimals: 18,
+        }
+    }
+
+    #[test]
+    fn test_new_with_custom_metadata() {
+        set_caller(accounts(1));
+        let mut metadata = metadata_with(Some(ICON), Some("ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"), Some(vec![7; 32]));
+        metadata.name = "MediToken Research".to_string();
+        metadata.decimals = 6;
+
+        let contract = MediTokenNEP141::new(accounts(1), U128(TOTAL_SUPPLY), Some(metadata));
+
+        let metadata = contract.ft_metadata();
+        assert_eq!(metadata.name, "MediToken Research");
+        assert_eq!(metadata.decimals, 6);
+        assert_eq!(metadata.icon.as_deref(), Some(ICON));
+        assert_eq!(metadata.reference_hash.unwrap().0, vec![7; 32]);
+    }
+
+    #[test]
+    fn test_new_defaults_metadata() {
+        let contract = setup();
+        let metadata = contract.ft_metadata();
+        assert_eq!(metadata.spec, FT_METADATA_SPEC);
+        assert_eq!(metadata.symbol, "MEDT");
+        assert_eq!(metadata.decimals, 18);
+        assert!(metadata.icon.is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Icon must be a data URL")]
+    fn test_new_rejects_invalid_metadata() {
+        set_caller(accounts(1));
+        MediTokenNEP141::new(
+            accounts(1),
+            U128(TOTAL_SUPPLY),
+            Some(metadata_with(Some("https://example.com/icon.png"), None, None)),
+        );
+    }
+
+    #[test]
+    fn test_set_metadata() {
+        let mut contract = setup();
+
+        contract.set_metadata(metadata_with(Some(ICON), Some("https://meditoken.example/ft.json"), Some(vec![1; 32])));
+
+        let metadata = contract.ft_metadata();
+        assert_eq!(metadata.icon.as_deref(), Some(ICON));
+        assert_eq!(metadata.reference.as_deref(), Some("https://meditoken.example/ft.json"));
+        assert!(get_logs()[0].contains("\"event\":\"metadata_updated\""));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the owner can update metadata")]
+    fn test_set_metadata_requires_owner() {
+        let mut contract = setup();
+        set_caller(accounts(2));
+        contract.set_metadata(metadata_with(Some(ICON), None, None));
+    }
+
+    #[test]
+    #[should_panic(expected = "Icon must be at most")]
+    fn test_set_metadata_rejects_large_icon() {
+        let mut contract = setup();
+        let icon = format!("data:image/svg+xml,{}", "a".repeat(MAX_ICON_LENGTH));
+        contract.set_metadata(metadata_with(Some(&icon), None, None));
+    }
+
+    #[test]
+    #[should_panic(expected = "Reference must be an https, ipfs or ar URL")]
+    fn test_set_metadata_rejects_invalid_reference() {
+        let mut contract = setup();
+        contract.set_metadata(metadata_with(None, Some("not a url"), Some(vec![1; 32])));
+    }
+
+    #[test]
+    #[should_panic(expected = "Reference and reference_hash must be set together")]
+    fn test_set_metadata_requires_reference_hash() {
+        let mut contract = setup();
+        contract.set_metadata(metadata_with(None, Some("https://meditoken.example/ft.json"), None));
+    }
+
+    #[test]
+    #[should_panic(expected = "Reference hash must be a base64-encoded sha256 hash")]
+    fn test_set_metadata_rejects_short_reference_hash() {
+        let mut contract = setup();
+        contract.set_metadata(metadata_with(None, Some("https://meditoken.example/ft.json"), Some(vec![1; 16])));
+    }
+
+    #[test]
+    #[should_panic(expected = "Decimals cannot be changed")]
+    fn test_set_metadata_keeps_decimals() {
+        let mut contract = setup();
+        let mut metadata = metadata_with(None, None, None);
+        metadata.decimals = 6;
+        contract.set_metadata(metadata);
+    }
+
+    fn transfer(contract: &mut MediTokenNEP141, from: AccountId, to: AccountId, amount: u128) {
+        set_caller_with_deposit(from, NearToken::from_yoctonear(1));
+        contract.ft_transfer(to, U128(amount), None);
+    }
+
+    /// accounts(2) is a funded sponsor; sponsorship is enabled with the given limits
+    fn setup_sponsorship(pool_max_accounts: u32, max_accounts_per_sponsor: u32) -> MediTokenNEP141 {
+        let mut contract = setup();
+        contract.grant_role(SPONSOR_ROLE.to_string(), accounts(2));
+        contract.set_sponsorship_config(SponsorshipConfig {
+            enabled: true,
+            pool_max_accounts,
+            max_accounts_per_sponsor,
+        });
+        register(&mut contract, accounts(2));
+        transfer(&mut contract, accounts(1), accounts(2), 1000);
+        contract
+    }
+
+    fn fund_sponsor(contract: &mut MediTokenNEP141, sponsor: AccountId, deposit: NearToken) {
+        set_caller_with_deposit(sponsor, deposit);
+        contract.sponsor_deposit();
+    }
+
+    #[test]
+    #[should_panic(expected = "is not registered")]
+    fn test_sponsorship_disabled_by_default() {
+        let mut contract = setup();
+        assert!(!contract.sponsorship_config().enabled);
+        contract.grant_role(SPONSOR_ROLE.to_string(), accounts(2));
+        register(&mut contract, accounts(2));
+        transfer(&mut contract, accounts(1), accounts(2), 1000);
+
+        transfer(&mut contract, accounts(2), accounts(3), 10);
+    }
+
+    #[test]
+    fn test_sponsor_deposit_pays_registration() {
+        let mut contract = setup_sponsorship(0, 10);
+        fund_sponsor(&mut contract, accounts(2), NearToken::from_near(1));
+        let storage_cost = contract.storage_balance_bounds().min.as_yoctonear();
+
+        transfer(&mut contract, accounts(2), accounts(3), 10);
+
+        assert_eq!(contract.ft_balance_of(accounts(3)).0, 10);
+        assert!(contract.storage_balance_of(accounts(3)).is_some());
+        assert_eq!(
+            contract.sponsored_account(accounts(3)),
+            Some(SponsoredAccount { account_id: accounts(3), sponsor_id: accounts(2), paid_by_pool: false })
+        );
+        assert_eq!(
+            contract.sponsor_balance(accounts(2)).0,
+            NearToken::from_near(1).as_yoctonear() - storage_cost
+        );
+        assert!(get_logs()[0].contains("\"event\":\"storage_sponsored\""));
+    }
+
+    #[test]
+    fn test_pool_pays_when_sponsor_has_no_deposit() {
+        let mut contract = setup_sponsorship(1, 10);
+
+        transfer(&mut contract, accounts(2), accounts(3), 10);
+
+        assert!(contract.sponsored_account(accounts(3)).unwrap().paid_by_pool);
+        assert_eq!(contract.sponsor_balance(accounts(2)).0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "No storage sponsorship available")]
+    fn test_pool_limit() {
+        let mut contract = setup_sponsorship(1, 10);
+
+        transfer(&mut contract, accounts(2), accounts(3), 10);
+        transfer(&mut contract, accounts(2), accounts(4), 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "Sponsor has reached its sponsored account limit")]
+    fn test_per_sponsor_limit() {
+        let mut contract = setup_sponsorship(0, 1);
+        fund_sponsor(&mut contract, accounts(2), NearToken::from_near(1));
+
+        transfer(&mut contract, accounts(2), accounts(3), 10);
+        transfer(&mut contract, accounts(2), accounts(4), 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not registered")]
+    fn test_non_sponsor_transfer_is_not_sponsored() {
+        let mut contract = setup_sponsorship(10, 10);
+        transfer(&mut contract, accounts(1), accounts(3), 10);
+    }
+
+    #[test]
+    fn test_sponsored_transfer_call_registers_receiver() {
+        let mut contract = setup_sponsorship(1, 10);
+
+        set_caller_with_deposit(accounts(2), NearToken::from_yoctonear(1));
+        let _ = contract.ft_transfer_call(accounts(3), U128(10), None, String::new());
+
+        assert!(contract.sponsored_account(accounts(3)).is_some());
+        assert_eq!(contract.ft_balance_of(accounts(3)).0, 10);
+    }
+
+    #[test]
+    fn test_sponsored_unregister_refunds_sponsor() {
+        let mut contract = setup_sponsorship(0, 10);
+        fund_sponsor(&mut contract, accounts(2), NearToken::from_near(1));
+        transfer(&mut contract, accounts(2), accounts(3), 10);
+
+        set_caller_with_deposit(accounts(3), NearToken::from_yoctonear(1));
+        assert!(contract.storage_unregister(Some(true)));
+
+        assert!(contract.sponsored_account(accounts(3)).is_none());
+        assert!(contract.storage_balance_of(accounts(3)).is_none());
+        assert_eq!(contract.ft_total_supply().0, TOTAL_SUPPLY - 10);
+        assert_eq!(contract.sponsor_balance(accounts(2)).0, NearToken::from_near(1).as_yoctonear());
+        // The storage deposit goes back to the sponsor's balance, not to the account
+        assert!(get_created_receipts().is_empty());
+        assert!(get_logs()[0].contains("\"event\":\"ft_burn\""));
+    }
+
+    #[test]
+    fn test_pool_slot_released_on_unregister() {
+        let mut contract = setup_sponsorship(1, 10);
+        transfer(&mut contract, accounts(2), accounts(3), 10);
+
+        set_caller_with_deposit(accounts(3), NearToken::from_yoctonear(1));
+        contract.storage_unregister(Some(true));
+
+        transfer(&mut contract, accounts(2), accounts(4), 10);
+        assert!(contract.sponsored_account(accounts(4)).unwrap().paid_by_pool);
+    }
+
+    #[test]
+    fn test_sponsored_accounts_view() {
+        let mut contract = setup_sponsorship(5, 10);
+        for receiver in [accounts(3), accounts(4), accounts(5)] {
+            transfer(&mut contract, accounts(2), receiver, 1);
+        }
+
+        let all = contract.sponsored_accounts(None, None);
+        assert_eq!(all.len(), 3);
+        let page = contract.sponsored_accounts(Some(1), Some(1));
+        assert_eq!(page, vec![all[1].clone()]);
+    }
+
+    #[test]
+    fn test_sponsor_withdraw() {
+        let mut contract = setup_sponsorship(0, 10);
+        fund_sponsor(&mut contract, accounts(2), NearToken::from_near(2));
+
+        set_caller_with_deposit(accounts(2), NearToken::from_yoctonear(1));
+        let remaining = contract.sponsor_withdraw(Some(U128(NearToken::from_near(1).as_yoctonear())));
+
+        assert_eq!(remaining.0, NearToken::from_near(1).as_yoctonear());
+        assert_eq!(get_created_receipts()[0].receiver_id, accounts(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "is missing role sponsor")]
+    fn test_sponsor_deposit_requires_role() {
+        let mut contract = setup();
+        fund_sponsor(&mut contract, accounts(3), NearToken::from_near(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the owner can configure sponsorship")]
+    fn test_set_sponsorship_config_requires_owner() {
+        let mut contract = setup();
+        set_caller(accounts(2));
+        contract.set_sponsorship_config(SponsorshipConfig::default());
+    }
+
+    #[test]
+    fn test_freeze_account() {
+        let mut contract = setup();
+        assert!(contract.has_role(COMPLIANCE_ROLE.to_string(), accounts(1)));
+
+        contract.freeze_account(accounts(2));
+        assert!(contract.is_frozen(accounts(2)));
+        assert_eq!(contract.frozen_accounts(None, None), vec![accounts(2)]);
+        assert!(get_logs()[0].contains("\"event\":\"account_frozen\""));
+
+        // Freezing twice does not log again
+        contract.freeze_account(accounts(2));
+        assert_eq!(get_logs().len(), 1);
+
+        contract.unfreeze_account(accounts(2));
+        assert!(!contract.is_frozen(accounts(2)));
+        assert!(get_logs()[1].contains("\"event\":\"account_unfrozen\""));
+    }
+
+    #[test]
+    #[should_panic(expected = "Account bob is frozen")]
+    fn test_frozen_sender_cannot_transfer() {
+        let mut contract = setup();
+        register(&mut contract, accounts(2));
+        set_caller(accounts(1));
+        contract.freeze_account(accounts(1));
+
+        transfer(&mut contract, accounts(1), accounts(2), 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account charlie is frozen")]
+    fn test_frozen_receiver_cannot_receive() {
+        let mut contract = setup();
+        register(&mut contract, accounts(2));
+        set_caller(accounts(1));
+        contract.freeze_account(accounts(2));
+
+        transfer(&mut contract, accounts(1), accounts(2), 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account charlie is frozen")]
+    fn test_frozen_receiver_blocks_ft_transfer_call() {
+        let mut contract = setup();
+        register(&mut contract, accounts(2));
+        set_caller(accounts(1));
+        contract.freeze_account(accounts(2));
+
+        set_caller_with_deposit(accounts(1), NearToken::from_yoctonear(1));
+        let _ = contract.ft_transfer_call(accounts(2), U128(10), None, String::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "Account charlie is frozen")]
+    fn test_frozen_account_cannot_receive_mint() {
+        let mut contract = setup();
+        register(&mut contract, accounts(2));
+        set_caller(accounts(1));
+        contract.freeze_account(accounts(2));
+
+        contract.ft_mint(accounts(2), U128(10), None);
+    }
+
+    #[test]
+    fn test_unfrozen_account_can_transfer() {
+        let mut contract = setup();
+        register(&mut contract, accounts(2));
+        set_caller(accounts(1));
+        contract.freeze_account(accounts(2));
+        contract.unfreeze_account(accounts(2));
+
+        transfer(&mut contract, accounts(1), accounts(2), 10);
+        assert_eq!(contract.ft_balance_of(accounts(2)).0, 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "is missing role compliance")]
+    fn test_freeze_requires_compliance_role() {
+        let mut contract = setup();
+        set_caller(accounts(2));
+        contract.freeze_account(accounts(3));
+    }
+
+    #[test]
+    fn test_frozen_accounts_pagination() {
+        let mut contract = setup();
+        for account in [accounts(2), accounts(3), accounts(4)] {
+            contract.freeze_account(account);
+        }
+        contract.unfreeze_account(accounts(3));
+
+        let all = contract.frozen_accounts(None, None);
+        assert_eq!(all.len(), 2);
+        assert!(all.contains(&accounts(2)) && all.contains(&accounts(4)));
+        assert_eq!(contract.frozen_accounts(Some(1), Some(10)), vec![all[1].clone()]);
+    }
+
+    fn set_caller_with_yocto(account_id: AccountId) {
+        set_caller_with_deposit(account_id, NearToken::from_yoctonear(1));
+    }
+
+    fn take_snapshot(contract: &mut MediTokenNEP141) -> u64 {
+        set_caller(accounts(1));
+        contract.snapshot()
+    }
+
+    #[test]
+    fn test_snapshot_emits_event() {
+        let mut contract = setup();
+        assert_eq!(contract.current_snapshot_id(), 0);
+
+        assert_eq!(contract.snapshot(), 1);
+        assert_eq!(contract.current_snapshot_id(), 1);
+        assert!(get_logs()[0].contains("\"event\":\"snapshot\""));
+        assert!(get_logs()[0].contains("\"id\":1"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Account charlie is missing role snapshot")]
+    fn test_snapshot_requires_role() {
+        let mut contract = setup();
+        set_caller(accounts(2));
+        contract.snapshot();
+    }
+
+    #[test]
+    fn test_balance_of_at_snapshot() {
+        let mut contract = setup();
+        register(&mut contract, accounts(2));
+        set_caller_with_yocto(accounts(1));
+        contract.ft_transfer(accounts(2), U128(100), None);
+
+        let first = take_snapshot(&mut contract);
+        set_caller_with_yocto(accounts(1));
+        contract.ft_transfer(accounts(2), U128(50), None);
+        set_caller_with_yocto(accounts(2));
+        contract.ft_transfer(accounts(1), U128(30), None);
+
+        let second = take_snapshot(&mut contract);
+        let third = take_snapshot(&mut contract);
+        set_caller_with_yocto(accounts(2));
+        contract.ft_transfer(accounts(1), U128(120), None);
+
+        let at = |id| contract.ft_balance_of_at(accounts(2), id).0;
+        assert_eq!(at(first), 100);
+        assert_eq!(at(second), 120);
+        assert_eq!(at(third), 120);
+        assert_eq!(contract.ft_balance_of(accounts(2)).0, 0);
+        assert_eq!(contract.ft_balance_of_at(accounts(1), first).0, TOTAL_SUPPLY - 100);
+        assert_eq!(contract.ft_balance_of_at(accounts(1), third).0, TOTAL_SUPPLY - 120);
+        // Accounts that never held tokens read as empty
+        assert_eq!(contract.ft_balance_of_at(accounts(4), second).0, 0);
+    }
+
+    #[test]
+    fn test_total_supply_at_snapshot() {
+        let mut contract = setup();
+        contract.ft_mint(accounts(1), U128(500), None);
+
+        let first = take_snapshot(&mut contract);
+        contract.ft_burn(accounts(1), U128(200), None);
+        contract.ft_burn(accounts(1), U128(100), None);
+        let second = take_snapshot(&mut contract);
+
+        assert_eq!(contract.ft_total_supply_at(first).0, TOTAL_SUPPLY + 500);
+        assert_eq!(contract.ft_total_supply_at(second).0, TOTAL_SUPPLY + 200);
+        assert_eq!(contract.ft_balance_of_at(accounts(1), first).0, TOTAL_SUPPLY + 500);
+    }
+
+    #[test]
+    fn test_checkpoints_are_only_written_after_a_snapshot() {
+        let mut contract = setup();
+        register(&mut contract, accounts(2));
+        set_caller_with_yocto(accounts(1));
+        let storage_before = env::storage_usage();
+        for _ in 0..5 {
+            contract.ft_transfer(accounts(2), U128(10), None);
+        }
+        assert_eq!(env::storage_usage(), storage_before);
+
+        // One checkpoint per account and one for the supply, however many changes follow
+        let snapshot_id = take_snapshot(&mut contract);
+        set_caller_with_yocto(accounts(1));
+        contract.ft_transfer(accounts(2), U128(10), None);
+        let storage_after_first = env::storage_usage();
+        contract.ft_transfer(accounts(2), U128(10), None);
+        contract.ft_mint(accounts(2), U128(10), None);
+        assert_eq!(env::storage_usage(), storage_after_first);
+        assert_eq!(contract.ft_balance_of_at(accounts(2), snapshot_id).0, 50);
+    }
+
+    #[test]
+    fn test_unregister_is_checkpointed() {
+        let mut contract = setup();
+        register(&mut contract, accounts(2));
+        set_caller_with_yocto(accounts(1));
+        contract.ft_transfer(accounts(2), U128(100), None);
+
+        let snapshot_id = take_snapshot(&mut contract);
+        set_caller_with_yocto(accounts(2));
+        contract.storage_unregister(Some(true));
+
+        assert_eq!(contract.ft_balance_of_at(accounts(2), snapshot_id).0, 100);
+        assert_eq!(contract.ft_total_supply_at(snapshot_id).0, TOTAL_SUPPLY);
+        assert_eq!(contract.ft_total_supply().0, TOTAL_SUPPLY - 100);
+    }
+
+    fn set_block(account_id: AccountId, block_height: u64) {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .current_account_id(accounts(0))
+            .predecessor_account_id(account_id.clone())
+            .signer_account_id(account_id)
+            .attached_deposit(NearToken::from_yoctonear(1))
+            .block_height(block_height);
+        testing_env!(builder.build());
+    }
+
+    #[test]
+    fn test_balance_at_block_height_uses_latest_snapshot() {
+        let mut contract = setup();
+        register(&mut contract, accounts(2));
+
+        set_block(accounts(1), 10);
+        contract.ft_transfer(accounts(2), U128(100), None);
+        contract.snapshot();
+        set_block(accounts(1), 20);
+        contract.ft_transfer(accounts(2), U128(50), None);
+        set_block(accounts(1), 30);
+        contract.snapshot();
+        set_block(accounts(1), 40);
+        contract.ft_transfer(accounts(2), U128(25), None);
+        contract.ft_mint(accounts(1), U128(500), None);
+
+        let at = |height| contract.ft_balance_of_at_block(accounts(2), height).0;
+        assert_eq!(at(10), 100);
+        // Between snapshots the earlier one answers
+        assert_eq!(at(25), 100);
+        assert_eq!(at(30), 150);
+        assert_eq!(at(40), 150);
+        assert_eq!(contract.ft_balance_of(accounts(2)).0, 175);
+        assert_eq!(contract.ft_total_supply_at_block(35).0, TOTAL_SUPPLY);
+    }
+
+    #[test]
+    #[should_panic(expected = "No snapshot was taken at or before this block height")]
+    fn test_balance_at_block_height_before_first_snapshot() {
+        let mut contract = setup();
+        set_block(accounts(1), 10);
+        contract.snapshot();
+        contract.ft_balance_of_at_block(accounts(1), 9);
+    }
+
+    #[test]
+    #[should_panic(expected = "Block height is in the future")]
+    fn test_total_supply_at_future_block_height() {
+        let mut contract = setup();
+        set_block(accounts(1), 10);
+        contract.snapshot();
+        contract.ft_total_supply_at_block(11);
+    }
+
+    #[test]
+    #[should_panic(expected = "Snapshot not found")]
+    fn test_balance_of_at_requires_taken_snapshot() {
+        let mut contract = setup();
+        take_snapshot(&mut contract);
+        contract.ft_balance_of_at(accounts(1), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Snapshot not found")]
+    fn test_total_supply_at_rejects_snapshot_zero() {
+        let contract = setup();
+        contract.ft_total_supply_at(0);
+    }
+}
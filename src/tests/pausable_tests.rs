@@ -0,0 +1,73 @@
+use medi_token::{MediToken, utils::Utils, MediTokenError, token::MediTokenEvent, U256};
+use medi_token::access_control::PAUSER_ROLE;
+
+#[cfg(test)]
+mod pausable_tests {
+    use super::*;
+
+    #[test]
+    fn test_pause_blocks_transfers() {
+        let owner = Utils::generate_random_address();
+        let recipient = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner.clone());
+
+        assert!(!token.is_paused());
+        token.pause(&owner).unwrap();
+        assert!(token.is_paused());
+        match token.get_events().last().unwrap() {
+            MediTokenEvent::Paused { account } => assert_eq!(account, &owner),
+            _ => panic!("Expected Paused event"),
+        }
+
+        let result = token.transfer(&owner, &recipient, U256::from(10));
+        assert!(matches!(result.unwrap_err(), MediTokenError::Paused));
+
+        token.approve(&owner, &recipient, U256::from(10)).unwrap();
+        let result = token.transfer_from(&recipient, &owner, &recipient, U256::from(10));
+        assert!(matches!(result.unwrap_err(), MediTokenError::Paused));
+        assert_eq!(token.balance_of(&recipient), U256::zero());
+        assert_eq!(token.allowance(&owner, &recipient), U256::from(10));
+    }
+
+    #[test]
+    fn test_unpause_resumes_transfers() {
+        let owner = Utils::generate_random_address();
+        let recipient = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner.clone());
+
+        token.pause(&owner).unwrap();
+        token.unpause(&owner).unwrap();
+        assert!(!token.is_paused());
+        assert!(matches!(
+            token.get_events().last().unwrap(),
+            MediTokenEvent::Unpaused { .. }
+        ));
+
+        token.transfer(&owner, &recipient, U256::from(10)).unwrap();
+        assert_eq!(token.balance_of(&recipient), U256::from(10));
+    }
+
+    #[test]
+    fn test_pause_requires_pauser_role() {
+        let owner = Utils::generate_random_address();
+        let operator = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner.clone());
+
+        let result = token.pause(&operator);
+        assert!(matches!(result.unwrap_err(), MediTokenError::MissingRole { .. }));
+
+        token.grant_role(&owner, PAUSER_ROLE, &operator).unwrap();
+        token.pause(&operator).unwrap();
+        assert!(token.is_paused());
+    }
+
+    #[test]
+    fn test_pause_state_transitions() {
+        let owner = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner.clone());
+
+        assert!(matches!(token.unpause(&owner).unwrap_err(), MediTokenError::NotPaused));
+        token.pause(&owner).unwrap();
+        assert!(matches!(token.pause(&owner).unwrap_err(), MediTokenError::Paused));
+    }
+}
@@ -0,0 +1,131 @@
+use medi_token::deployment::{get_deployed_addresses, DeployedContract, Network};
+use medi_token::registry::{
+    abi_hash, DeploymentRecord, DeploymentRegistry, RegistryChange, CONTRACT_VERSION, REGISTRY_FORMAT_VERSION,
+};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+#[cfg(test)]
+mod registry_tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("meditoken-{}-{}.json", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn local_deployment(address: &str) -> DeployedContract {
+        DeployedContract {
+            address: address.to_string(),
+            network: Network::Local,
+            transaction_hash: format!("0x{}", "ab".repeat(32)),
+            block_number: 7,
+            gas_used: 654321,
+            deployer: "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_seed_matches_known_addresses() {
+        let registry = DeploymentRegistry::seed();
+        assert_eq!(registry.records().len(), 6);
+        assert!(registry.latest(&Network::Local).is_none());
+
+        let sepolia = registry.get(&Network::Sepolia, CONTRACT_VERSION).unwrap();
+        assert_eq!(sepolia.address, "0x3B550adA770897B0b215e414e45354861357788c");
+        assert_eq!(sepolia.transaction_hash, None);
+        assert_eq!(sepolia.abi_hash, abi_hash());
+
+        assert_eq!(registry.addresses(), get_deployed_addresses());
+    }
+
+    #[test]
+    fn test_abi_hash_is_stable() {
+        let hash = abi_hash();
+        assert!(hash.starts_with("0x"));
+        assert_eq!(hash.len(), 66);
+        assert_eq!(hash, abi_hash());
+    }
+
+    #[test]
+    fn test_add_and_lookup() {
+        let mut registry = DeploymentRegistry::new();
+        let first = DeploymentRecord::from_deployed(&local_deployment("0x5fbdb2315678afecb367f032d93f642f64180aa3"), "1.0.0");
+        assert_eq!(registry.add(first.clone()), None);
+
+        let upgrade = DeploymentRecord::from_deployed(&local_deployment("0xe7f1725e7734ce288f8367e1bb143e90bb3f0512"), "1.10.0");
+        registry.add(upgrade.clone());
+        let minor = DeploymentRecord::from_deployed(&local_deployment("0x9fe46736679d2d9a65f0992f2272de9f3c7fa6e0"), "1.9.0");
+        registry.add(minor);
+
+        assert_eq!(registry.get(&Network::Local, "1.0.0"), Some(&first));
+        assert_eq!(registry.get(&Network::Local, "2.0.0"), None);
+        // Versions compare numerically, not as text
+        assert_eq!(registry.latest(&Network::Local), Some(&upgrade));
+        assert_eq!(registry.addresses()[&Network::Local], upgrade.address);
+
+        let redeploy = DeploymentRecord::from_deployed(&local_deployment("0xcf7ed3acca5a467e9e704c703e8d87f634fb0fc9"), "1.0.0");
+        assert_eq!(registry.add(redeploy), Some(first));
+        assert_eq!(registry.records().len(), 3);
+    }
+
+    #[test]
+    fn test_load_missing_file_returns_seed() {
+        let path = temp_path("missing-registry");
+        assert_eq!(DeploymentRegistry::load(&path).unwrap(), DeploymentRegistry::seed());
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let path = temp_path("registry-round-trip");
+        let mut registry = DeploymentRegistry::seed();
+        registry.add(DeploymentRecord::from_deployed(
+            &local_deployment("0x5fbdb2315678afecb367f032d93f642f64180aa3"),
+            CONTRACT_VERSION,
+        ));
+        registry.save(&path).unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        let loaded = DeploymentRegistry::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let json: serde_json::Value = serde_json::from_str(&contents).unwrap();
+        assert_eq!(json["version"], REGISTRY_FORMAT_VERSION);
+        assert_eq!(loaded, registry);
+        let local = loaded.latest(&Network::Local).unwrap();
+        assert_eq!(local.block_number, Some(7));
+        assert_eq!(local.deployer.as_deref(), Some("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266"));
+    }
+
+    #[test]
+    fn test_load_rejects_newer_format() {
+        let path = temp_path("registry-future");
+        fs::write(&path, r#"{ "version": 99, "deployments": [] }"#).unwrap();
+        let result = DeploymentRegistry::load(&path);
+        fs::remove_file(&path).unwrap();
+        assert!(result.unwrap_err().to_string().contains("registry format 99"));
+    }
+
+    #[test]
+    fn test_diff() {
+        let before = DeploymentRegistry::seed();
+        let mut after = before.clone();
+
+        let local = DeploymentRecord::from_deployed(&local_deployment("0x5fbdb2315678afecb367f032d93f642f64180aa3"), CONTRACT_VERSION);
+        after.add(local.clone());
+
+        let old_sepolia = before.get(&Network::Sepolia, CONTRACT_VERSION).unwrap().clone();
+        let mut new_sepolia = old_sepolia.clone();
+        new_sepolia.block_number = Some(5_000_000);
+        after.add(new_sepolia.clone());
+
+        assert!(before.diff(&before).is_empty());
+        let changes = before.diff(&after);
+        assert_eq!(changes.len(), 2);
+        assert!(changes.contains(&RegistryChange::Added(local.clone())));
+        assert!(changes.contains(&RegistryChange::Changed { before: old_sepolia, after: new_sepolia }));
+        assert!(after.diff(&before).contains(&RegistryChange::Removed(local)));
+    }
+}
@@ -0,0 +1,261 @@
+use k256::ecdsa::SigningKey;
+use rand::rngs::OsRng;
+use medi_token::{MediTokenError, U256};
+use medi_token::access_control::{MINTER_ROLE, PROVIDER_ROLE};
+use medi_token::clock::ManualClock;
+use medi_token::rewards::{
+    ActivityKind, Attestation, RewardEvent, RewardFunding, RewardProgram, Rewards, SignedAttestation,
+};
+use medi_token::transaction::signer_address;
+
+mod common;
+use common::fixtures::{account, Fixture, DAY, NOW};
+
+#[cfg(test)]
+mod rewards_tests {
+    use super::*;
+
+    struct Setup {
+        fx: Fixture,
+        rewards: Rewards<ManualClock>,
+        provider_key: SigningKey,
+        patient: String,
+    }
+
+    /// Rewards may mint, and `provider_key` signs for an account with the provider role
+    fn setup() -> Setup {
+        let mut fx = Fixture::new(10_000);
+        let rewards = Rewards::with_clock(account(), fx.clock.clone());
+        fx.token.grant_role(&fx.admin, MINTER_ROLE, &rewards.address).unwrap();
+
+        let provider_key = SigningKey::random(&mut OsRng);
+        fx.token.grant_role(&fx.admin, PROVIDER_ROLE, &signer_address(&provider_key)).unwrap();
+        Setup { fx, rewards, provider_key, patient: account() }
+    }
+
+    fn program(activity: ActivityKind, reward: u64, period_cap: u64, funding: RewardFunding) -> RewardProgram {
+        RewardProgram {
+            name: format!("{:?}", activity),
+            activity,
+            reward: U256::from(reward),
+            period: 30 * DAY,
+            period_cap: U256::from(period_cap),
+            funding,
+            active: true,
+        }
+    }
+
+    fn attest(s: &Setup, program_id: u64, streak_days: u32, nonce: u64) -> SignedAttestation {
+        Attestation { program_id, patient: s.patient.clone(), streak_days, timestamp: NOW, nonce }
+            .sign(&s.provider_key, &s.rewards.address)
+            .unwrap()
+    }
+
+    fn claim(s: &mut Setup, program_id: u64, streak_days: u32, nonce: u64) -> Result<U256, MediTokenError> {
+        let signed = attest(s, program_id, streak_days, nonce);
+        s.rewards.claim(&mut s.fx.token, &signed)
+    }
+
+    #[test]
+    fn test_checkup_reward_is_minted() {
+        let mut s = setup();
+        let id = s.rewards
+            .create_program(&s.fx.token, &s.fx.admin, program(ActivityKind::CheckupCompleted, 10, 100, RewardFunding::Mint))
+            .unwrap();
+
+        let signed = attest(&s, id, 0, 1);
+        assert_eq!(s.rewards.claim(&mut s.fx.token, &signed).unwrap(), U256::from(10));
+        assert_eq!(s.fx.token.balance_of(&s.patient), U256::from(10));
+        assert_eq!(s.fx.token.total_supply, U256::from(10_010));
+        assert!(s.rewards.is_nonce_used(&signer_address(&s.provider_key), 1));
+        assert_eq!(
+            s.rewards.get_events().last().unwrap(),
+            &RewardEvent::RewardPaid {
+                program_id: id,
+                patient: s.patient.clone(),
+                provider: signer_address(&s.provider_key),
+                nonce: 1,
+                amount: U256::from(10),
+                funding: RewardFunding::Mint,
+            }
+        );
+    }
+
+    #[test]
+    fn test_vaccination_reward_from_pool() {
+        let mut s = setup();
+        let id = s.rewards
+            .create_program(&s.fx.token, &s.fx.admin, program(ActivityKind::Vaccination, 15, 100, RewardFunding::Pool))
+            .unwrap();
+        let pool = s.rewards.address.clone();
+        s.fx.token.transfer(&s.fx.admin, &pool, U256::from(20)).unwrap();
+
+        claim(&mut s, id, 0, 1).unwrap();
+        assert_eq!(s.fx.token.balance_of(&s.patient), U256::from(15));
+        assert_eq!(s.fx.token.balance_of(&pool), U256::from(5));
+        assert_eq!(s.fx.token.total_supply, U256::from(10_000));
+
+        // An empty pool fails without consuming the nonce
+        let signed = attest(&s, id, 0, 2);
+        let result = s.rewards.claim(&mut s.fx.token, &signed);
+        assert!(matches!(result.unwrap_err(), MediTokenError::InsufficientBalance { .. }));
+        assert!(!s.rewards.is_nonce_used(&signer_address(&s.provider_key), 2));
+    }
+
+    #[test]
+    fn test_adherence_streak_rewards() {
+        let mut s = setup();
+        let activity = ActivityKind::MedicationAdherence { streak_days: 7 };
+        let id = s.rewards.create_program(&s.fx.token, &s.fx.admin, program(activity, 5, 100, RewardFunding::Mint)).unwrap();
+
+        assert_eq!(claim(&mut s, id, 15, 1).unwrap(), U256::from(10));
+        let result = claim(&mut s, id, 6, 2);
+        assert!(matches!(result.unwrap_err(), MediTokenError::RewardError { .. }));
+    }
+
+    #[test]
+    fn test_replayed_nonce_is_rejected() {
+        let mut s = setup();
+        let id = s.rewards
+            .create_program(&s.fx.token, &s.fx.admin, program(ActivityKind::CheckupCompleted, 10, 100, RewardFunding::Mint))
+            .unwrap();
+        let signed = attest(&s, id, 0, 7);
+        s.rewards.claim(&mut s.fx.token, &signed).unwrap();
+
+        let result = s.rewards.claim(&mut s.fx.token, &signed);
+        assert!(matches!(result.unwrap_err(), MediTokenError::NonceAlreadyUsed { nonce: 7, .. }));
+        assert_eq!(s.fx.token.balance_of(&s.patient), U256::from(10));
+    }
+
+    #[test]
+    fn test_period_cap_per_patient() {
+        let mut s = setup();
+        let id = s.rewards
+            .create_program(&s.fx.token, &s.fx.admin, program(ActivityKind::CheckupCompleted, 10, 25, RewardFunding::Mint))
+            .unwrap();
+
+        assert_eq!(claim(&mut s, id, 0, 1).unwrap(), U256::from(10));
+        assert_eq!(claim(&mut s, id, 0, 2).unwrap(), U256::from(10));
+        assert_eq!(claim(&mut s, id, 0, 3).unwrap(), U256::from(5));
+        assert_eq!(s.rewards.earned_this_period(id, &s.patient).unwrap(), U256::from(25));
+        let result = claim(&mut s, id, 0, 4);
+        assert!(matches!(result.unwrap_err(), MediTokenError::RewardCapReached { .. }));
+
+        // Other patients have their own cap, and the cap resets next period
+        let other = account();
+        let signed = Attestation { patient: other.clone(), ..attest(&s, id, 0, 5).attestation }
+            .sign(&s.provider_key, &s.rewards.address)
+            .unwrap();
+        assert_eq!(s.rewards.claim(&mut s.fx.token, &signed).unwrap(), U256::from(10));
+
+        s.fx.clock.advance(30 * DAY);
+        assert_eq!(s.rewards.earned_this_period(id, &s.patient).unwrap(), U256::zero());
+        let signed = Attestation { timestamp: NOW + 30 * DAY, ..attest(&s, id, 0, 6).attestation }
+            .sign(&s.provider_key, &s.rewards.address)
+            .unwrap();
+        assert_eq!(s.rewards.claim(&mut s.fx.token, &signed).unwrap(), U256::from(10));
+    }
+
+    #[test]
+    fn test_cap_follows_the_activity_period() {
+        let mut s = setup();
+        let id = s.rewards
+            .create_program(&s.fx.token, &s.fx.admin, program(ActivityKind::CheckupCompleted, 10, 10, RewardFunding::Mint))
+            .unwrap();
+
+        // Activities from the previous and the current period both pay, though claimed together
+        let earlier = Attestation { timestamp: NOW - 30 * DAY, ..attest(&s, id, 0, 1).attestation }
+            .sign(&s.provider_key, &s.rewards.address)
+            .unwrap();
+        assert_eq!(s.rewards.claim(&mut s.fx.token, &earlier).unwrap(), U256::from(10));
+        assert_eq!(claim(&mut s, id, 0, 2).unwrap(), U256::from(10));
+        assert_eq!(s.fx.token.balance_of(&s.patient), U256::from(20));
+
+        let result = claim(&mut s, id, 0, 3);
+        assert!(matches!(result.unwrap_err(), MediTokenError::RewardCapReached { .. }));
+    }
+
+    #[test]
+    fn test_attestation_must_be_signed_by_provider() {
+        let mut s = setup();
+        let id = s.rewards
+            .create_program(&s.fx.token, &s.fx.admin, program(ActivityKind::CheckupCompleted, 10, 100, RewardFunding::Mint))
+            .unwrap();
+
+        let stranger = SigningKey::random(&mut OsRng);
+        let signed = Attestation { program_id: id, patient: s.patient.clone(), streak_days: 0, timestamp: NOW, nonce: 1 }
+            .sign(&stranger, &s.rewards.address)
+            .unwrap();
+        let result = s.rewards.claim(&mut s.fx.token, &signed);
+        assert!(matches!(result.unwrap_err(), MediTokenError::RewardError { .. }));
+
+        // Changing the signed content changes the recovered signer
+        let mut tampered = attest(&s, id, 0, 1);
+        tampered.attestation.patient = account();
+        assert!(s.rewards.claim(&mut s.fx.token, &tampered).is_err());
+
+        // Signatures are bound to one rewards account
+        let signed = Attestation { program_id: id, patient: s.patient.clone(), streak_days: 0, timestamp: NOW, nonce: 1 }
+            .sign(&s.provider_key, &account())
+            .unwrap();
+        assert!(s.rewards.claim(&mut s.fx.token, &signed).is_err());
+
+        let mut truncated = attest(&s, id, 0, 1);
+        truncated.signature.pop();
+        assert!(s.rewards.claim(&mut s.fx.token, &truncated).is_err());
+        assert_eq!(s.fx.token.balance_of(&s.patient), U256::zero());
+    }
+
+    #[test]
+    fn test_future_stale_activity_and_inactive_program() {
+        let mut s = setup();
+        let mut settings = program(ActivityKind::CheckupCompleted, 10, 100, RewardFunding::Mint);
+        let id = s.rewards.create_program(&s.fx.token, &s.fx.admin, settings.clone()).unwrap();
+
+        let signed = Attestation { timestamp: NOW + 1, ..attest(&s, id, 0, 1).attestation }
+            .sign(&s.provider_key, &s.rewards.address)
+            .unwrap();
+        assert!(s.rewards.claim(&mut s.fx.token, &signed).is_err());
+
+        // Activities older than the claim window are stale
+        s.rewards.max_attestation_age = 7 * DAY;
+        let signed = Attestation { timestamp: NOW - 7 * DAY - 1, ..attest(&s, id, 0, 1).attestation }
+            .sign(&s.provider_key, &s.rewards.address)
+            .unwrap();
+        let result = s.rewards.claim(&mut s.fx.token, &signed);
+        assert!(matches!(result.unwrap_err(), MediTokenError::RewardError { .. }));
+        let signed = Attestation { timestamp: NOW - 7 * DAY, ..attest(&s, id, 0, 1).attestation }
+            .sign(&s.provider_key, &s.rewards.address)
+            .unwrap();
+        assert_eq!(s.rewards.claim(&mut s.fx.token, &signed).unwrap(), U256::from(10));
+
+        settings.active = false;
+        s.rewards.update_program(&s.fx.token, &s.fx.admin, id, settings).unwrap();
+        assert_eq!(s.rewards.get_events().last().unwrap(), &RewardEvent::ProgramUpdated { id });
+        let result = claim(&mut s, id, 0, 2);
+        assert!(matches!(result.unwrap_err(), MediTokenError::RewardError { .. }));
+
+        let result = claim(&mut s, 99, 0, 3);
+        assert!(matches!(result.unwrap_err(), MediTokenError::RewardProgramNotFound { id: 99 }));
+    }
+
+    #[test]
+    fn test_program_management_requires_admin() {
+        let mut s = setup();
+        let outsider = account();
+        let settings = program(ActivityKind::Vaccination, 10, 100, RewardFunding::Mint);
+
+        let result = s.rewards.create_program(&s.fx.token, &outsider, settings.clone());
+        assert!(matches!(result.unwrap_err(), MediTokenError::MissingRole { .. }));
+
+        let invalid = RewardProgram { period: 0, ..settings.clone() };
+        assert!(s.rewards.create_program(&s.fx.token, &s.fx.admin, invalid).is_err());
+        let invalid = program(ActivityKind::MedicationAdherence { streak_days: 0 }, 10, 100, RewardFunding::Mint);
+        assert!(s.rewards.create_program(&s.fx.token, &s.fx.admin, invalid).is_err());
+        let invalid = RewardProgram { reward: U256::zero(), ..settings };
+        assert!(matches!(
+            s.rewards.create_program(&s.fx.token, &s.fx.admin, invalid).unwrap_err(),
+            MediTokenError::InvalidAmount { .. }
+        ));
+    }
+}
@@ -0,0 +1,106 @@
+use medi_token::{MediToken, utils::Utils, MediTokenError, token::MediTokenEvent, U256};
+use medi_token::access_control::SNAPSHOT_ROLE;
+
+#[cfg(test)]
+mod snapshot_tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_ids() {
+        let owner = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner.clone());
+
+        assert_eq!(token.current_snapshot_id(), 0);
+        assert_eq!(token.snapshot(&owner).unwrap(), 1);
+        assert_eq!(token.snapshot(&owner).unwrap(), 2);
+        assert_eq!(token.current_snapshot_id(), 2);
+        assert_eq!(token.get_events().last().unwrap(), &MediTokenEvent::Snapshot { id: 2 });
+    }
+
+    #[test]
+    fn test_balances_at_snapshots() {
+        let owner = Utils::generate_random_address();
+        let alice = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner.clone());
+        token.transfer(&owner, &alice, U256::from(100)).unwrap();
+
+        let first = token.snapshot(&owner).unwrap();
+        token.transfer(&owner, &alice, U256::from(50)).unwrap();
+        token.transfer(&alice, &owner, U256::from(30)).unwrap();
+        let second = token.snapshot(&owner).unwrap();
+        let third = token.snapshot(&owner).unwrap();
+        token.transfer(&alice, &owner, U256::from(120)).unwrap();
+
+        assert_eq!(token.balance_of_at(&alice, first).unwrap(), U256::from(100));
+        assert_eq!(token.balance_of_at(&owner, first).unwrap(), U256::from(900));
+        assert_eq!(token.balance_of_at(&alice, second).unwrap(), U256::from(120));
+        assert_eq!(token.balance_of_at(&alice, third).unwrap(), U256::from(120));
+        assert_eq!(token.balance_of(&alice), U256::zero());
+    }
+
+    #[test]
+    fn test_unchanged_balance_uses_current_value() {
+        let owner = Utils::generate_random_address();
+        let bystander = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner.clone());
+        token.transfer(&owner, &bystander, U256::from(10)).unwrap();
+
+        let id = token.snapshot(&owner).unwrap();
+        assert_eq!(token.balance_of_at(&bystander, id).unwrap(), U256::from(10));
+        assert_eq!(token.balance_of_at(&Utils::generate_random_address(), id).unwrap(), U256::zero());
+    }
+
+    #[test]
+    fn test_total_supply_at_snapshots() {
+        let owner = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner.clone());
+
+        let before_mint = token.snapshot(&owner).unwrap();
+        token.mint(&owner, &owner, U256::from(500)).unwrap();
+        let before_burn = token.snapshot(&owner).unwrap();
+        token.burn(&owner, &owner, U256::from(200)).unwrap();
+
+        assert_eq!(token.total_supply_at(before_mint).unwrap(), U256::from(1000));
+        assert_eq!(token.total_supply_at(before_burn).unwrap(), U256::from(1500));
+        assert_eq!(token.total_supply, U256::from(1300));
+        assert_eq!(token.balance_of_at(&owner, before_mint).unwrap(), U256::from(1000));
+        assert_eq!(token.balance_of_at(&owner, before_burn).unwrap(), U256::from(1500));
+    }
+
+    #[test]
+    fn test_nonexistent_snapshot() {
+        let owner = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner.clone());
+
+        assert!(matches!(token.total_supply_at(0).unwrap_err(), MediTokenError::SnapshotNotFound { id: 0 }));
+        token.snapshot(&owner).unwrap();
+        let result = token.balance_of_at(&owner, 2);
+        assert!(matches!(result.unwrap_err(), MediTokenError::SnapshotNotFound { id: 2 }));
+    }
+
+    #[test]
+    fn test_snapshot_requires_role() {
+        let owner = Utils::generate_random_address();
+        let operator = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner.clone());
+
+        let result = token.snapshot(&operator);
+        assert!(matches!(result.unwrap_err(), MediTokenError::MissingRole { .. }));
+
+        token.grant_role(&owner, SNAPSHOT_ROLE, &operator).unwrap();
+        assert_eq!(token.snapshot(&operator).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_snapshots_survive_serialization() {
+        let owner = Utils::generate_random_address();
+        let alice = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner.clone());
+        let id = token.snapshot(&owner).unwrap();
+        token.transfer(&owner, &alice, U256::from(100)).unwrap();
+
+        let restored: MediToken = serde_json::from_str(&serde_json::to_string(&token).unwrap()).unwrap();
+        assert_eq!(restored.balance_of_at(&owner, id).unwrap(), U256::from(1000));
+        assert_eq!(restored.balance_of_at(&alice, id).unwrap(), U256::zero());
+    }
+}
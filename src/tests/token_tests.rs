@@ -1,4 +1,4 @@
-use medi_token::{MediToken, utils::Utils, MediTokenError, token::MediTokenEvent};
+use medi_token::{MediToken, utils::Utils, MediTokenError, token::{MediTokenEvent, ZERO_ADDRESS}, TokenAmount, U256};
 
 #[cfg(test)]
 mod token_tests {
@@ -7,7 +7,7 @@ mod token_tests {
     #[test]
     fn test_token_initialization() {
         let owner = "0x1234567890123456789012345678901234567890".to_string();
-        let initial_supply = 1000 * 10_u64.pow(18);
+        let initial_supply = U256::from(1000) * U256::exp10(18);
         let token = MediToken::new(initial_supply, owner.clone());
 
         assert_eq!(token.name, "MediToken");
@@ -34,14 +34,14 @@ mod token_tests {
     fn test_transfer_success() {
         let owner = Utils::generate_random_address();
         let recipient = Utils::generate_random_address();
-        let mut token = MediToken::new(1000 * 10_u64.pow(18), owner.clone());
+        let mut token = MediToken::new(U256::from(1000) * U256::exp10(18), owner.clone());
 
-        let transfer_amount = 100 * 10_u64.pow(18);
+        let transfer_amount = U256::from(100) * U256::exp10(18);
         let result = token.transfer(&owner, &recipient, transfer_amount);
 
         assert!(result.is_ok());
-        assert_eq!(token.balance_of(&owner), 900 * 10_u64.pow(18));
-        assert_eq!(token.balance_of(&recipient), 100 * 10_u64.pow(18));
+        assert_eq!(token.balance_of(&owner), U256::from(900) * U256::exp10(18));
+        assert_eq!(token.balance_of(&recipient), U256::from(100) * U256::exp10(18));
 
         // Check transfer event
         let events = token.get_events();
@@ -56,20 +56,36 @@ mod token_tests {
         }
     }
 
+    #[test]
+    fn test_self_transfer_keeps_balance() {
+        let owner = Utils::generate_random_address();
+        let holder = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner.clone());
+        token.transfer(&owner, &holder, U256::from(100)).unwrap();
+
+        token.transfer(&holder, &holder, U256::from(40)).unwrap();
+        assert_eq!(token.balance_of(&holder), U256::from(100));
+        assert_eq!(token.total_supply, U256::from(1000));
+
+        let result = token.transfer(&holder, &holder, U256::from(101));
+        assert!(matches!(result.unwrap_err(), MediTokenError::InsufficientBalance { .. }));
+        assert_eq!(token.balance_of(&holder), U256::from(100));
+    }
+
     #[test]
     fn test_transfer_insufficient_balance() {
         let owner = Utils::generate_random_address();
         let recipient = Utils::generate_random_address();
-        let mut token = MediToken::new(100 * 10_u64.pow(18), owner.clone());
+        let mut token = MediToken::new(U256::from(100) * U256::exp10(18), owner.clone());
 
-        let transfer_amount = 200 * 10_u64.pow(18); // More than balance
+        let transfer_amount = U256::from(200) * U256::exp10(18); // More than balance
         let result = token.transfer(&owner, &recipient, transfer_amount);
 
         assert!(result.is_err());
         match result.unwrap_err() {
             MediTokenError::InsufficientBalance { required, available } => {
                 assert_eq!(required, transfer_amount);
-                assert_eq!(available, 100 * 10_u64.pow(18));
+                assert_eq!(available, U256::from(100) * U256::exp10(18));
             }
             _ => panic!("Expected InsufficientBalance error"),
         }
@@ -78,18 +94,18 @@ mod token_tests {
     #[test]
     fn test_transfer_to_zero_address() {
         let owner = Utils::generate_random_address();
-        let mut token = MediToken::new(100 * 10_u64.pow(18), owner.clone());
+        let mut token = MediToken::new(U256::from(100) * U256::exp10(18), owner.clone());
 
-        let result = token.transfer(&owner, "0x0000000000000000000000000000000000000000", 50);
+        let result = token.transfer(&owner, "0x0000000000000000000000000000000000000000", U256::from(50));
         assert!(matches!(result.unwrap_err(), MediTokenError::TransferToZeroAddress));
     }
 
     #[test]
     fn test_transfer_from_zero_address() {
         let recipient = Utils::generate_random_address();
-        let mut token = MediToken::new(100 * 10_u64.pow(18), recipient.clone());
+        let mut token = MediToken::new(U256::from(100) * U256::exp10(18), recipient.clone());
 
-        let result = token.transfer("0x0000000000000000000000000000000000000000", &recipient, 50);
+        let result = token.transfer("0x0000000000000000000000000000000000000000", &recipient, U256::from(50));
         assert!(matches!(result.unwrap_err(), MediTokenError::TransferFromZeroAddress));
     }
 
@@ -97,9 +113,9 @@ mod token_tests {
     fn test_approve_success() {
         let owner = Utils::generate_random_address();
         let spender = Utils::generate_random_address();
-        let mut token = MediToken::new(1000 * 10_u64.pow(18), owner.clone());
+        let mut token = MediToken::new(U256::from(1000) * U256::exp10(18), owner.clone());
 
-        let allowance_amount = 200 * 10_u64.pow(18);
+        let allowance_amount = U256::from(200) * U256::exp10(18);
         let result = token.approve(&owner, &spender, allowance_amount);
 
         assert!(result.is_ok());
@@ -121,18 +137,18 @@ mod token_tests {
     #[test]
     fn test_approve_to_zero_address() {
         let owner = Utils::generate_random_address();
-        let mut token = MediToken::new(100 * 10_u64.pow(18), owner.clone());
+        let mut token = MediToken::new(U256::from(100) * U256::exp10(18), owner.clone());
 
-        let result = token.approve(&owner, "0x0000000000000000000000000000000000000000", 50);
+        let result = token.approve(&owner, "0x0000000000000000000000000000000000000000", U256::from(50));
         assert!(matches!(result.unwrap_err(), MediTokenError::ApproveToZeroAddress));
     }
 
     #[test]
     fn test_approve_from_zero_address() {
         let spender = Utils::generate_random_address();
-        let mut token = MediToken::new(100 * 10_u64.pow(18), spender.clone());
+        let mut token = MediToken::new(U256::from(100) * U256::exp10(18), spender.clone());
 
-        let result = token.approve("0x0000000000000000000000000000000000000000", &spender, 50);
+        let result = token.approve("0x0000000000000000000000000000000000000000", &spender, U256::from(50));
         assert!(matches!(result.unwrap_err(), MediTokenError::ApproveFromZeroAddress));
     }
 
@@ -141,10 +157,10 @@ mod token_tests {
         let owner = Utils::generate_random_address();
         let spender = Utils::generate_random_address();
         let recipient = Utils::generate_random_address();
-        let mut token = MediToken::new(1000 * 10_u64.pow(18), owner.clone());
+        let mut token = MediToken::new(U256::from(1000) * U256::exp10(18), owner.clone());
 
-        let allowance_amount = 200 * 10_u64.pow(18);
-        let transfer_amount = 50 * 10_u64.pow(18);
+        let allowance_amount = U256::from(200) * U256::exp10(18);
+        let transfer_amount = U256::from(50) * U256::exp10(18);
 
         // First approve
         token.approve(&owner, &spender, allowance_amount).unwrap();
@@ -153,9 +169,9 @@ mod token_tests {
         let result = token.transfer_from(&spender, &owner, &recipient, transfer_amount);
 
         assert!(result.is_ok());
-        assert_eq!(token.balance_of(&owner), 950 * 10_u64.pow(18));
-        assert_eq!(token.balance_of(&recipient), 50 * 10_u64.pow(18));
-        assert_eq!(token.allowance(&owner, &spender), 150 * 10_u64.pow(18));
+        assert_eq!(token.balance_of(&owner), U256::from(950) * U256::exp10(18));
+        assert_eq!(token.balance_of(&recipient), U256::from(50) * U256::exp10(18));
+        assert_eq!(token.allowance(&owner, &spender), U256::from(150) * U256::exp10(18));
     }
 
     #[test]
@@ -163,10 +179,10 @@ mod token_tests {
         let owner = Utils::generate_random_address();
         let spender = Utils::generate_random_address();
         let recipient = Utils::generate_random_address();
-        let mut token = MediToken::new(1000 * 10_u64.pow(18), owner.clone());
+        let mut token = MediToken::new(U256::from(1000) * U256::exp10(18), owner.clone());
 
-        let allowance_amount = 50 * 10_u64.pow(18);
-        let transfer_amount = 100 * 10_u64.pow(18); // More than allowance
+        let allowance_amount = U256::from(50) * U256::exp10(18);
+        let transfer_amount = U256::from(100) * U256::exp10(18); // More than allowance
 
         // First approve
         token.approve(&owner, &spender, allowance_amount).unwrap();
@@ -190,38 +206,38 @@ mod token_tests {
         let owner = Utils::generate_random_address();
         let spender1 = Utils::generate_random_address();
         let spender2 = Utils::generate_random_address();
-        let mut token = MediToken::new(1000 * 10_u64.pow(18), owner.clone());
+        let mut token = MediToken::new(U256::from(1000) * U256::exp10(18), owner.clone());
 
         // Approve multiple spenders
-        token.approve(&owner, &spender1, 100 * 10_u64.pow(18)).unwrap();
-        token.approve(&owner, &spender2, 200 * 10_u64.pow(18)).unwrap();
+        token.approve(&owner, &spender1, U256::from(100) * U256::exp10(18)).unwrap();
+        token.approve(&owner, &spender2, U256::from(200) * U256::exp10(18)).unwrap();
 
-        assert_eq!(token.allowance(&owner, &spender1), 100 * 10_u64.pow(18));
-        assert_eq!(token.allowance(&owner, &spender2), 200 * 10_u64.pow(18));
+        assert_eq!(token.allowance(&owner, &spender1), U256::from(100) * U256::exp10(18));
+        assert_eq!(token.allowance(&owner, &spender2), U256::from(200) * U256::exp10(18));
 
         // Update approval for spender1
-        token.approve(&owner, &spender1, 300 * 10_u64.pow(18)).unwrap();
-        assert_eq!(token.allowance(&owner, &spender1), 300 * 10_u64.pow(18));
-        assert_eq!(token.allowance(&owner, &spender2), 200 * 10_u64.pow(18)); // Unchanged
+        token.approve(&owner, &spender1, U256::from(300) * U256::exp10(18)).unwrap();
+        assert_eq!(token.allowance(&owner, &spender1), U256::from(300) * U256::exp10(18));
+        assert_eq!(token.allowance(&owner, &spender2), U256::from(200) * U256::exp10(18)); // Unchanged
     }
 
     #[test]
     fn test_balance_of_nonexistent_address() {
         let owner = Utils::generate_random_address();
-        let token = MediToken::new(1000 * 10_u64.pow(18), owner);
+        let token = MediToken::new(U256::from(1000) * U256::exp10(18), owner);
 
         let nonexistent = Utils::generate_random_address();
-        assert_eq!(token.balance_of(&nonexistent), 0);
+        assert_eq!(token.balance_of(&nonexistent), U256::zero());
     }
 
     #[test]
     fn test_allowance_nonexistent_addresses() {
         let owner = Utils::generate_random_address();
-        let token = MediToken::new(1000 * 10_u64.pow(18), owner);
+        let token = MediToken::new(U256::from(1000) * U256::exp10(18), owner);
 
         let addr1 = Utils::generate_random_address();
         let addr2 = Utils::generate_random_address();
-        assert_eq!(token.allowance(&addr1, &addr2), 0);
+        assert_eq!(token.allowance(&addr1, &addr2), U256::zero());
     }
 
     #[test]
@@ -229,21 +245,60 @@ mod token_tests {
         let token = MediToken::default();
 
         // Test various amounts
-        let test_cases = vec![1.0, 0.5, 1.5, 100.0, 0.001, 999.999];
+        let test_cases = vec!["1", "0.5", "1.5", "100", "0.001", "999.999", "0.1", "123.456789012345678901"];
 
         for amount in test_cases {
-            let raw = token.to_raw_amount(amount);
-            let converted_back = token.from_raw_amount(raw);
-            assert!((converted_back - amount).abs() < 1e-10, 
-                   "Conversion failed for {}: got {}", amount, converted_back);
+            let raw = token.to_raw_amount(amount).unwrap();
+            let converted_back = token.from_raw_amount(raw).unwrap();
+            assert_eq!(converted_back, amount, "Conversion failed for {}", amount);
         }
+
+        assert_eq!(token.to_raw_amount("0.1").unwrap(), U256::exp10(17));
+        assert_eq!(token.from_raw_amount(U256::one()).unwrap(), "0.000000000000000001");
+    }
+
+    #[test]
+    fn test_decimal_conversion_errors() {
+        let token = MediToken::default();
+
+        assert!(matches!(
+            token.to_raw_amount("1.0000000000000000001").unwrap_err(),
+            MediTokenError::TooManyDecimals { decimals: 18, .. }
+        ));
+        assert!(matches!(
+            token.to_raw_amount("-1").unwrap_err(),
+            MediTokenError::InvalidAmount { .. }
+        ));
+        assert!(matches!(
+            token.to_raw_amount("1e18").unwrap_err(),
+            MediTokenError::InvalidAmount { .. }
+        ));
+        assert!(matches!(
+            token.to_raw_amount(".").unwrap_err(),
+            MediTokenError::InvalidAmount { .. }
+        ));
+
+        // 10^60 tokens with 18 decimals exceeds U256::MAX
+        let too_large = format!("1{}", "0".repeat(60));
+        assert!(matches!(
+            token.to_raw_amount(&too_large).unwrap_err(),
+            MediTokenError::ArithmeticOverflow
+        ));
+
+        // Formatting with more decimals than a U256 can scale by is rejected
+        let token = MediToken { decimals: 78, ..MediToken::default() };
+        assert!(matches!(
+            token.from_raw_amount(U256::one()).unwrap_err(),
+            MediTokenError::InvalidAmount { .. }
+        ));
+        assert!(TokenAmount::from_raw(U256::one(), 77).is_ok());
     }
 
     #[test]
     fn test_large_transfers() {
         let owner = Utils::generate_random_address();
         let recipient = Utils::generate_random_address();
-        let large_amount = u64::MAX / 2; // Large but safe amount
+        let large_amount = U256::MAX / 2; // Large but safe amount
         let mut token = MediToken::new(large_amount, owner.clone());
 
         let transfer_amount = large_amount / 4;
@@ -253,4 +308,139 @@ mod token_tests {
         assert_eq!(token.balance_of(&owner), large_amount - transfer_amount);
         assert_eq!(token.balance_of(&recipient), transfer_amount);
     }
+
+    #[test]
+    fn test_mint_by_owner() {
+        let owner = Utils::generate_random_address();
+        let recipient = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000) * U256::exp10(18), owner.clone());
+
+        let mint_amount = U256::from(250) * U256::exp10(18);
+        assert!(token.mint(&owner, &recipient, mint_amount).is_ok());
+
+        assert_eq!(token.balance_of(&recipient), mint_amount);
+        assert_eq!(token.total_supply, U256::from(1250) * U256::exp10(18));
+        match token.get_events().last().unwrap() {
+            MediTokenEvent::Transfer { from, to, value } => {
+                assert_eq!(from, ZERO_ADDRESS);
+                assert_eq!(to, &recipient);
+                assert_eq!(*value, mint_amount);
+            }
+            _ => panic!("Expected Transfer event"),
+        }
+    }
+
+    #[test]
+    fn test_mint_requires_owner() {
+        let owner = Utils::generate_random_address();
+        let attacker = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner);
+
+        let result = token.mint(&attacker, &attacker, U256::from(1));
+        assert!(matches!(result.unwrap_err(), MediTokenError::MissingRole { account, .. } if account == attacker));
+        assert_eq!(token.total_supply, U256::from(1000));
+    }
+
+    #[test]
+    fn test_mint_to_zero_address() {
+        let owner = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner.clone());
+
+        let result = token.mint(&owner, ZERO_ADDRESS, U256::from(1));
+        assert!(matches!(result.unwrap_err(), MediTokenError::MintToZeroAddress));
+    }
+
+    #[test]
+    fn test_supply_cap() {
+        let owner = Utils::generate_random_address();
+        let recipient = Utils::generate_random_address();
+        let mut token = MediToken::new_capped(U256::from(900), owner.clone(), U256::from(1000)).unwrap();
+
+        assert!(token.mint(&owner, &recipient, U256::from(100)).is_ok());
+        match token.mint(&owner, &recipient, U256::from(1)).unwrap_err() {
+            MediTokenError::SupplyCapExceeded { cap, requested } => {
+                assert_eq!(cap, U256::from(1000));
+                assert_eq!(requested, U256::from(1001));
+            }
+            _ => panic!("Expected SupplyCapExceeded error"),
+        }
+        assert_eq!(token.total_supply, U256::from(1000));
+
+        let result = MediToken::new_capped(U256::from(1001), owner, U256::from(1000));
+        assert!(matches!(result.unwrap_err(), MediTokenError::SupplyCapExceeded { .. }));
+    }
+
+    #[test]
+    fn test_mint_overflow() {
+        let owner = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::MAX, owner.clone());
+
+        let result = token.mint(&owner, &owner, U256::one());
+        assert!(matches!(result.unwrap_err(), MediTokenError::ArithmeticOverflow));
+    }
+
+    #[test]
+    fn test_burn_by_owner() {
+        let owner = Utils::generate_random_address();
+        let holder = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner.clone());
+        token.transfer(&owner, &holder, U256::from(300)).unwrap();
+
+        assert!(token.burn(&owner, &holder, U256::from(100)).is_ok());
+        assert_eq!(token.balance_of(&holder), U256::from(200));
+        assert_eq!(token.total_supply, U256::from(900));
+        match token.get_events().last().unwrap() {
+            MediTokenEvent::Transfer { from, to, value } => {
+                assert_eq!(from, &holder);
+                assert_eq!(to, ZERO_ADDRESS);
+                assert_eq!(*value, U256::from(100));
+            }
+            _ => panic!("Expected Transfer event"),
+        }
+
+        let result = token.burn(&holder, &holder, U256::from(1));
+        assert!(matches!(result.unwrap_err(), MediTokenError::MissingRole { .. }));
+
+        let result = token.burn(&owner, &holder, U256::from(201));
+        assert!(matches!(result.unwrap_err(), MediTokenError::InsufficientBalance { .. }));
+    }
+
+    #[test]
+    fn test_burn_from_spends_allowance() {
+        let owner = Utils::generate_random_address();
+        let holder = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner.clone());
+        token.transfer(&owner, &holder, U256::from(300)).unwrap();
+
+        let result = token.burn_from(&owner, &holder, U256::from(50));
+        assert!(matches!(result.unwrap_err(), MediTokenError::InsufficientAllowance { .. }));
+
+        token.approve(&holder, &owner, U256::from(80)).unwrap();
+        assert!(token.burn_from(&owner, &holder, U256::from(50)).is_ok());
+
+        assert_eq!(token.balance_of(&holder), U256::from(250));
+        assert_eq!(token.allowance(&holder, &owner), U256::from(30));
+        assert_eq!(token.total_supply, U256::from(950));
+    }
+
+    #[test]
+    fn test_spending_without_any_allowance() {
+        let owner = Utils::generate_random_address();
+        let holder = Utils::generate_random_address();
+        let mut token = MediToken::new(U256::from(1000), owner.clone());
+        token.transfer(&owner, &holder, U256::from(300)).unwrap();
+
+        // A missing allowance reads as zero, so spending nothing succeeds as in ERC20
+        assert!(token.burn_from(&owner, &holder, U256::zero()).is_ok());
+        assert!(token.transfer_from(&owner, &holder, &owner, U256::zero()).is_ok());
+        assert_eq!(token.allowance(&holder, &owner), U256::zero());
+
+        let result = token.burn_from(&owner, &holder, U256::from(10));
+        assert!(matches!(result.unwrap_err(), MediTokenError::InsufficientAllowance { .. }));
+        let result = token.transfer_from(&owner, &holder, &owner, U256::from(10));
+        assert!(matches!(result.unwrap_err(), MediTokenError::InsufficientAllowance { .. }));
+
+        assert_eq!(token.balance_of(&holder), U256::from(300));
+        assert_eq!(token.total_supply, U256::from(1000));
+    }
 }
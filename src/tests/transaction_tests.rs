@@ -0,0 +1,74 @@
+use medi_token::transaction::{parse_private_key, signer_address, LegacyTransaction};
+use medi_token::rpc::{parse_quantity, to_quantity};
+use medi_token::{MediTokenError, U256};
+
+#[cfg(test)]
+mod transaction_tests {
+    use super::*;
+
+    #[test]
+    fn test_eip155_signing_vector() {
+        // Example transaction from the EIP-155 specification
+        let key = parse_private_key("0x4646464646464646464646464646464646464646464646464646464646464646").unwrap();
+        let tx = LegacyTransaction {
+            nonce: U256::from(9),
+            gas_price: U256::from(20_000_000_000u64),
+            gas_limit: U256::from(21000),
+            to: Some([0x35; 20]),
+            value: U256::exp10(18),
+            data: Vec::new(),
+            chain_id: 1,
+        };
+
+        let signed = tx.sign(&key).unwrap();
+        assert_eq!(
+            signed.raw_hex(),
+            "0xf86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83"
+        );
+        assert_eq!(
+            signed.hash_hex(),
+            "0x33469b22e9f636356c4160a87eb19df52b7412e8eac32a4a55ffe88ea8350788"
+        );
+    }
+
+    #[test]
+    fn test_contract_creation_has_empty_recipient() {
+        let key = parse_private_key("4646464646464646464646464646464646464646464646464646464646464646").unwrap();
+        let tx = LegacyTransaction {
+            nonce: U256::zero(),
+            gas_price: U256::one(),
+            gas_limit: U256::from(100000),
+            to: None,
+            value: U256::zero(),
+            data: vec![0xab; 100],
+            chain_id: 31337,
+        };
+
+        let signed = tx.sign(&key).unwrap();
+        // Long list prefix, then nonce 0x80, gas price 0x01, gas limit, empty `to` (0x80)
+        assert_eq!(signed.raw[0], 0xf8);
+        assert_eq!(signed.raw[1] as usize, signed.raw.len() - 2);
+        assert_eq!(&signed.raw[2..9], &[0x80, 0x01, 0x83, 0x01, 0x86, 0xa0, 0x80]);
+    }
+
+    #[test]
+    fn test_signer_address() {
+        let key = parse_private_key("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+        assert_eq!(signer_address(&key), "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266");
+    }
+
+    #[test]
+    fn test_invalid_private_key() {
+        assert!(matches!(parse_private_key("0xnothex").unwrap_err(), MediTokenError::ContractError { .. }));
+        assert!(matches!(parse_private_key("0x1234").unwrap_err(), MediTokenError::ContractError { .. }));
+    }
+
+    #[test]
+    fn test_quantities() {
+        assert_eq!(parse_quantity("0x0").unwrap(), U256::zero());
+        assert_eq!(parse_quantity("0x7a69").unwrap(), U256::from(31337));
+        assert_eq!(to_quantity(U256::from(31337)), "0x7a69");
+        assert_eq!(to_quantity(U256::zero()), "0x0");
+        assert!(parse_quantity("0xzz").is_err());
+    }
+}
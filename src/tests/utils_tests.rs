@@ -1,4 +1,4 @@
-use medi_token::{utils::Utils, MediTokenError};
+use medi_token::{utils::Utils, MediTokenError, U256};
 
 #[cfg(test)]
 mod utils_tests {
@@ -40,16 +40,19 @@ mod utils_tests {
     #[test]
     fn test_wei_ether_conversions() {
         // Test exact conversions
-        assert_eq!(Utils::wei_to_ether(1_000_000_000_000_000_000), 1.0);
-        assert_eq!(Utils::wei_to_ether(500_000_000_000_000_000), 0.5);
-        assert_eq!(Utils::wei_to_ether(2_500_000_000_000_000_000), 2.5);
+        assert_eq!(Utils::wei_to_ether(U256::from(1_000_000_000_000_000_000u64)), "1");
+        assert_eq!(Utils::wei_to_ether(U256::from(500_000_000_000_000_000u64)), "0.5");
+        assert_eq!(Utils::wei_to_ether(U256::from(2_500_000_000_000_000_000u64)), "2.5");
 
-        assert_eq!(Utils::ether_to_wei(1.0), 1_000_000_000_000_000_000);
-        assert_eq!(Utils::ether_to_wei(0.5), 500_000_000_000_000_000);
-        assert_eq!(Utils::ether_to_wei(2.5), 2_500_000_000_000_000_000);
+        assert_eq!(Utils::ether_to_wei("1").unwrap(), U256::from(1_000_000_000_000_000_000u64));
+        assert_eq!(Utils::ether_to_wei("0.5").unwrap(), U256::from(500_000_000_000_000_000u64));
+        assert_eq!(Utils::ether_to_wei("2.5").unwrap(), U256::from(2_500_000_000_000_000_000u64));
+
+        // Amounts beyond u64::MAX must not saturate
+        assert_eq!(Utils::ether_to_wei("10000").unwrap(), U256::from(10000) * U256::exp10(18));
 
         // Test round-trip conversions
-        let test_wei_values = vec![
+        let test_wei_values: Vec<u64> = vec![
             1_000_000_000_000_000_000,
             500_000_000_000_000_000,
             1_234_567_890_123_456_789,
@@ -57,11 +60,10 @@ mod utils_tests {
             999_999_999_999_999_999,
         ];
 
-        for wei in test_wei_values {
+        for wei in test_wei_values.into_iter().map(U256::from) {
             let ether = Utils::wei_to_ether(wei);
-            let back_to_wei = Utils::ether_to_wei(ether);
-            // Allow small rounding errors due to floating point precision
-            assert!((back_to_wei as i128 - wei as i128).abs() <= 1);
+            let back_to_wei = Utils::ether_to_wei(&ether).unwrap();
+            assert_eq!(back_to_wei, wei);
         }
     }
 
@@ -92,16 +94,20 @@ mod utils_tests {
     #[test]
     fn test_overflow_check() {
         // Valid additions
-        assert_eq!(Utils::check_overflow(100, 200).unwrap(), 300);
-        assert_eq!(Utils::check_overflow(0, u64::MAX).unwrap(), u64::MAX);
-        assert_eq!(Utils::check_overflow(u64::MAX, 0).unwrap(), u64::MAX);
+        assert_eq!(Utils::check_overflow(U256::from(100), U256::from(200)).unwrap(), U256::from(300));
+        assert_eq!(Utils::check_overflow(U256::zero(), U256::MAX).unwrap(), U256::MAX);
+        assert_eq!(Utils::check_overflow(U256::MAX, U256::zero()).unwrap(), U256::MAX);
+        assert_eq!(
+            Utils::check_overflow(U256::from(u64::MAX), U256::one()).unwrap(),
+            U256::from(u64::MAX) + 1
+        );
 
         // Overflow cases
-        assert!(Utils::check_overflow(u64::MAX, 1).is_err());
-        assert!(Utils::check_overflow(u64::MAX / 2 + 1, u64::MAX / 2 + 1).is_err());
+        assert!(Utils::check_overflow(U256::MAX, U256::one()).is_err());
+        assert!(Utils::check_overflow(U256::MAX / 2 + 1, U256::MAX / 2 + 1).is_err());
 
         // Check error type
-        match Utils::check_overflow(u64::MAX, 1).unwrap_err() {
+        match Utils::check_overflow(U256::MAX, U256::one()).unwrap_err() {
             MediTokenError::ArithmeticOverflow => {}, // Expected
             _ => panic!("Expected ArithmeticOverflow error"),
         }
@@ -110,16 +116,16 @@ mod utils_tests {
     #[test]
     fn test_underflow_check() {
         // Valid subtractions
-        assert_eq!(Utils::check_underflow(300, 200).unwrap(), 100);
-        assert_eq!(Utils::check_underflow(u64::MAX, 0).unwrap(), u64::MAX);
-        assert_eq!(Utils::check_underflow(100, 100).unwrap(), 0);
+        assert_eq!(Utils::check_underflow(U256::from(300), U256::from(200)).unwrap(), U256::from(100));
+        assert_eq!(Utils::check_underflow(U256::MAX, U256::zero()).unwrap(), U256::MAX);
+        assert_eq!(Utils::check_underflow(U256::from(100), U256::from(100)).unwrap(), U256::zero());
 
         // Underflow cases
-        assert!(Utils::check_underflow(0, 1).is_err());
-        assert!(Utils::check_underflow(100, 200).is_err());
+        assert!(Utils::check_underflow(U256::zero(), U256::one()).is_err());
+        assert!(Utils::check_underflow(U256::from(100), U256::from(200)).is_err());
 
         // Check error type
-        match Utils::check_underflow(0, 1).unwrap_err() {
+        match Utils::check_underflow(U256::zero(), U256::one()).unwrap_err() {
             MediTokenError::ArithmeticUnderflow => {}, // Expected
             _ => panic!("Expected ArithmeticUnderflow error"),
         }
@@ -128,13 +134,13 @@ mod utils_tests {
     #[test]
     fn test_edge_cases() {
         // Test with maximum values
-        let max_wei = u64::MAX;
+        let max_wei = U256::MAX;
         let ether_val = Utils::wei_to_ether(max_wei);
-        assert!(ether_val > 0.0);
+        assert_eq!(Utils::ether_to_wei(&ether_val).unwrap(), max_wei);
 
         // Test with minimum values
-        assert_eq!(Utils::wei_to_ether(0), 0.0);
-        assert_eq!(Utils::ether_to_wei(0.0), 0);
+        assert_eq!(Utils::wei_to_ether(U256::zero()), "0");
+        assert_eq!(Utils::ether_to_wei("0").unwrap(), U256::zero());
 
         // Test address validation edge cases
         assert!(!Utils::is_valid_address("0X1234567890123456789012345678901234567890")); // Uppercase X
@@ -0,0 +1,208 @@
+use medi_token::{MediToken, MediTokenError, U256};
+use medi_token::clock::{Clock, ManualClock};
+use medi_token::vesting::{GrantTerms, Milestone, Vesting, VestingEvent, VestingSchedule};
+
+mod common;
+use common::fixtures::{account, Fixture, NOW as START};
+
+#[cfg(test)]
+mod vesting_tests {
+    use super::*;
+
+    const MONTH: u64 = 30 * 24 * 60 * 60;
+
+    struct Setup {
+        fx: Fixture,
+        vesting: Vesting<ManualClock>,
+        researcher: String,
+    }
+
+    /// The admin is the grantor, holding all 10000 tokens
+    fn setup() -> Setup {
+        let fx = Fixture::new(10_000);
+        let vesting = Vesting::with_clock(account(), fx.clock.clone());
+        Setup { fx, vesting, researcher: account() }
+    }
+
+    fn linear(beneficiary: &str, total: u64, revocable: bool) -> GrantTerms {
+        GrantTerms {
+            beneficiary: beneficiary.to_string(),
+            total: U256::from(total),
+            start: START,
+            cliff: 3 * MONTH,
+            duration: 12 * MONTH,
+            schedule: VestingSchedule::Linear,
+            revocable,
+        }
+    }
+
+    #[test]
+    fn test_manual_clock_is_shared() {
+        let clock = ManualClock::new(10);
+        let copy = clock.clone();
+        clock.advance(5);
+        assert_eq!(copy.now(), 15);
+        copy.set(3);
+        assert_eq!(clock.now(), 3);
+    }
+
+    #[test]
+    fn test_create_grant_escrows_tokens() {
+        let mut s = setup();
+        let id = s.vesting.create_grant(&mut s.fx.token, &s.fx.admin, linear(&s.researcher, 1200, false)).unwrap();
+
+        assert_eq!(s.fx.token.balance_of(&s.vesting.address), U256::from(1200));
+        assert_eq!(s.fx.token.balance_of(&s.fx.admin), U256::from(8800));
+        assert_eq!(s.vesting.grants_for(&s.researcher).len(), 1);
+        assert_eq!(
+            s.vesting.get_events().last().unwrap(),
+            &VestingEvent::GrantCreated {
+                id,
+                grantor: s.fx.admin.clone(),
+                beneficiary: s.researcher.clone(),
+                total: U256::from(1200),
+            }
+        );
+    }
+
+    #[test]
+    fn test_linear_vesting_with_cliff() {
+        let mut s = setup();
+        let id = s.vesting.create_grant(&mut s.fx.token, &s.fx.admin, linear(&s.researcher, 1200, false)).unwrap();
+
+        s.fx.clock.set(START + 3 * MONTH - 1);
+        assert_eq!(s.vesting.releasable(id).unwrap(), U256::zero());
+        assert_eq!(s.vesting.release(&mut s.fx.token, id).unwrap(), U256::zero());
+
+        s.fx.clock.set(START + 3 * MONTH);
+        assert_eq!(s.vesting.releasable(id).unwrap(), U256::from(300));
+        assert_eq!(s.vesting.release(&mut s.fx.token, id).unwrap(), U256::from(300));
+        assert_eq!(s.fx.token.balance_of(&s.researcher), U256::from(300));
+        assert_eq!(s.vesting.releasable(id).unwrap(), U256::zero());
+
+        s.fx.clock.set(START + 6 * MONTH);
+        assert_eq!(s.vesting.release(&mut s.fx.token, id).unwrap(), U256::from(300));
+
+        s.fx.clock.set(START + 24 * MONTH);
+        assert_eq!(s.vesting.release(&mut s.fx.token, id).unwrap(), U256::from(600));
+        assert_eq!(s.fx.token.balance_of(&s.researcher), U256::from(1200));
+        assert_eq!(s.fx.token.balance_of(&s.vesting.address), U256::zero());
+        assert_eq!(s.vesting.grant(id).unwrap().released, U256::from(1200));
+    }
+
+    #[test]
+    fn test_release_emits_event_per_payment() {
+        let mut s = setup();
+        let id = s.vesting.create_grant(&mut s.fx.token, &s.fx.admin, linear(&s.researcher, 1200, false)).unwrap();
+        let created = s.vesting.get_events().len();
+
+        s.vesting.release(&mut s.fx.token, id).unwrap();
+        assert_eq!(s.vesting.get_events().len(), created);
+
+        s.fx.clock.set(START + 4 * MONTH);
+        s.vesting.release(&mut s.fx.token, id).unwrap();
+        assert_eq!(
+            s.vesting.get_events().last().unwrap(),
+            &VestingEvent::TokensReleased { id, beneficiary: s.researcher.clone(), amount: U256::from(400) }
+        );
+    }
+
+    #[test]
+    fn test_milestone_vesting() {
+        let mut s = setup();
+        let terms = GrantTerms {
+            cliff: 0,
+            duration: 0,
+            schedule: VestingSchedule::Milestones(vec![
+                Milestone { offset: MONTH, amount: U256::from(100) },
+                Milestone { offset: 6 * MONTH, amount: U256::from(400) },
+                Milestone { offset: 12 * MONTH, amount: U256::from(500) },
+            ]),
+            ..linear(&s.researcher, 1000, false)
+        };
+        let id = s.vesting.create_grant(&mut s.fx.token, &s.fx.admin, terms).unwrap();
+
+        assert_eq!(s.vesting.releasable(id).unwrap(), U256::zero());
+        s.fx.clock.set(START + 7 * MONTH);
+        assert_eq!(s.vesting.release(&mut s.fx.token, id).unwrap(), U256::from(500));
+        s.fx.clock.set(START + 12 * MONTH);
+        assert_eq!(s.vesting.release(&mut s.fx.token, id).unwrap(), U256::from(500));
+        assert_eq!(s.fx.token.balance_of(&s.researcher), U256::from(1000));
+    }
+
+    #[test]
+    fn test_revoke_refunds_unvested() {
+        let mut s = setup();
+        let id = s.vesting.create_grant(&mut s.fx.token, &s.fx.admin, linear(&s.researcher, 1200, true)).unwrap();
+
+        s.fx.clock.set(START + 4 * MONTH);
+        s.vesting.release(&mut s.fx.token, id).unwrap();
+        s.fx.clock.set(START + 6 * MONTH);
+        assert_eq!(s.vesting.revoke(&mut s.fx.token, &s.fx.admin, id).unwrap(), U256::from(600));
+        assert_eq!(s.fx.token.balance_of(&s.fx.admin), U256::from(9400));
+        assert_eq!(
+            s.vesting.get_events().last().unwrap(),
+            &VestingEvent::GrantRevoked { id, refunded: U256::from(600) }
+        );
+
+        // Vesting stops at revocation but what had vested can still be released
+        s.fx.clock.set(START + 24 * MONTH);
+        assert_eq!(s.vesting.release(&mut s.fx.token, id).unwrap(), U256::from(200));
+        assert_eq!(s.fx.token.balance_of(&s.researcher), U256::from(600));
+        assert_eq!(s.fx.token.balance_of(&s.vesting.address), U256::zero());
+
+        let result = s.vesting.revoke(&mut s.fx.token, &s.fx.admin, id);
+        assert!(matches!(result.unwrap_err(), MediTokenError::VestingError { .. }));
+    }
+
+    #[test]
+    fn test_revoke_rules() {
+        let mut s = setup();
+        let fixed = s.vesting.create_grant(&mut s.fx.token, &s.fx.admin, linear(&s.researcher, 100, false)).unwrap();
+        let revocable = s.vesting.create_grant(&mut s.fx.token, &s.fx.admin, linear(&s.researcher, 100, true)).unwrap();
+
+        let result = s.vesting.revoke(&mut s.fx.token, &s.fx.admin, fixed);
+        assert!(matches!(result.unwrap_err(), MediTokenError::VestingError { .. }));
+        let result = s.vesting.revoke(&mut s.fx.token, &s.researcher, revocable);
+        assert!(matches!(result.unwrap_err(), MediTokenError::Unauthorized { .. }));
+        let result = s.vesting.revoke(&mut s.fx.token, &s.fx.admin, 99);
+        assert!(matches!(result.unwrap_err(), MediTokenError::GrantNotFound { id: 99 }));
+    }
+
+    #[test]
+    fn test_invalid_terms() {
+        let mut s = setup();
+
+        let terms = GrantTerms { cliff: 13 * MONTH, ..linear(&s.researcher, 100, false) };
+        assert!(matches!(
+            s.vesting.create_grant(&mut s.fx.token, &s.fx.admin, terms).unwrap_err(),
+            MediTokenError::VestingError { .. }
+        ));
+
+        let terms = GrantTerms {
+            schedule: VestingSchedule::Milestones(vec![Milestone { offset: 0, amount: U256::from(99) }]),
+            ..linear(&s.researcher, 100, false)
+        };
+        assert!(s.vesting.create_grant(&mut s.fx.token, &s.fx.admin, terms).is_err());
+
+        assert!(matches!(
+            s.vesting.create_grant(&mut s.fx.token, &s.fx.admin, linear(&s.researcher, 0, false)).unwrap_err(),
+            MediTokenError::InvalidAmount { .. }
+        ));
+
+        let result = s.vesting.create_grant(&mut s.fx.token, &s.fx.admin, linear(&s.researcher, 20_000, false));
+        assert!(matches!(result.unwrap_err(), MediTokenError::InsufficientBalance { .. }));
+        assert_eq!(s.fx.token.balance_of(&s.fx.admin), U256::from(10_000));
+    }
+
+    #[test]
+    fn test_large_grant_does_not_overflow() {
+        let mut s = setup();
+        s.fx.token = MediToken::new(U256::MAX, s.fx.admin.clone());
+        let terms = GrantTerms { total: U256::MAX, ..linear(&s.researcher, 1, false) };
+        let id = s.vesting.create_grant(&mut s.fx.token, &s.fx.admin, terms).unwrap();
+
+        s.fx.clock.set(START + 6 * MONTH);
+        assert_eq!(s.vesting.releasable(id).unwrap(), U256::MAX / 2);
+    }
+}
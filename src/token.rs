@@ -6,20 +6,60 @@
 
 use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
+use primitive_types::U256;
+use crate::access_control::{AccessControl, COMPLIANCE_ROLE, DEFAULT_ADMIN_ROLE, MINTER_ROLE, PAUSER_ROLE, SNAPSHOT_ROLE};
+use crate::amount::TokenAmount;
+use crate::compliance::Compliance;
 use crate::error::{MediTokenError, Result};
+use crate::snapshot::Snapshots;
+
+/// The zero address, used as the counterparty of mint and burn events
+pub const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
 
 /// Events emitted by the MediToken contract
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MediTokenEvent {
     Transfer {
         from: String,
         to: String,
-        value: u64,
+        value: U256,
     },
     Approval {
         owner: String,
         spender: String,
-        value: u64,
+        value: U256,
+    },
+    RoleGranted {
+        role: String,
+        account: String,
+        sender: String,
+    },
+    RoleRevoked {
+        role: String,
+        account: String,
+        sender: String,
+    },
+    RoleAdminChanged {
+        role: String,
+        previous_admin_role: String,
+        new_admin_role: String,
+    },
+    Paused {
+        account: String,
+    },
+    Unpaused {
+        account: String,
+    },
+    AccountFrozen {
+        account: String,
+        sender: String,
+    },
+    AccountUnfrozen {
+        account: String,
+        sender: String,
+    },
+    Snapshot {
+        id: u64,
     },
 }
 
@@ -33,23 +73,44 @@ pub struct MediToken {
     /// Number of decimals
     pub decimals: u8,
     /// Total supply of tokens
-    pub total_supply: u64,
+    pub total_supply: U256,
     /// Balance mapping: address -> balance
-    pub balances: HashMap<String, u64>,
+    pub balances: HashMap<String, U256>,
     /// Allowance mapping: owner -> spender -> amount
-    pub allowances: HashMap<String, HashMap<String, u64>>,
+    pub allowances: HashMap<String, HashMap<String, U256>>,
     /// Contract owner
     pub owner: String,
+    /// Optional maximum total supply
+    #[serde(default)]
+    pub cap: Option<U256>,
+    /// Role assignments
+    #[serde(default)]
+    pub roles: AccessControl,
+    /// Whether transfers are halted
+    #[serde(default)]
+    pub paused: bool,
+    /// Accounts blocked from sending and receiving
+    #[serde(default)]
+    pub compliance: Compliance,
+    /// Historical balances and total supply
+    #[serde(default)]
+    pub snapshots: Snapshots,
     /// Event log
     pub events: Vec<MediTokenEvent>,
 }
 
 impl MediToken {
     /// Creates a new MediToken instance
-    pub fn new(initial_supply: u64, owner: String) -> Self {
+    pub fn new(initial_supply: U256, owner: String) -> Self {
         let mut balances = HashMap::new();
         balances.insert(owner.clone(), initial_supply);
         
+        // The deployer administers all roles and may mint, pause, freeze and snapshot
+        let mut roles = AccessControl::new();
+        for role in [DEFAULT_ADMIN_ROLE, MINTER_ROLE, PAUSER_ROLE, COMPLIANCE_ROLE, SNAPSHOT_ROLE] {
+            roles.grant_role_unchecked(role, &owner);
+        }
+        
         let mut token = Self {
             name: "MediToken".to_string(),
             symbol: "MEDT".to_string(),
@@ -58,12 +119,17 @@ impl MediToken {
             balances,
             allowances: HashMap::new(),
             owner: owner.clone(),
+            cap: None,
+            roles,
+            paused: false,
+            compliance: Compliance::new(),
+            snapshots: Snapshots::new(),
             events: Vec::new(),
         };
         
         // Emit initial transfer event (mint)
         token.emit_event(MediTokenEvent::Transfer {
-            from: "0x0000000000000000000000000000000000000000".to_string(),
+            from: ZERO_ADDRESS.to_string(),
             to: owner,
             value: initial_supply,
         });
@@ -71,22 +137,36 @@ impl MediToken {
         token
     }
     
+    /// Creates a new MediToken instance whose total supply can never exceed `cap`
+    pub fn new_capped(initial_supply: U256, owner: String, cap: U256) -> Result<Self> {
+        if initial_supply > cap {
+            return Err(MediTokenError::SupplyCapExceeded {
+                cap,
+                requested: initial_supply,
+            });
+        }
+        
+        let mut token = Self::new(initial_supply, owner);
+        token.cap = Some(cap);
+        Ok(token)
+    }
+    
     /// Gets the balance of an address
-    pub fn balance_of(&self, address: &str) -> u64 {
-        self.balances.get(address).copied().unwrap_or(0)
+    pub fn balance_of(&self, address: &str) -> U256 {
+        self.balances.get(address).copied().unwrap_or_default()
     }
     
     /// Gets the allowance for a spender from an owner
-    pub fn allowance(&self, owner: &str, spender: &str) -> u64 {
+    pub fn allowance(&self, owner: &str, spender: &str) -> U256 {
         self.allowances
             .get(owner)
             .and_then(|allowances| allowances.get(spender))
             .copied()
-            .unwrap_or(0)
+            .unwrap_or_default()
     }
     
     /// Transfers tokens from one address to another
-    pub fn transfer(&mut self, from: &str, to: &str, amount: u64) -> Result<bool> {
+    pub fn transfer(&mut self, from: &str, to: &str, amount: U256) -> Result<bool> {
         self.validate_transfer(from, to, amount)?;
         
         let from_balance = self.balance_of(from);
@@ -97,10 +177,21 @@ impl MediToken {
             });
         }
         
+        // The recipient balance is read after the debit so that self-transfers net to zero
+        let to_balance = if from == to {
+            from_balance - amount
+        } else {
+            self.balance_of(to)
+        };
+        let new_to_balance = to_balance
+            .checked_add(amount)
+            .ok_or(MediTokenError::ArithmeticOverflow)?;
+        
         // Update balances
+        self.snapshots.update_account(from, from_balance);
+        self.snapshots.update_account(to, to_balance);
         self.balances.insert(from.to_string(), from_balance - amount);
-        let to_balance = self.balance_of(to);
-        self.balances.insert(to.to_string(), to_balance + amount);
+        self.balances.insert(to.to_string(), new_to_balance);
         
         // Emit transfer event
         self.emit_event(MediTokenEvent::Transfer {
@@ -113,12 +204,12 @@ impl MediToken {
     }
     
     /// Approves a spender to spend tokens on behalf of owner
-    pub fn approve(&mut self, owner: &str, spender: &str, amount: u64) -> Result<bool> {
+    pub fn approve(&mut self, owner: &str, spender: &str, amount: U256) -> Result<bool> {
         self.validate_approval(owner, spender)?;
         
         self.allowances
             .entry(owner.to_string())
-            .or_insert_with(HashMap::new)
+            .or_default()
             .insert(spender.to_string(), amount);
         
         // Emit approval event
@@ -132,45 +223,334 @@ impl MediToken {
     }
     
     /// Transfers tokens from owner to recipient using allowance
-    pub fn transfer_from(&mut self, spender: &str, from: &str, to: &str, amount: u64) -> Result<bool> {
+    pub fn transfer_from(&mut self, spender: &str, from: &str, to: &str, amount: U256) -> Result<bool> {
         self.validate_transfer(from, to, amount)?;
         
-        let current_allowance = self.allowance(from, spender);
-        if current_allowance < amount {
+        self.check_allowance(from, spender, amount)?;
+        
+        // Perform transfer, then update allowance
+        self.transfer(from, to, amount)?;
+        self.spend_allowance(from, spender, amount);
+        Ok(true)
+    }
+    
+    /// Mints new tokens to an address (minter role only)
+    pub fn mint(&mut self, caller: &str, to: &str, amount: U256) -> Result<bool> {
+        self.roles.check_role(MINTER_ROLE, caller)?;
+        if to == ZERO_ADDRESS {
+            return Err(MediTokenError::MintToZeroAddress);
+        }
+        self.compliance.check_not_frozen(to)?;
+        
+        let new_supply = self
+            .total_supply
+            .checked_add(amount)
+            .ok_or(MediTokenError::ArithmeticOverflow)?;
+        if let Some(cap) = self.cap {
+            if new_supply > cap {
+                return Err(MediTokenError::SupplyCapExceeded {
+                    cap,
+                    requested: new_supply,
+                });
+            }
+        }
+        
+        let to_balance = self.balance_of(to);
+        let new_to_balance = to_balance
+            .checked_add(amount)
+            .ok_or(MediTokenError::ArithmeticOverflow)?;
+        
+        self.snapshots.update_account(to, to_balance);
+        self.snapshots.update_total_supply(self.total_supply);
+        self.total_supply = new_supply;
+        self.balances.insert(to.to_string(), new_to_balance);
+        
+        self.emit_event(MediTokenEvent::Transfer {
+            from: ZERO_ADDRESS.to_string(),
+            to: to.to_string(),
+            value: amount,
+        });
+        
+        Ok(true)
+    }
+    
+    /// Burns tokens held by an address (minter role only)
+    pub fn burn(&mut self, caller: &str, from: &str, amount: U256) -> Result<bool> {
+        self.roles.check_role(MINTER_ROLE, caller)?;
+        self.burn_unchecked(from, amount)
+    }
+    
+    /// Burns tokens from an address, spending the allowance `from` gave the caller
+    pub fn burn_from(&mut self, caller: &str, from: &str, amount: U256) -> Result<bool> {
+        self.roles.check_role(MINTER_ROLE, caller)?;
+        
+        self.check_allowance(from, caller, amount)?;
+        
+        self.burn_unchecked(from, amount)?;
+        self.spend_allowance(from, caller, amount);
+        Ok(true)
+    }
+    
+    /// Fails with `InsufficientAllowance` unless `owner` approved `spender` for at least `amount`
+    fn check_allowance(&self, owner: &str, spender: &str, amount: U256) -> Result<()> {
+        let allowance = self.allowance(owner, spender);
+        if allowance < amount {
             return Err(MediTokenError::InsufficientAllowance {
                 spender: spender.to_string(),
-                allowance: current_allowance,
+                allowance,
                 needed: amount,
             });
         }
+        Ok(())
+    }
+    
+    /// Lowers an allowance already checked with `check_allowance`
+    fn spend_allowance(&mut self, owner: &str, spender: &str, amount: U256) {
+        if let Some(allowance) = self
+            .allowances
+            .get_mut(owner)
+            .and_then(|allowances| allowances.get_mut(spender))
+        {
+            *allowance -= amount;
+        }
+    }
+    
+    /// Removes tokens from circulation without checking the caller
+    fn burn_unchecked(&mut self, from: &str, amount: U256) -> Result<bool> {
+        if from == ZERO_ADDRESS {
+            return Err(MediTokenError::BurnFromZeroAddress);
+        }
         
-        // Update allowance
-        self.allowances
-            .get_mut(from)
-            .unwrap()
-            .insert(spender.to_string(), current_allowance - amount);
+        let from_balance = self.balance_of(from);
+        if from_balance < amount {
+            return Err(MediTokenError::InsufficientBalance {
+                required: amount,
+                available: from_balance,
+            });
+        }
+        let new_supply = self
+            .total_supply
+            .checked_sub(amount)
+            .ok_or(MediTokenError::ArithmeticUnderflow)?;
+        
+        self.snapshots.update_account(from, from_balance);
+        self.snapshots.update_total_supply(self.total_supply);
+        self.balances.insert(from.to_string(), from_balance - amount);
+        self.total_supply = new_supply;
+        
+        self.emit_event(MediTokenEvent::Transfer {
+            from: from.to_string(),
+            to: ZERO_ADDRESS.to_string(),
+            value: amount,
+        });
+        
+        Ok(true)
+    }
+    
+    /// Checks whether an account holds a role
+    pub fn has_role(&self, role: &str, account: &str) -> bool {
+        self.roles.has_role(role, account)
+    }
+    
+    /// Gets the role that administers `role`
+    pub fn get_role_admin(&self, role: &str) -> String {
+        self.roles.get_role_admin(role)
+    }
+    
+    /// Grants a role to an account (caller must hold the role's admin role)
+    pub fn grant_role(&mut self, caller: &str, role: &str, account: &str) -> Result<bool> {
+        self.roles.check_role(&self.roles.get_role_admin(role), caller)?;
+        
+        if self.roles.grant_role_unchecked(role, account) {
+            self.emit_event(MediTokenEvent::RoleGranted {
+                role: role.to_string(),
+                account: account.to_string(),
+                sender: caller.to_string(),
+            });
+        }
+        
+        Ok(true)
+    }
+    
+    /// Revokes a role from an account (caller must hold the role's admin role)
+    pub fn revoke_role(&mut self, caller: &str, role: &str, account: &str) -> Result<bool> {
+        self.roles.check_role(&self.roles.get_role_admin(role), caller)?;
+        self.revoke_role_internal(caller, role, account);
+        Ok(true)
+    }
+    
+    /// Gives up a role held by the caller; `account` must equal the caller
+    pub fn renounce_role(&mut self, caller: &str, role: &str, account: &str) -> Result<bool> {
+        if caller != account {
+            return Err(MediTokenError::Unauthorized {
+                caller: caller.to_string(),
+            });
+        }
+        self.revoke_role_internal(caller, role, account);
+        Ok(true)
+    }
+    
+    /// Changes the admin role of `role` (caller must hold the current admin role)
+    pub fn set_role_admin(&mut self, caller: &str, role: &str, admin_role: &str) -> Result<bool> {
+        self.roles.check_role(&self.roles.get_role_admin(role), caller)?;
+        
+        let previous_admin_role = self.roles.set_role_admin_unchecked(role, admin_role);
+        self.emit_event(MediTokenEvent::RoleAdminChanged {
+            role: role.to_string(),
+            previous_admin_role,
+            new_admin_role: admin_role.to_string(),
+        });
+        
+        Ok(true)
+    }
+    
+    /// Removes a role and emits `RoleRevoked` if the account held it
+    fn revoke_role_internal(&mut self, caller: &str, role: &str, account: &str) {
+        if self.roles.revoke_role_unchecked(role, account) {
+            self.emit_event(MediTokenEvent::RoleRevoked {
+                role: role.to_string(),
+                account: account.to_string(),
+                sender: caller.to_string(),
+            });
+        }
+    }
+    
+    /// Changes or removes the supply cap (admin role only)
+    pub fn set_cap(&mut self, caller: &str, cap: Option<U256>) -> Result<bool> {
+        self.roles.check_role(DEFAULT_ADMIN_ROLE, caller)?;
+        if let Some(cap) = cap {
+            if self.total_supply > cap {
+                return Err(MediTokenError::SupplyCapExceeded {
+                    cap,
+                    requested: self.total_supply,
+                });
+            }
+        }
+        
+        self.cap = cap;
+        Ok(true)
+    }
+    
+    /// Checks whether transfers are paused
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+    
+    /// Halts all transfers (pauser role only)
+    pub fn pause(&mut self, caller: &str) -> Result<bool> {
+        self.roles.check_role(PAUSER_ROLE, caller)?;
+        if self.paused {
+            return Err(MediTokenError::Paused);
+        }
+        
+        self.paused = true;
+        self.emit_event(MediTokenEvent::Paused {
+            account: caller.to_string(),
+        });
         
-        // Perform transfer
-        self.transfer(from, to, amount)
+        Ok(true)
+    }
+    
+    /// Resumes transfers (pauser role only)
+    pub fn unpause(&mut self, caller: &str) -> Result<bool> {
+        self.roles.check_role(PAUSER_ROLE, caller)?;
+        if !self.paused {
+            return Err(MediTokenError::NotPaused);
+        }
+        
+        self.paused = false;
+        self.emit_event(MediTokenEvent::Unpaused {
+            account: caller.to_string(),
+        });
+        
+        Ok(true)
+    }
+    
+    /// Checks whether an account is frozen
+    pub fn is_frozen(&self, account: &str) -> bool {
+        self.compliance.is_frozen(account)
+    }
+    
+    /// Lists frozen accounts in sorted order
+    pub fn frozen_accounts(&self) -> Vec<String> {
+        self.compliance.frozen_accounts()
+    }
+    
+    /// Blocks an account from sending or receiving tokens (compliance role only)
+    pub fn freeze(&mut self, caller: &str, account: &str) -> Result<bool> {
+        self.roles.check_role(COMPLIANCE_ROLE, caller)?;
+        
+        if self.compliance.freeze_unchecked(account) {
+            self.emit_event(MediTokenEvent::AccountFrozen {
+                account: account.to_string(),
+                sender: caller.to_string(),
+            });
+        }
+        
+        Ok(true)
+    }
+    
+    /// Lifts a freeze (compliance role only)
+    pub fn unfreeze(&mut self, caller: &str, account: &str) -> Result<bool> {
+        self.roles.check_role(COMPLIANCE_ROLE, caller)?;
+        
+        if self.compliance.unfreeze_unchecked(account) {
+            self.emit_event(MediTokenEvent::AccountUnfrozen {
+                account: account.to_string(),
+                sender: caller.to_string(),
+            });
+        }
+        
+        Ok(true)
+    }
+    
+    /// Records the current balances and total supply, returning the snapshot id (snapshot role only)
+    pub fn snapshot(&mut self, caller: &str) -> Result<u64> {
+        self.roles.check_role(SNAPSHOT_ROLE, caller)?;
+        
+        let id = self.snapshots.take();
+        self.emit_event(MediTokenEvent::Snapshot { id });
+        
+        Ok(id)
+    }
+    
+    /// Id of the latest snapshot, 0 if none has been taken
+    pub fn current_snapshot_id(&self) -> u64 {
+        self.snapshots.current_id()
+    }
+    
+    /// Gets the balance of an address when a snapshot was taken
+    pub fn balance_of_at(&self, address: &str, snapshot_id: u64) -> Result<U256> {
+        self.snapshots.balance_at(address, snapshot_id, self.balance_of(address))
+    }
+    
+    /// Gets the total supply when a snapshot was taken
+    pub fn total_supply_at(&self, snapshot_id: u64) -> Result<U256> {
+        self.snapshots.total_supply_at(snapshot_id, self.total_supply)
     }
     
     /// Validates transfer parameters
-    fn validate_transfer(&self, from: &str, to: &str, _amount: u64) -> Result<()> {
-        if from == "0x0000000000000000000000000000000000000000" {
+    fn validate_transfer(&self, from: &str, to: &str, _amount: U256) -> Result<()> {
+        if self.paused {
+            return Err(MediTokenError::Paused);
+        }
+        if from == ZERO_ADDRESS {
             return Err(MediTokenError::TransferFromZeroAddress);
         }
-        if to == "0x0000000000000000000000000000000000000000" {
+        if to == ZERO_ADDRESS {
             return Err(MediTokenError::TransferToZeroAddress);
         }
+        self.compliance.check_not_frozen(from)?;
+        self.compliance.check_not_frozen(to)?;
         Ok(())
     }
     
     /// Validates approval parameters
     fn validate_approval(&self, owner: &str, spender: &str) -> Result<()> {
-        if owner == "0x0000000000000000000000000000000000000000" {
+        if owner == ZERO_ADDRESS {
             return Err(MediTokenError::ApproveFromZeroAddress);
         }
-        if spender == "0x0000000000000000000000000000000000000000" {
+        if spender == ZERO_ADDRESS {
             return Err(MediTokenError::ApproveToZeroAddress);
         }
         Ok(())
@@ -186,21 +566,21 @@ impl MediToken {
         &self.events
     }
     
-    /// Converts amount with decimals to raw amount
-    pub fn to_raw_amount(&self, amount: f64) -> u64 {
-        (amount * 10_f64.powi(self.decimals as i32)) as u64
+    /// Parses a decimal amount string into a raw amount using the token decimals
+    pub fn to_raw_amount(&self, amount: &str) -> Result<U256> {
+        TokenAmount::parse(amount, self.decimals).map(|amount| amount.raw())
     }
     
-    /// Converts raw amount to amount with decimals
-    pub fn from_raw_amount(&self, raw_amount: u64) -> f64 {
-        raw_amount as f64 / 10_f64.powi(self.decimals as i32)
+    /// Formats a raw amount as an exact decimal string
+    pub fn from_raw_amount(&self, raw_amount: U256) -> Result<String> {
+        TokenAmount::from_raw(raw_amount, self.decimals).map(|amount| amount.to_string())
     }
 }
 
 impl Default for MediToken {
     fn default() -> Self {
         Self::new(
-            1000 * 10_u64.pow(18), 
+            U256::from(1000) * U256::exp10(18),
             "0x0000000000000000000000000000000000000001".to_string(),
         )
     }
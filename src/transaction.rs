@@ -0,0 +1,146 @@
+//! This module is not available for WASM targets
+#![cfg(not(target_arch = "wasm32"))]
+
+use k256::ecdsa::{SigningKey, VerifyingKey};
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+use crate::error::{MediTokenError, Result};
+use crate::utils::Utils;
+
+/// Legacy (EIP-155) Ethereum transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LegacyTransaction {
+    pub nonce: U256,
+    pub gas_price: U256,
+    pub gas_limit: U256,
+    /// Recipient; `None` creates a contract
+    pub to: Option<[u8; 20]>,
+    pub value: U256,
+    pub data: Vec<u8>,
+    pub chain_id: u64,
+}
+
+/// RLP-encoded, signed transaction ready for `eth_sendRawTransaction`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedTransaction {
+    pub raw: Vec<u8>,
+    pub hash: [u8; 32],
+}
+
+impl SignedTransaction {
+    /// Raw transaction as a 0x-prefixed hex string
+    pub fn raw_hex(&self) -> String {
+        format!("0x{}", hex::encode(&self.raw))
+    }
+
+    /// Transaction hash as a 0x-prefixed hex string
+    pub fn hash_hex(&self) -> String {
+        format!("0x{}", hex::encode(self.hash))
+    }
+}
+
+impl LegacyTransaction {
+    /// Signs the transaction with EIP-155 replay protection
+    pub fn sign(&self, key: &SigningKey) -> Result<SignedTransaction> {
+        let mut unsigned = self.rlp_fields();
+        unsigned.push(rlp::encode_u256(U256::from(self.chain_id)));
+        unsigned.push(rlp::encode_bytes(&[]));
+        unsigned.push(rlp::encode_bytes(&[]));
+        let sighash = Utils::keccak256(&rlp::encode_list(&unsigned));
+
+        let (signature, recovery_id) = key
+            .sign_prehash_recoverable(&sighash)
+            .map_err(|e| MediTokenError::ContractError { message: format!("Signing failed: {}", e) })?;
+        let v = U256::from(self.chain_id) * 2 + 35 + recovery_id.to_byte();
+        let signature = signature.to_bytes();
+
+        let mut signed = self.rlp_fields();
+        signed.push(rlp::encode_u256(v));
+        signed.push(rlp::encode_u256(U256::from_big_endian(&signature[..32])));
+        signed.push(rlp::encode_u256(U256::from_big_endian(&signature[32..])));
+        let raw = rlp::encode_list(&signed);
+        let hash = Utils::keccak256(&raw);
+
+        Ok(SignedTransaction { raw, hash })
+    }
+
+    fn rlp_fields(&self) -> Vec<Vec<u8>> {
+        vec![
+            rlp::encode_u256(self.nonce),
+            rlp::encode_u256(self.gas_price),
+            rlp::encode_u256(self.gas_limit),
+            rlp::encode_bytes(self.to.as_ref().map(|to| &to[..]).unwrap_or(&[])),
+            rlp::encode_u256(self.value),
+            rlp::encode_bytes(&self.data),
+        ]
+    }
+}
+
+/// Parses a hex private key, with or without a 0x prefix
+pub fn parse_private_key(private_key: &str) -> Result<SigningKey> {
+    let bytes = hex::decode(private_key.trim().trim_start_matches("0x"))
+        .map_err(|_| MediTokenError::ContractError { message: "Private key is not valid hex".to_string() })?;
+    SigningKey::from_slice(&bytes)
+        .map_err(|_| MediTokenError::ContractError { message: "Private key is not a valid secp256k1 key".to_string() })
+}
+
+/// Gas limit for an `eth_estimateGas` result plus a safety buffer of `buffer_percent`
+pub fn buffered_gas_limit(estimate: U256, buffer_percent: u64) -> Result<U256> {
+    estimate
+        .checked_mul(U256::from(100) + U256::from(buffer_percent))
+        .map(|gas| gas / 100)
+        .ok_or_else(|| MediTokenError::ContractError {
+            message: format!("Gas estimate {} overflows with a {}% buffer", estimate, buffer_percent),
+        })
+}
+
+/// Derives the Ethereum address controlled by a signing key
+pub fn signer_address(key: &SigningKey) -> String {
+    public_key_address(&VerifyingKey::from(key))
+}
+
+/// Derives the Ethereum address of a public key
+pub fn public_key_address(key: &VerifyingKey) -> String {
+    let public_key = key.to_encoded_point(false);
+    let hash = Utils::keccak256(&public_key.as_bytes()[1..]);
+    format!("0x{}", hex::encode(&hash[12..]))
+}
+
+/// Minimal Recursive Length Prefix encoding
+mod rlp {
+    use primitive_types::U256;
+
+    pub fn encode_bytes(bytes: &[u8]) -> Vec<u8> {
+        if bytes.len() == 1 && bytes[0] < 0x80 {
+            return bytes.to_vec();
+        }
+        let mut out = encode_length(bytes.len(), 0x80);
+        out.extend_from_slice(bytes);
+        out
+    }
+
+    pub fn encode_u256(value: U256) -> Vec<u8> {
+        let mut buf = [0u8; 32];
+        value.to_big_endian(&mut buf);
+        let start = buf.iter().position(|b| *b != 0).unwrap_or(32);
+        encode_bytes(&buf[start..])
+    }
+
+    pub fn encode_list(items: &[Vec<u8>]) -> Vec<u8> {
+        let payload: Vec<u8> = items.concat();
+        let mut out = encode_length(payload.len(), 0xc0);
+        out.extend(payload);
+        out
+    }
+
+    fn encode_length(len: usize, offset: u8) -> Vec<u8> {
+        if len < 56 {
+            return vec![offset + len as u8];
+        }
+        let len_bytes = len.to_be_bytes();
+        let start = len_bytes.iter().position(|b| *b != 0).unwrap_or(len_bytes.len() - 1);
+        let mut out = vec![offset + 55 + (len_bytes.len() - start) as u8];
+        out.extend_from_slice(&len_bytes[start..]);
+        out
+    }
+}
@@ -1,7 +1,9 @@
 //! This module is not available for WASM targets
 #![cfg(not(target_arch = "wasm32"))]
 
+use crate::amount::TokenAmount;
 use crate::error::{MediTokenError, Result};
+use primitive_types::U256;
 use k256::ecdsa::{SigningKey, VerifyingKey};
 use rand::rngs::OsRng;
 use sha3::{Digest, Keccak256};
@@ -34,13 +36,15 @@ impl Utils {
     }
     
     /// Converts wei to ether (considering 18 decimals)
-    pub fn wei_to_ether(wei: u64) -> f64 {
-        wei as f64 / 1e18
+    pub fn wei_to_ether(wei: U256) -> String {
+        TokenAmount::from_raw(wei, 18)
+            .expect("18 decimals are within MAX_DECIMALS")
+            .to_string()
     }
     
     /// Converts ether to wei (considering 18 decimals)
-    pub fn ether_to_wei(ether: f64) -> u64 {
-        (ether * 1e18) as u64
+    pub fn ether_to_wei(ether: &str) -> Result<U256> {
+        TokenAmount::parse(ether, 18).map(|amount| amount.raw())
     }
     
     /// Calculates Keccak256 hash of input
@@ -51,13 +55,13 @@ impl Utils {
     }
     
     /// Validates that an amount doesn't cause overflow
-    pub fn check_overflow(a: u64, b: u64) -> Result<u64> {
+    pub fn check_overflow(a: U256, b: U256) -> Result<U256> {
         a.checked_add(b)
             .ok_or(MediTokenError::ArithmeticOverflow)
     }
     
     /// Validates that an amount doesn't cause underflow
-    pub fn check_underflow(a: u64, b: u64) -> Result<u64> {
+    pub fn check_underflow(a: U256, b: U256) -> Result<U256> {
         a.checked_sub(b)
             .ok_or(MediTokenError::ArithmeticUnderflow)
     }
@@ -0,0 +1,258 @@
+//! Vesting grants: tokens set aside for a beneficiary that unlock over time, linearly after
+//! a cliff or at fixed milestones, and that the grantor may revoke if the grant allows it.
+//!
+//! This module is not available for WASM targets
+#![cfg(not(target_arch = "wasm32"))]
+
+use serde::{Deserialize, Serialize};
+use primitive_types::U256;
+use crate::clock::{Clock, SystemClock};
+use crate::error::{MediTokenError, Result};
+use crate::records::{EventLog, Records};
+use crate::token::MediToken;
+
+/// An amount that unlocks `offset` seconds after the grant start
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Milestone {
+    pub offset: u64,
+    pub amount: U256,
+}
+
+/// How a grant unlocks after its cliff
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VestingSchedule {
+    /// Unlocks in proportion to the time elapsed since start, fully at start + duration
+    Linear,
+    /// Unlocks each milestone amount once its offset has passed
+    Milestones(Vec<Milestone>),
+}
+
+/// Terms of a new grant; times are in seconds
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GrantTerms {
+    pub beneficiary: String,
+    pub total: U256,
+    pub start: u64,
+    /// Nothing unlocks before start + cliff
+    pub cliff: u64,
+    /// Length of a linear schedule
+    pub duration: u64,
+    pub schedule: VestingSchedule,
+    /// Whether the grantor may reclaim the unvested part
+    pub revocable: bool,
+}
+
+/// A funded grant and how much of it has been paid out
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Grant {
+    pub id: u64,
+    pub grantor: String,
+    pub terms: GrantTerms,
+    pub released: U256,
+    /// Time of revocation; vesting stops there
+    pub revoked_at: Option<u64>,
+}
+
+impl Grant {
+    /// Amount unlocked at time `now`, including what was already released
+    pub fn vested_amount(&self, now: u64) -> U256 {
+        let terms = &self.terms;
+        let now = self.revoked_at.map_or(now, |revoked_at| now.min(revoked_at));
+        if now < terms.start.saturating_add(terms.cliff) {
+            return U256::zero();
+        }
+
+        let elapsed = now - terms.start;
+        match &terms.schedule {
+            VestingSchedule::Linear => {
+                if elapsed >= terms.duration {
+                    return terms.total;
+                }
+                let vested = terms.total.full_mul(U256::from(elapsed)) / U256::from(terms.duration);
+                U256::try_from(vested).expect("vested amount is below the total")
+            }
+            VestingSchedule::Milestones(milestones) => milestones
+                .iter()
+                .filter(|milestone| milestone.offset <= elapsed)
+                .fold(U256::zero(), |vested, milestone| vested + milestone.amount),
+        }
+    }
+}
+
+/// Events emitted by the vesting subsystem
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VestingEvent {
+    GrantCreated {
+        id: u64,
+        grantor: String,
+        beneficiary: String,
+        total: U256,
+    },
+    TokensReleased {
+        id: u64,
+        beneficiary: String,
+        amount: U256,
+    },
+    GrantRevoked {
+        id: u64,
+        refunded: U256,
+    },
+}
+
+/// Time-locked grants paid out of `MediToken`.
+/// Creating a grant moves its whole total to `address`; releases and revocation refunds
+/// are transfers back out of that account.
+#[derive(Debug, Clone)]
+pub struct Vesting<C: Clock = SystemClock> {
+    pub address: String,
+    clock: C,
+    grants: Records<Grant>,
+    events: EventLog<VestingEvent>,
+}
+
+impl Vesting<SystemClock> {
+    /// Creates a vesting account that follows the system clock
+    pub fn new(address: String) -> Self {
+        Self::with_clock(address, SystemClock)
+    }
+}
+
+impl<C: Clock> Vesting<C> {
+    /// Creates a vesting account that reads time from `clock`
+    pub fn with_clock(address: String, clock: C) -> Self {
+        Self {
+            address,
+            clock,
+            grants: Records::new(),
+            events: EventLog::new(),
+        }
+    }
+
+    /// Moves `terms.total` from the grantor into vesting and returns the grant id
+    pub fn create_grant(&mut self, token: &mut MediToken, grantor: &str, terms: GrantTerms) -> Result<u64> {
+        validate_terms(&terms)?;
+        token.transfer(grantor, &self.address, terms.total)?;
+
+        let (beneficiary, total) = (terms.beneficiary.clone(), terms.total);
+        let id = self.grants.insert_with(|id| Grant {
+            id,
+            grantor: grantor.to_string(),
+            terms,
+            released: U256::zero(),
+            revoked_at: None,
+        });
+        self.events.emit(VestingEvent::GrantCreated {
+            id,
+            grantor: grantor.to_string(),
+            beneficiary,
+            total,
+        });
+        Ok(id)
+    }
+
+    /// Vested amount of a grant that has not been released yet
+    pub fn releasable(&self, id: u64) -> Result<U256> {
+        let grant = self.grant(id)?;
+        Ok(grant.vested_amount(self.clock.now()) - grant.released)
+    }
+
+    /// Pays the releasable amount to the beneficiary and returns it
+    pub fn release(&mut self, token: &mut MediToken, id: u64) -> Result<U256> {
+        let amount = self.releasable(id)?;
+        if amount.is_zero() {
+            return Ok(amount);
+        }
+
+        let beneficiary = self.grants[id].terms.beneficiary.clone();
+        token.transfer(&self.address, &beneficiary, amount)?;
+        self.grants.get_mut(id).expect("grant exists").released += amount;
+
+        self.events.emit(VestingEvent::TokensReleased { id, beneficiary, amount });
+        Ok(amount)
+    }
+
+    /// Stops a revocable grant and returns its unvested part to the grantor (grantor only).
+    /// What had vested stays releasable to the beneficiary.
+    pub fn revoke(&mut self, token: &mut MediToken, caller: &str, id: u64) -> Result<U256> {
+        let grant = self.grant(id)?;
+        if grant.grantor != caller {
+            return Err(MediTokenError::Unauthorized {
+                caller: caller.to_string(),
+            });
+        }
+        if !grant.terms.revocable {
+            return Err(vesting_error(&format!("Grant {} is not revocable", id)));
+        }
+        if grant.revoked_at.is_some() {
+            return Err(vesting_error(&format!("Grant {} is already revoked", id)));
+        }
+
+        let now = self.clock.now();
+        let refunded = grant.terms.total - grant.vested_amount(now);
+        if !refunded.is_zero() {
+            token.transfer(&self.address, caller, refunded)?;
+        }
+        self.grants.get_mut(id).expect("grant exists").revoked_at = Some(now);
+
+        self.events.emit(VestingEvent::GrantRevoked { id, refunded });
+        Ok(refunded)
+    }
+
+    /// Gets a grant by id
+    pub fn grant(&self, id: u64) -> Result<&Grant> {
+        self.grants
+            .get(id)
+            .ok_or(MediTokenError::GrantNotFound { id })
+    }
+
+    /// Gets the grants paying out to `beneficiary`
+    pub fn grants_for(&self, beneficiary: &str) -> Vec<&Grant> {
+        self.grants
+            .values()
+            .filter(|grant| grant.terms.beneficiary == beneficiary)
+            .collect()
+    }
+
+    /// Gets the clock used for vesting
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Gets all events
+    pub fn get_events(&self) -> &[VestingEvent] {
+        self.events.all()
+    }
+}
+
+fn validate_terms(terms: &GrantTerms) -> Result<()> {
+    if terms.total.is_zero() {
+        return Err(MediTokenError::InvalidAmount {
+            amount: terms.total.to_string(),
+        });
+    }
+    match &terms.schedule {
+        VestingSchedule::Linear => {
+            if terms.cliff > terms.duration {
+                return Err(vesting_error("Cliff must not be longer than the duration"));
+            }
+        }
+        VestingSchedule::Milestones(milestones) => {
+            let mut sum = U256::zero();
+            for milestone in milestones {
+                sum = sum
+                    .checked_add(milestone.amount)
+                    .ok_or(MediTokenError::ArithmeticOverflow)?;
+            }
+            if milestones.is_empty() || sum != terms.total {
+                return Err(vesting_error("Milestone amounts must add up to the total"));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn vesting_error(message: &str) -> MediTokenError {
+    MediTokenError::VestingError {
+        message: message.to_string(),
+    }
+}